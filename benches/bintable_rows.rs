@@ -0,0 +1,121 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use fitsrs::{Fits, HDU};
+
+/// Number of repetitions of the (id, magnitude, name) triplet of fields in a row
+const NUM_FIELD_GROUPS: usize = 20;
+const NUM_ROWS: usize = 10_000;
+/// Byte size of the `1K`, `1E` and `16A` fields
+const GROUP_BYTE_SIZE: usize = 8 + 4 + 16;
+
+fn card(text: &str) -> [u8; 80] {
+    let mut card = [b' '; 80];
+    card[..text.len()].copy_from_slice(text.as_bytes());
+    card
+}
+
+/// Concatenate the cards of a header padded with spaces
+fn header(cards: &[[u8; 80]]) -> Vec<u8> {
+    let mut bytes = cards.concat();
+    bytes.resize(bytes.len().div_ceil(2880) * 2880, b' ');
+    bytes
+}
+
+/// A catalog whose rows mix integer, float and string fields
+fn mock_wide_table() -> Vec<u8> {
+    let row_byte_size = NUM_FIELD_GROUPS * GROUP_BYTE_SIZE;
+
+    let mut buf = header(&[
+        card("SIMPLE  =                    T"),
+        card("BITPIX  =                    8"),
+        card("NAXIS   =                    0"),
+        card("END"),
+    ]);
+
+    let mut cards = vec![
+        card("XTENSION= 'BINTABLE'"),
+        card("BITPIX  =                    8"),
+        card("NAXIS   =                    2"),
+        card(&format!("NAXIS1  = {row_byte_size:>20}")),
+        card(&format!("NAXIS2  = {NUM_ROWS:>20}")),
+        card("PCOUNT  =                    0"),
+        card("GCOUNT  =                    1"),
+        card(&format!("TFIELDS = {:>20}", 3 * NUM_FIELD_GROUPS)),
+    ];
+    for group in 0..NUM_FIELD_GROUPS {
+        cards.push(card(&format!("TTYPE{:<3}= 'ID{group}'", 3 * group + 1)));
+        cards.push(card(&format!("TFORM{:<3}= '1K      '", 3 * group + 1)));
+        cards.push(card(&format!("TTYPE{:<3}= 'MAG{group}'", 3 * group + 2)));
+        cards.push(card(&format!("TFORM{:<3}= '1E      '", 3 * group + 2)));
+        cards.push(card(&format!("TTYPE{:<3}= 'NAME{group}'", 3 * group + 3)));
+        cards.push(card(&format!("TFORM{:<3}= '16A     '", 3 * group + 3)));
+    }
+    cards.push(card("END"));
+    buf.extend(header(&cards));
+
+    let mut data = Vec::with_capacity(row_byte_size * NUM_ROWS);
+    for row in 0..NUM_ROWS {
+        for group in 0..NUM_FIELD_GROUPS {
+            data.extend((row as i64).to_be_bytes());
+            data.extend((group as f32 * 0.5).to_be_bytes());
+            data.extend(format!("{:<16}", format!("star {row}")).as_bytes());
+        }
+    }
+    data.resize(data.len().div_ceil(2880) * 2880, 0);
+    buf.extend(data);
+
+    buf
+}
+
+fn criterion_benchmark_bintable_rows(c: &mut Criterion) {
+    let buf = mock_wide_table();
+
+    let mut group = c.benchmark_group("bintable rows");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    group.bench_function("iterate over the rows of a wide table", |b| {
+        b.iter(|| {
+            let mut hdu_list = Fits::from_bytes(&buf);
+            let _primary = hdu_list.next().unwrap().unwrap();
+            let Some(Ok(HDU::XBinaryTable(hdu))) = hdu_list.next() else {
+                panic!("expected a binary table");
+            };
+
+            hdu_list
+                .get_data(&hdu)
+                .table_data()
+                .row_iter()
+                .map(|row| row.len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("decode all the fields of a wide table", |b| {
+        b.iter(|| {
+            let mut hdu_list = Fits::from_bytes(&buf);
+            let _primary = hdu_list.next().unwrap().unwrap();
+            let Some(Ok(HDU::XBinaryTable(hdu))) = hdu_list.next() else {
+                panic!("expected a binary table");
+            };
+
+            hdu_list.get_data(&hdu).table_data().count()
+        })
+    });
+    group.bench_function("decode 2 fields of a wide table", |b| {
+        b.iter(|| {
+            let mut hdu_list = Fits::from_bytes(&buf);
+            let _primary = hdu_list.next().unwrap().unwrap();
+            let Some(Ok(HDU::XBinaryTable(hdu))) = hdu_list.next() else {
+                panic!("expected a binary table");
+            };
+
+            hdu_list
+                .get_data(&hdu)
+                .table_data()
+                .select(&["ID0", "MAG12"])
+                .unwrap()
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark_bintable_rows);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fitsrs::It;
+use std::io::Cursor;
+
+fn criterion_benchmark_byte_swap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("big endian to native");
+
+    for num_values in [1 << 10, 1 << 16, 1 << 20] {
+        let buf = (0..num_values)
+            .flat_map(|i| (i as f32).to_be_bytes())
+            .collect::<Vec<_>>();
+        group.throughput(Throughput::Bytes(buf.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("iterate", num_values), &buf, |b, buf| {
+            b.iter(|| {
+                let it = It::<_, f32>::new(Cursor::new(&buf[..]), buf.len() as u64);
+                it.fold(0.0, |acc, v| acc + v)
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("read_into", num_values), &buf, |b, buf| {
+            let mut values = vec![0.0_f32; num_values];
+            b.iter(|| {
+                let mut it = It::<_, f32>::new(Cursor::new(&buf[..]), buf.len() as u64);
+                it.read_into(&mut values).unwrap()
+            })
+        });
+
+        // Only the swap, without any reader
+        group.bench_with_input(
+            BenchmarkId::new("swap_bytes", num_values),
+            &buf,
+            |b, buf| {
+                let mut values = vec![0_u32; num_values];
+                b.iter(|| {
+                    for (v, bytes) in values.iter_mut().zip(buf.chunks_exact(4)) {
+                        *v = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    }
+                    values[num_values - 1]
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark_byte_swap);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use fitsrs::FITSFile;
+use fitsrs::{FITSFile, Fits};
 
 fn open_headers(filename: &str) {
     let hdu_list = FITSFile::open(filename).expect("Can find fits file");
@@ -58,6 +58,36 @@ fn criterion_benchmark_parse_only_headers(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, criterion_benchmark_parse_only_headers);
+fn criterion_benchmark_parse_primary_headers(c: &mut Criterion) {
+    let cards: [&[u8; 80]; 8] = [
+        b"SIMPLE  =                    T / conforms to FITS standard                      ",
+        b"BITPIX  =                  -32 / array data type                                ",
+        b"NAXIS   =                    2 / number of array dimensions                     ",
+        b"NAXIS1  =                  512                                                  ",
+        b"NAXIS2  =                  512                                                  ",
+        b"OBJECT  = 'M31     '                                                            ",
+        b"DATE-OBS= '2021-02-28T23:59:59.999'                                             ",
+        b"END                                                                             ",
+    ];
+    let mut buf = vec![b' '; 2880];
+    for (i, card) in cards.iter().enumerate() {
+        buf[i * 80..(i + 1) * 80].copy_from_slice(*card);
+    }
+
+    c.bench_function("parse 10k primary headers", |b| {
+        b.iter(|| {
+            for _ in 0..10_000 {
+                let (_, header) = Fits::primary_header(&buf).unwrap();
+                criterion::black_box(header);
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark_parse_only_headers,
+    criterion_benchmark_parse_primary_headers
+);
 
 criterion_main!(benches);
@@ -6,7 +6,7 @@ use fitsrs::{
     Fits, HDU, fits,
     hdu::header::{
         Xtension,
-        extension::{asciitable::AsciiTable, bintable::BinTable, image::Image},
+        extension::{asciitable::AsciiTable, bintable::BinTable, image::Image, unknown::Unknown},
     },
 };
 
@@ -36,6 +36,7 @@ fn print_hdu_struct(i: usize, hdu: HDU) -> Result<(), Box<dyn Error>> {
         HDU::XImage(img) => print_imghdu_struct(img),
         HDU::XBinaryTable(bintable) => print_bintablehdu_struct(bintable),
         HDU::XASCIITable(asciitable) => print_ascisstablehdu_struct(asciitable),
+        HDU::XUnknown(unknown) => print_unknownhdu_struct(unknown),
     }
 }
 
@@ -67,6 +68,12 @@ fn print_ascisstablehdu_struct(hdu: fits::HDU<AsciiTable>) -> Result<(), Box<dyn
     Ok(())
 }
 
+fn print_unknownhdu_struct(hdu: fits::HDU<Unknown>) -> Result<(), Box<dyn Error>> {
+    print_hdu_type(hdu.get_header().get_xtension().get_name());
+    print_data_struct(&hdu);
+    Ok(())
+}
+
 fn print_hdu_type(hdu_type: &str) {
     println!(" * HDU type: {}", hdu_type);
 }
@@ -86,7 +93,7 @@ where
 fn print_img_header(img: &Image) {
     println!(
         " * HEAD naxis: {}; bitpix : {:?}; dimensions: {}.",
-        img.get_naxis(),
+        img.get_naxis().len(),
         img.get_bitpix(),
         img.get_naxis()
             .iter()
@@ -0,0 +1,23 @@
+//! Parse arbitrary bytes as cards and as a FITS file. None of them must panic.
+//!
+//! Run with `cargo +nightly fuzz run parse_hdus`
+#![no_main]
+
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use fitsrs::card::{Card, CardBuf};
+use fitsrs::Fits;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Each 80 bytes card is tokenized independently
+    for card in data.chunks_exact(80) {
+        let card = <&CardBuf>::try_from(card).unwrap();
+        let _ = Card::try_from(card);
+    }
+
+    // Only the headers are parsed, data units are skipped
+    let mut hdu_list = Fits::from_reader(Cursor::new(data));
+    while let Some(Ok(_)) = hdu_list.next() {}
+});
@@ -1,11 +1,12 @@
 use std::pin::Pin;
 
-use crate::card::Card;
+use crate::card::{Card, CardBuf};
 use crate::hdu;
 use crate::hdu::data::AsyncDataBufRead;
 use crate::hdu::header::extension::asciitable::AsciiTable;
 use crate::hdu::header::extension::bintable::BinTable;
 use crate::hdu::header::extension::image::Image;
+use crate::hdu::header::extension::unknown::Unknown;
 use crate::hdu::header::extension::Xtension;
 use crate::hdu::header::Header;
 use futures::{Future, Stream};
@@ -50,6 +51,7 @@ where
     R: AsyncDataBufRead<'a, Image>
         + AsyncDataBufRead<'a, AsciiTable>
         + AsyncDataBufRead<'a, BinTable>
+        + AsyncDataBufRead<'a, Unknown>
         + 'a,
 {
     /// Returns a boolean to know if we are at EOF
@@ -67,7 +69,7 @@ where
         // 2. We are at the end of the real data. As FITS standard stores data in block of 2880 bytes
         // we must read until the next block of data to get the location of the next HDU
 
-        let is_remaining_bytes = (self.num_bytes_in_cur_hdu % 2880) > 0;
+        let is_remaining_bytes = !self.num_bytes_in_cur_hdu.is_multiple_of(2880);
         // Skip the remaining bytes to set the reader where a new HDU begins
         if is_remaining_bytes {
             let mut block_mem_buf: [u8; 2880] = [0; 2880];
@@ -117,6 +119,7 @@ where
     R: AsyncDataBufRead<'a, Image>
         + AsyncDataBufRead<'a, BinTable>
         + AsyncDataBufRead<'a, AsciiTable>
+        + AsyncDataBufRead<'a, Unknown>
         + 'a,
 {
     type Item = Result<hdu::AsyncHDU, Error>;
@@ -182,6 +185,10 @@ where
                             let xtension = h.get_header().get_xtension();
                             xtension.get_num_bytes_data_block() as usize
                         }
+                        hdu::AsyncHDU::XUnknown(h) => {
+                            let xtension = h.get_header().get_xtension();
+                            xtension.get_num_bytes_data_block() as usize
+                        }
                     };
 
                     self.num_remaining_bytes_in_cur_hdu = self.num_bytes_in_cur_hdu;
@@ -226,7 +233,7 @@ where
         let header = Header::parse(cards)?;
         /* 2. Skip the next bytes to a new 2880 multiple of bytes
         This is where the data block should start */
-        let is_remaining_bytes = ((*num_bytes_read) % 2880) > 0;
+        let is_remaining_bytes = !(*num_bytes_read).is_multiple_of(2880);
 
         // Skip the remaining bytes to set the reader where a new HDU begins
         if is_remaining_bytes {
@@ -246,6 +253,13 @@ where
         Ok(Self { header })
     }
 
+    /// Keep the bytes the cards of the header have been parsed from
+    pub(crate) fn with_raw_cards(self, raw_cards: Vec<CardBuf>) -> Self {
+        Self {
+            header: self.header.with_raw_cards(raw_cards),
+        }
+    }
+
     pub fn get_header(&self) -> &Header<X> {
         &self.header
     }
@@ -6,6 +6,7 @@ pub type Keyword = [u8; 8];
 /// Holds 80 bytes of ASCII characters, i.e. one line in a FITS compliant file.
 pub type CardBuf = [u8; 80];
 
+use serde::de::value::SeqDeserializer;
 use serde::de::IntoDeserializer;
 use serde::{forward_to_deserialize_any, Deserializer, Serialize};
 
@@ -30,7 +31,8 @@ pub enum Card {
     History(String),
     /// A keyword card, with a value and optional comment, using the HIERARCH convention.
     Hierarch { name: String, value: Value },
-    /// An empty line at the end of the header block providing header space for adding new cards or used for aesthetic purposes.
+    /// A blank card, i.e. whose keyword and text are all spaces. It can be found anywhere in the header
+    /// providing header space for adding new cards or used for aesthetic purposes.
     Space,
     /// End marker.
     End,
@@ -43,23 +45,37 @@ impl Card {
     /// [string](Value::String) value or a [hierarch](Card::Hierarch) card with a string](Value::String),
     /// or a [continuation](Card::Continuation) where the string value ends with an ampersand,
     /// i.e. the `&` character.
+    ///
+    /// A card whose comment ends with an ampersand is also continued, its comment being completed
+    /// by the comment of the following [continuation](Card::Continuation) card.
     pub fn continued(&self) -> bool {
         match self {
-            Card::Value {
-                value: Value::String { value: s, .. },
-                ..
-            }
-            | Card::Hierarch {
-                value: Value::String { value: s, .. },
-                ..
+            Card::Value { value, .. } | Card::Hierarch { value, .. } => value.continued(),
+            Card::Continuation { string, comment } => {
+                string.as_deref().is_some_and(|s| s.ends_with('&')) || comment_continued(comment)
             }
-            | Card::Continuation {
-                string: Some(s), ..
-            } => s.ends_with('&'),
             _ => false,
         }
     }
 
+    /// Returns the keyword of the card, i.e. the trimmed content of its keyword field.
+    ///
+    /// [Comment](Card::Comment) cards are given the `COMMENT` keyword even if their keyword field
+    /// is blank and [Space](Card::Space) cards return an empty string.
+    /// For [hierarch](Card::Hierarch) cards, the name without the `HIERARCH` prefix is returned.
+    pub fn keyword(&self) -> &str {
+        match self {
+            Card::Value { name, .. } | Card::Hierarch { name, .. } => name,
+            Card::Continuation { .. } => "CONTINUE",
+            Card::Xtension { .. } => "XTENSION",
+            Card::Comment(_) => "COMMENT",
+            Card::History(_) => "HISTORY",
+            Card::Space => "",
+            Card::End => "END",
+            Card::Undefined(card) => card.get(..8).unwrap_or(card).trim(),
+        }
+    }
+
     /// Append a [Card::Continuation] to this [Card] if it is a [Card::Value] of
     /// type [Value::String] and if the string is
     /// [continued][Self::continued()], else panics.
@@ -116,6 +132,117 @@ impl Card {
         self.append(&r);
         self
     }
+
+    /// Write the card into its 80 bytes, the values following the fixed format of the standard.
+    ///
+    /// Numbers and logicals are right justified in columns 11-30 and strings start in column 11.
+    /// Comments not fitting in the card are truncated. An error is returned if the value does not
+    /// fit in the card, long strings not being split into [continuation](Card::Continuation) cards.
+    /// ```
+    ///     # use fitsrs::card::{Card, Value};
+    ///     # use std::convert::TryFrom;
+    ///     let card = Card::Value {
+    ///         name: "EXPTIME".to_owned(),
+    ///         value: Value::Float { value: 30.0, comment: Some("[s] exposure".to_owned()) },
+    ///     };
+    ///     let buf = card.to_bytes().unwrap();
+    ///     assert_eq!(&buf[..], &b"EXPTIME =                 30.0 / [s] exposure                                   "[..]);
+    ///     assert_eq!(Card::try_from(&buf).unwrap().keyword(), "EXPTIME");
+    /// ```
+    pub fn to_bytes(&self) -> Result<CardBuf, Error> {
+        let text = match self {
+            Card::Value { name, value } => {
+                if name.len() > 8 {
+                    return Err(Error::DynamicError(format!(
+                        "The keyword {name} exceeds 8 characters"
+                    )));
+                }
+                format!("{name:<8}= {}", format_value(value)?)
+            }
+            Card::Hierarch { name, value } => {
+                format!(
+                    "HIERARCH {} = {}",
+                    name.replace('.', " "),
+                    format_value(value)?.trim_start()
+                )
+            }
+            Card::Xtension { x, comment } => format!(
+                "XTENSION= {}",
+                with_comment(format_string(x.as_str())?, comment)
+            ),
+            Card::Continuation { string, comment } => format!(
+                "CONTINUE  {}",
+                with_comment(format_string(string.as_deref().unwrap_or(""))?, comment)
+            ),
+            Card::Comment(text) => format!("COMMENT {text}"),
+            Card::History(text) => format!("HISTORY {text}"),
+            Card::Space => String::new(),
+            Card::End => "END".to_owned(),
+            Card::Undefined(card) => card.clone(),
+        };
+
+        if !text.is_ascii() {
+            return Err(Error::StaticError(
+                "A card must only contain ASCII characters",
+            ));
+        }
+        let mut buf = [b' '; 80];
+        let len = text.len().min(80);
+        buf[..len].copy_from_slice(&text.as_bytes()[..len]);
+        Ok(buf)
+    }
+}
+
+/// Format a value and its comment from column 11 of a card
+fn format_value(value: &Value) -> Result<String, Error> {
+    let (value, comment) = match value {
+        Value::Integer { value, comment } => (format!("{value:>20}"), comment),
+        Value::Float { value, comment } => (format!("{:>20}", format_float(*value)?), comment),
+        Value::Logical { value, comment } => {
+            (format!("{:>20}", if *value { "T" } else { "F" }), comment)
+        }
+        Value::String { value, comment } => (format_string(value)?, comment),
+        Value::Complex { re, im, comment } => (
+            format!(
+                "{:>20}",
+                format!("({}, {})", format_float(*re)?, format_float(*im)?)
+            ),
+            comment,
+        ),
+        Value::Undefined => return Ok(String::new()),
+        Value::Invalid(value) => return Ok(value.clone()),
+    };
+
+    Ok(with_comment(value, comment))
+}
+
+/// Append a comment after the `/` separator
+fn with_comment(value: String, comment: &Option<String>) -> String {
+    match comment {
+        // The comment usually keeps the space following the separator
+        Some(comment) if comment.starts_with(' ') => format!("{value} /{comment}"),
+        Some(comment) => format!("{value} / {comment}"),
+        None => value,
+    }
+}
+
+/// Format a float so that it is read back as a float, i.e. with a decimal point or an exponent
+fn format_float(value: f64) -> Result<String, Error> {
+    if !value.is_finite() {
+        return Err(Error::StaticError("A card value cannot be NaN or infinite"));
+    }
+    Ok(format!("{value:?}").to_uppercase())
+}
+
+/// Quote a string, escaping its quotes and padding it to 8 characters
+fn format_string(value: &str) -> Result<String, Error> {
+    let quoted = format!("'{:<8}'", value.replace('\'', "''"));
+    if quoted.len() > 70 {
+        return Err(Error::DynamicError(format!(
+            "The string '{value}' does not fit in a card"
+        )));
+    }
+    Ok(quoted)
 }
 
 fn append_string(value: &mut String, v: &Option<String>) {
@@ -136,13 +263,31 @@ fn append_string(value: &mut String, v: &Option<String>) {
     }
 }
 
-fn append_comment(comment: &mut String, c: &Option<String>) {
-    if let Some(c) = c {
-        comment.push('\n');
-        comment.push_str(c);
+fn append_comment(comment: &mut Option<String>, c: &Option<String>) {
+    match (comment.as_mut(), c) {
+        (Some(comment), Some(c)) => {
+            if comment.trim_end().ends_with('&') {
+                // The comment is split with an ampersand, e.g. a long file path
+                comment.truncate(comment.trim_end().len() - 1);
+                comment.push_str(c.trim_start());
+            } else {
+                comment.push('\n');
+                comment.push_str(c);
+            }
+        }
+        (None, Some(c)) => *comment = Some(c.clone()),
+        _ => (),
     }
 }
 
+/// Returns `true` if the comment ends with an ampersand, i.e. it continues in the comment of the
+/// next [continuation](Card::Continuation) card
+fn comment_continued(comment: &Option<String>) -> bool {
+    comment
+        .as_deref()
+        .is_some_and(|c| c.trim_end().ends_with('&'))
+}
+
 impl TryFrom<&CardBuf> for Card {
     type Error = Error;
 
@@ -174,7 +319,7 @@ impl TryFrom<&CardBuf> for Card {
 
 fn parse_extension(buf: &[u8; 80]) -> Result<Card, Error> {
     let (value, comment) = split_value_and_comment(&buf[10..])?;
-    if value.starts_with("'") && value.ends_with("'") {
+    if value.len() > 1 && value.starts_with("'") && value.ends_with("'") {
         let end = value.len() - 1;
         let x = value[1..end].trim_ascii().parse()?;
         Ok(Card::Xtension { x, comment })
@@ -231,7 +376,7 @@ fn parse_value(buf: &[u8]) -> Result<Value, Error> {
                 value: false,
                 comment: c,
             }),
-            '(' => Err(Error::StaticError("Complex values not yet supported")),
+            '(' => parse_complex(&v, c),
             '0'..='9' | '-' | '+' | '.' => parse_number(v, c),
             _ => Ok(Value::Invalid(String::from_utf8_lossy(buf).into_owned())),
         }
@@ -271,11 +416,36 @@ fn parse_number(v: String, c: Option<String>) -> Result<Value, Error> {
     }
 }
 
+/// Parse a [Value::Complex] written as `(re, im)`, the real and imaginary parts being integers or floats.
+///
+/// FITSv4, sections 4.2.5 Complex integer number and 4.2.6 Complex floating-point number
+fn parse_complex(v: &str, c: Option<String>) -> Result<Value, Error> {
+    let parse_part = |s: &str| {
+        let s = s.trim();
+        s.parse::<f64>()
+            .or_else(|_| s.replace('D', "E").parse::<f64>())
+            .ok()
+    };
+
+    let parts = v
+        .strip_prefix('(')
+        .and_then(|v| v.strip_suffix(')'))
+        .and_then(|v| v.split_once(','))
+        .and_then(|(re, im)| Some((parse_part(re)?, parse_part(im)?)));
+
+    if let Some((re, im)) = parts {
+        Ok(Value::Complex { re, im, comment: c })
+    } else {
+        Ok(Value::Invalid(v.to_owned()))
+    }
+}
+
 /// FITSv4 section 4.2.1.1: `null` string contains no space, `empty` string is one or more space
 /// trimmed to one space.
 fn parse_string(s: String) -> Result<String, Error> {
     let start_quote = s.starts_with("'");
-    let end_quote = s.ends_with("'");
+    // A lone quote is a starting quote without its ending one
+    let end_quote = s.len() > 1 && s.ends_with("'");
     let value: &str = match (start_quote, end_quote) {
         (true, true) => &s[1..s.len() - 1], // string enclosed in single quotes
         (false, false) => &s,               // comment string has no quotes
@@ -436,6 +606,12 @@ pub enum Value {
         value: String,
         comment: Option<String>,
     },
+    /// FITSv4, sections 4.2.5 Complex integer number and 4.2.6 Complex floating-point number
+    Complex {
+        re: f64,
+        im: f64,
+        comment: Option<String>,
+    },
     /// Value field consisting entirely of blank space.
     ///
     /// FITSv4, section 4.1.2.3. Value/comment (Bytes 11 through 80)
@@ -453,16 +629,20 @@ impl Value {
             Value::Integer { comment, .. }
             | Value::Float { comment, .. }
             | Value::String { comment, .. }
-            | Value::Logical { comment, .. } => parse_unit(comment),
+            | Value::Logical { comment, .. }
+            | Value::Complex { comment, .. } => parse_unit(comment),
             _ => None,
         }
     }
 
     pub(crate) fn continued(&self) -> bool {
-        if let Value::String { value, .. } = self {
-            value.ends_with('&')
-        } else {
-            false
+        match self {
+            Value::String { value, comment } => value.ends_with('&') || comment_continued(comment),
+            Value::Integer { comment, .. }
+            | Value::Float { comment, .. }
+            | Value::Logical { comment, .. }
+            | Value::Complex { comment, .. } => comment_continued(comment),
+            _ => false,
         }
     }
 }
@@ -479,6 +659,9 @@ impl<'de> Deserializer<'de> for &'de Value {
             Value::Float { value, comment: _ } => visitor.visit_f64(*value),
             Value::Logical { value, comment: _ } => visitor.visit_bool(*value),
             Value::String { value, comment: _ } => visitor.visit_borrowed_str(value),
+            Value::Complex { re, im, .. } => {
+                visitor.visit_seq(SeqDeserializer::new([*re, *im].iter().copied()))
+            }
             Value::Undefined => visitor.visit_unit(),
             Value::Invalid(s) => visitor.visit_borrowed_str(s),
         }
@@ -516,6 +699,8 @@ impl<'de> IntoDeserializer<'de, Error> for &'de Value {
 /// FITSv4 section 4.3.2. Units in comment fields
 fn parse_unit(comment: &Option<String>) -> Option<&str> {
     if let Some(c) = comment {
+        // The comment usually begins with the space following the `/` separator
+        let c = c.trim_start();
         if c.starts_with("[") {
             if let Some(i) = c.find("]") {
                 Some(&c[1..i])
@@ -532,15 +717,25 @@ fn parse_unit(comment: &Option<String>) -> Option<&str> {
 
 impl Value {
     pub fn append(&mut self, v: &Option<String>, c: &Option<String>) -> &mut Self {
-        if let Value::String { value, comment } = self {
-            append_string(value, v);
-            if let Some(comment) = comment {
+        match self {
+            Value::String { value, comment } => {
+                // Only the comment may be continued
+                if value.ends_with('&') {
+                    append_string(value, v);
+                }
                 append_comment(comment, c);
-            } else {
-                *comment = c.clone();
             }
-        } else {
-            panic!("self is not a string variant")
+            Value::Integer { comment, .. }
+            | Value::Float { comment, .. }
+            | Value::Logical { comment, .. }
+            | Value::Complex { comment, .. } => {
+                assert!(
+                    comment_continued(comment),
+                    "only the comment of a non string value can be continued"
+                );
+                append_comment(comment, c);
+            }
+            _ => panic!("self has no value or comment to continue"),
         }
         self
     }
@@ -639,6 +834,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn signed_and_padded_integer_values() {
+        for (buf, v) in [
+            (
+                b"BZERO   =               +32768                                                  ",
+                32768,
+            ),
+            (
+                b"INTKEY  =                   +5                                                  ",
+                5,
+            ),
+            (
+                b"INTKEY  =                   -5                                                  ",
+                -5,
+            ),
+            (
+                b"INTKEY  = -1                                                                    ",
+                -1,
+            ),
+            (
+                b"INTKEY  =                  -01 / leading zero and a comment                     ",
+                -1,
+            ),
+        ] {
+            if let Card::Value {
+                value: Value::Integer { value, .. },
+                ..
+            } = Card::try_from(buf).unwrap()
+            {
+                assert_eq!(value, v);
+            } else {
+                panic!("expected an integer value card");
+            }
+        }
+
+        assert_eq!(
+            parse_number("+5".to_owned(), None),
+            Ok(Value::Integer {
+                value: 5,
+                comment: None
+            })
+        );
+        assert_eq!(
+            parse_number("-5".to_owned(), None),
+            Ok(Value::Integer {
+                value: -5,
+                comment: None
+            })
+        );
+    }
+
     #[test]
     fn number_values() -> Result<(), Error> {
         assert_eq!(
@@ -815,6 +1061,42 @@ mod tests {
         assert_eq!(parse_empty_keyword_card(r), Card::Space);
     }
 
+    #[test]
+    fn commentary_cards_without_text() {
+        let r = b"COMMENT                                                                         ";
+        assert_eq!(Card::try_from(r), Ok(Card::Comment(String::new())));
+
+        let r = b"HISTORY                                                                         ";
+        assert_eq!(Card::try_from(r), Ok(Card::History(String::new())));
+
+        let r = b"COMMENT = 'not a value'                                                         ";
+        assert_eq!(
+            Card::try_from(r),
+            Ok(Card::Comment("= 'not a value'".to_owned()))
+        );
+
+        let r = b"                                                                                ";
+        assert_eq!(Card::try_from(r), Ok(Card::Space));
+
+        let r = b"          text-only card with a blank keyword                                   ";
+        assert_eq!(
+            Card::try_from(r),
+            Ok(Card::Comment(
+                "  text-only card with a blank keyword".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn lone_quote_value() {
+        let r = b"STRKEY  = '                                                                     ";
+        assert!(Card::try_from(r).is_err());
+        let r = b"XTENSION= '                                                                     ";
+        assert!(Card::try_from(r).is_err());
+        let r = b"CONTINUE  '                                                                     ";
+        assert!(Card::try_from(r).is_err());
+    }
+
     #[test]
     fn undefined_card() -> Result<(), Error> {
         let r1 =
@@ -918,6 +1200,113 @@ mod tests {
         }
     }
 
+    /// A comment ending with an ampersand is continued even if the value is not.
+    #[test]
+    fn long_comment() {
+        let cards = [
+            b"FILENAME= 'obs.fits'           / read from /data/archive/2021/instrument/raw/&  ",
+            b"CONTINUE '' / night_03/calibrated/&                                             ",
+            b"CONTINUE '' / obs.fits                                                          ",
+        ];
+
+        let card = Card::try_from(cards[0]).unwrap();
+        assert!(card.continued());
+        let card = card.splice(Card::try_from(cards[1]).unwrap());
+        assert!(card.continued());
+        let card = card.splice(Card::try_from(cards[2]).unwrap());
+        assert!(!card.continued());
+
+        assert_eq!(
+            card,
+            Card::Value {
+                name: "FILENAME".to_owned(),
+                value: Value::String {
+                    value: "obs.fits".to_owned(),
+                    comment: Some(
+                        " read from /data/archive/2021/instrument/raw/night_03/calibrated/obs.fits"
+                            .to_owned()
+                    ),
+                },
+            }
+        );
+
+        let card = Card::try_from(
+            b"EXPTIME =                 30.0 / exposure time of the frames listed in &        ",
+        )
+        .unwrap()
+        .splice(
+            Card::try_from(
+                b"CONTINUE '' / the FRAMES card                                                   ",
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            card,
+            Card::Value {
+                name: "EXPTIME".to_owned(),
+                value: Value::Float {
+                    value: 30.0,
+                    comment: Some(
+                        " exposure time of the frames listed in the FRAMES card".to_owned()
+                    ),
+                },
+            }
+        );
+    }
+
+    /// Complex integer and floating point values, FITSv4 sections 4.2.5 and 4.2.6
+    #[test]
+    fn complex_values() {
+        let card = Card::try_from(
+            b"CPLXKEY =       (1.5D2, -2.0E-1) / [V] complex float                            ",
+        )
+        .unwrap();
+        let Card::Value { value, .. } = &card else {
+            panic!("Card is not a Card::Value")
+        };
+        assert_eq!(
+            value,
+            &Value::Complex {
+                re: 150.0,
+                im: -0.2,
+                comment: Some(" [V] complex float".to_owned())
+            }
+        );
+        assert_eq!(value.unit(), Some("V"));
+        assert_eq!(
+            serde::Deserialize::deserialize(value),
+            Ok::<_, Error>((150.0, -0.2))
+        );
+
+        let card = Card::try_from(
+            b"CPLXINT =              ( 12 , 3)                                                ",
+        )
+        .unwrap();
+        assert_eq!(
+            card,
+            Card::Value {
+                name: "CPLXINT".to_owned(),
+                value: Value::Complex {
+                    re: 12.0,
+                    im: 3.0,
+                    comment: None
+                }
+            }
+        );
+
+        let card = Card::try_from(
+            b"BADCPLX =               (12 3)                                                  ",
+        )
+        .unwrap();
+        assert!(matches!(
+            card,
+            Card::Value {
+                value: Value::Invalid(_),
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn hierarch_keyword_record() {
         let r =
@@ -0,0 +1,57 @@
+//! Detection of the compression of a whole FITS file, e.g. `image.fits.gz`, from its first bytes
+
+/// A compression format applied to a whole file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gzip, starting with the bytes `1f 8b`
+    Gzip,
+    /// bzip2, starting with the bytes `42 5a 68`, i.e. `BZh`
+    Bzip2,
+    /// xz, starting with the bytes `fd 37 7a`
+    Xz,
+}
+
+impl Compression {
+    /// The magic bytes a file compressed in this format starts with
+    pub fn magic_bytes(&self) -> &'static [u8] {
+        match self {
+            Compression::Gzip => &[0x1f, 0x8b],
+            Compression::Bzip2 => &[0x42, 0x5a, 0x68],
+            Compression::Xz => &[0xfd, 0x37, 0x7a],
+        }
+    }
+}
+
+/// Detect the compression of a file from its first bytes
+///
+/// Returns `None` if the bytes do not start with the magic bytes of a known compression format, which
+/// is the case of an uncompressed FITS file starting with `SIMPLE`. Three bytes are enough to detect
+/// all the formats.
+///
+/// # Params
+/// * `buf` - the first bytes of the file
+pub fn detect(buf: &[u8]) -> Option<Compression> {
+    [Compression::Gzip, Compression::Bzip2, Compression::Xz]
+        .iter()
+        .copied()
+        .find(|compression| buf.starts_with(compression.magic_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect, Compression};
+
+    #[test]
+    fn detect_from_magic_bytes() {
+        assert_eq!(detect(&[0x1f, 0x8b, 0x08, 0x00]), Some(Compression::Gzip));
+        assert_eq!(detect(b"BZh91AY&SY"), Some(Compression::Bzip2));
+        assert_eq!(
+            detect(&[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00]),
+            Some(Compression::Xz)
+        );
+
+        assert_eq!(detect(b"SIMPLE  =                    T"), None);
+        assert_eq!(detect(&[0x1f]), None);
+        assert_eq!(detect(&[]), None);
+    }
+}
@@ -21,6 +21,18 @@ quick_error! {
             from(wcs::error::Error)
             display("WCS parsing")
         }
+        /// A card could not be parsed
+        ///
+        /// `card_index` is the index of the card in its header and `offset` is the byte offset
+        /// of the card from the beginning of the stream. For the async API, `offset` is given
+        /// from the beginning of the header.
+        CardParse { card_index: usize, keyword: String, offset: u64, cause: Box<Error> } {
+            display("card {card_index} ({keyword}) at byte {offset} failed: {cause}")
+        }
+        /// The number of `TFORMn` cards of a table does not match its `TFIELDS` card
+        TableFieldMismatch { declared: usize, found: usize } {
+            display("TFIELDS = {declared} but {found} TFORMn cards are found")
+        }
         NotSupportedXtensionType(extension: String) {
             display("{extension} extension is not supported. Only BINTABLE, TABLE and IMAGE are.")
         }
@@ -28,12 +40,17 @@ quick_error! {
             from(std::str::Utf8Error)
             display("Fail to parse a keyword as a utf8 string")
         }
+        /// The data unit holds fewer bytes than declared by its header
+        TruncatedData {
+            display("The data unit is truncated, it holds fewer bytes than declared by its header")
+        }
         /// IO error wrapping the std::io::Error
         Io(kind: std::io::ErrorKind) {
             // to be able to derive from PartialEq just above
             // as std::io::Error does not impl PartialEq I decided
             // to only store its error kind which is sufficiant for our use
             from(err: std::io::Error) -> (err.kind())
+            display("IO error: {kind}")
         }
     }
 }
@@ -43,3 +60,43 @@ impl serde::de::Error for Error {
         Error::DynamicError(msg.to_string())
     }
 }
+
+impl Error {
+    /// Shift the byte offset of a [CardParse](Error::CardParse) error
+    pub(crate) fn offset_by(self, num_bytes: u64) -> Self {
+        match self {
+            Error::CardParse {
+                card_index,
+                keyword,
+                offset,
+                cause,
+            } => Error::CardParse {
+                card_index,
+                keyword,
+                offset: offset + num_bytes,
+                cause,
+            },
+            e => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    fn read_exact(mut bytes: &[u8]) -> Result<[u8; 4], Error> {
+        let mut buf = [0; 4];
+        std::io::Read::read_exact(&mut bytes, &mut buf)?;
+        Ok(buf)
+    }
+
+    #[test]
+    fn io_error_conversion() {
+        assert_eq!(read_exact(&[1, 2, 3, 4, 5]), Ok([1, 2, 3, 4]));
+
+        let err = read_exact(&[1, 2]).unwrap_err();
+        assert_eq!(err, Error::Io(std::io::ErrorKind::UnexpectedEof));
+        assert_eq!(err.to_string(), "IO error: unexpected end of file");
+    }
+}
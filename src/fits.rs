@@ -1,12 +1,24 @@
-use crate::card::Card;
+use crate::card::{Card, CardBuf, Value};
 use crate::hdu;
+use crate::hdu::data::bintable::events::{EventColumns, EventList};
+use crate::hdu::data::bintable::{decode_fixed_width_string, ColumnValue, NullPolicy};
+use crate::hdu::data::image::DataRange;
+use crate::hdu::data::DataUnitReader;
 use crate::hdu::header::extension::asciitable::AsciiTable;
-use crate::hdu::header::extension::bintable::BinTable;
+use crate::hdu::header::extension::bintable::{BinTable, TFormType};
 use crate::hdu::header::extension::image::Image;
+use crate::hdu::header::extension::unknown::Unknown;
+use crate::hdu::header::Bitpix;
 use crate::hdu::header::Header;
 use crate::hdu::header::Xtension;
+use crate::integrity::checksum;
+use crate::options::{FitsOptions, FitsVersion};
 
+use byteorder::{BigEndian, ByteOrder};
+use log::warn;
+use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::io::Cursor;
 
 #[derive(Debug, Clone)]
 pub struct Fits<R> {
@@ -19,10 +31,12 @@ pub struct Fits<R> {
     num_bytes_in_cur_du: usize,
     // If an error has been encountered, the HDU iterator ends
     error_parsing_encountered: bool,
+    // The parsing options
+    options: FitsOptions,
     // The reader
     reader: R,
 }
-use std::io::Read;
+use std::io::{Read, Write};
 impl<R> Read for Fits<R>
 where
     R: Read,
@@ -39,45 +53,539 @@ impl<R> Fits<R> {
     /// # Params
     /// * `reader` - a reader created i.e. from the opening of a file
     pub fn from_reader(reader: R) -> Self {
+        Self::from_reader_with(reader, FitsOptions::default())
+    }
+
+    /// Parse a FITS file with specific parsing options
+    /// # Params
+    /// * `reader` - a reader created i.e. from the opening of a file
+    /// * `options` - the parsing options
+    pub fn from_reader_with(reader: R, options: FitsOptions) -> Self {
         Self {
             reader,
             pos_start_cur_du: 0,
             num_bytes_in_cur_du: 0,
             error_parsing_encountered: false,
+            options,
             start: true,
         }
     }
+
+    /// Get the parsing options
+    pub fn get_options(&self) -> &FitsOptions {
+        &self.options
+    }
 }
 use hdu::data::FitsRead;
-use std::io::Seek;
+use std::io::{Seek, SeekFrom};
 impl<'a, R> Fits<R>
 where
-    R: FitsRead<'a, Image> + FitsRead<'a, AsciiTable> + FitsRead<'a, BinTable> + 'a + Seek,
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + 'a
+        + Seek,
 {
     /// Targets the reader to the next HDU
     ///
     /// It is possible that EOF is reached immediately because some fits files do not have these blank bytes
     /// at the end of its last HDU
     pub(crate) fn consume_until_next_hdu(&mut self) -> Result<(), Error> {
-        // Seek to the beginning of the next HDU.
-        // The number of bytes to skip is the remaining bytes +
-        // an offset to get to a multiple of 2880 bytes
-
-        // current seek position since the start of the stream
+        // Seek to the beginning of the next HDU, the data unit being possibly partially read.
+        // Non-seekable streams are only supported by AsyncFits which reads and discards the bytes instead.
         let cur_pos = self.reader.stream_position()? as usize;
-        let mut num_bytes_to_skip = self.num_bytes_in_cur_du - (cur_pos - self.pos_start_cur_du);
+        let num_bytes_read = cur_pos - self.pos_start_cur_du;
 
-        let offset_in_2880_block = self.num_bytes_in_cur_du % 2880;
-        let is_aligned_on_block = offset_in_2880_block == 0;
-        if !is_aligned_on_block {
-            num_bytes_to_skip += 2880 - offset_in_2880_block;
+        hdu::data::skip_data_unit(
+            &mut self.reader,
+            self.num_bytes_in_cur_du as u64,
+            num_bytes_read as u64,
+        )
+    }
+
+    /// Check that an extension header starts where the previous data unit and its padding end
+    ///
+    /// Some producers write extra bytes after the data unit or omit its padding. In lenient mode,
+    /// a warning is emitted and the `XTENSION` card is looked for from the end of the data unit
+    /// up to the block following the expected position. If it is not found, the reader stays at the
+    /// expected position. In strict mode, an error is returned.
+    fn check_next_header_alignment(&mut self) -> Result<(), Error> {
+        const XTENSION: &[u8] = b"XTENSION= '";
+
+        let expected_pos = self.reader.stream_position()?;
+        let mut first_bytes = Vec::with_capacity(XTENSION.len());
+        (&mut self.reader)
+            .take(XTENSION.len() as u64)
+            .read_to_end(&mut first_bytes)?;
+        self.reader.seek(SeekFrom::Start(expected_pos))?;
+
+        // EOF or aligned header
+        if first_bytes.is_empty() || first_bytes == XTENSION {
+            return Ok(());
         }
-        self.reader.seek_relative(num_bytes_to_skip as i64)?;
+
+        if self.options.is_strict() {
+            return Err(Error::DynamicError(format!(
+                "The HDU following the data unit is not aligned on a 2880-byte block at byte {expected_pos}"
+            )));
+        }
+
+        let start = (self.pos_start_cur_du + self.num_bytes_in_cur_du) as u64;
+        self.reader.seek(SeekFrom::Start(start))?;
+        let mut window = Vec::new();
+        (&mut self.reader)
+            .take(expected_pos + 2880 + XTENSION.len() as u64 - start)
+            .read_to_end(&mut window)?;
+
+        let pos = window
+            .windows(XTENSION.len())
+            .position(|bytes| bytes == XTENSION)
+            .map_or(expected_pos, |offset| start + offset as u64);
+        if pos != expected_pos {
+            warn!(
+                "The HDU following the data unit is not aligned on a 2880-byte block, it is found at byte {pos} instead of {expected_pos}"
+            );
+        }
+        self.reader.seek(SeekFrom::Start(pos))?;
 
         Ok(())
     }
 }
 
+impl<'a, R> Fits<R>
+where
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + Debug
+        + 'a
+        + Seek,
+{
+    /// Read only the header of the HDU located at a specific index
+    ///
+    /// The headers of the preceding HDUs are parsed but their data units are skipped
+    /// by seeking the reader so that no data is ever read nor decoded.
+    /// The returned [HDU](hdu::HDU) only stores the header and the position of its data unit.
+    ///
+    /// # Params
+    /// * `reader` - a seekable reader positioned at the beginning of the FITS file
+    /// * `hdu_index` - the index of the HDU, `0` referring to the primary HDU
+    pub fn read_header(reader: R, hdu_index: usize) -> Result<hdu::HDU, Error> {
+        Fits::from_reader(reader).nth(hdu_index).unwrap_or_else(|| {
+            Err(Error::DynamicError(format!(
+                "HDU index {hdu_index} is out of range"
+            )))
+        })
+    }
+}
+
+impl<'a, R> Fits<R>
+where
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + Debug
+        + 'a
+        + Seek,
+{
+    /// Give a summary of the HDUs of the FITS file in a table similar to `astropy.io.fits.info()`
+    ///
+    /// Only the headers are parsed, the data units being skipped. The table has one line per HDU
+    /// with the following whitespace separated columns:
+    /// * `No.` - the index of the HDU, `0` being the primary HDU
+    /// * `Name` - the `EXTNAME` of the HDU, `PRIMARY` for the primary HDU if not given
+    /// * `Type` - one of `PrimaryHDU`, `ImageHDU`, `BinTableHDU`, `TableHDU`, `UnknownHDU`
+    /// * `BITPIX` - the `BITPIX` integer code
+    /// * `Dimensions` - `(NAXIS1, NAXIS2, ...)` for images and unknown extensions, `<NAXIS2>R x <TFIELDS>C` for tables
+    ///
+    /// The format is kept stable so that it can be parsed by other tools.
+    pub fn summary(self) -> Result<String, Error> {
+        let mut summary = format!(
+            "{:<4} {:<16} {:<12} {:>6}  {}\n",
+            "No.", "Name", "Type", "BITPIX", "Dimensions"
+        );
+
+        for (idx, hdu) in self.enumerate() {
+            let (name, kind, bitpix, dims) = match &hdu? {
+                hdu::HDU::Primary(hdu) => (
+                    hdu.get_header()
+                        .get_parsed::<Option<String>>("EXTNAME")?
+                        .unwrap_or_else(|| "PRIMARY".to_owned()),
+                    "PrimaryHDU",
+                    hdu.get_header().get_xtension().get_bitpix(),
+                    image_dimensions(hdu.get_header().get_xtension().get_naxis()),
+                ),
+                hdu::HDU::XImage(hdu) => (
+                    hdu.get_header()
+                        .get_parsed::<Option<String>>("EXTNAME")?
+                        .unwrap_or_default(),
+                    "ImageHDU",
+                    hdu.get_header().get_xtension().get_bitpix(),
+                    image_dimensions(hdu.get_header().get_xtension().get_naxis()),
+                ),
+                hdu::HDU::XBinaryTable(hdu) => {
+                    let xtension = hdu.get_header().get_xtension();
+                    (
+                        hdu.get_header()
+                            .get_parsed::<Option<String>>("EXTNAME")?
+                            .unwrap_or_default(),
+                        "BinTableHDU",
+                        Bitpix::U8,
+                        format!(
+                            "{}R x {}C",
+                            xtension.get_num_rows(),
+                            xtension.get_num_cols()
+                        ),
+                    )
+                }
+                hdu::HDU::XASCIITable(hdu) => {
+                    let xtension = hdu.get_header().get_xtension();
+                    (
+                        hdu.get_header()
+                            .get_parsed::<Option<String>>("EXTNAME")?
+                            .unwrap_or_default(),
+                        "TableHDU",
+                        xtension.get_bitpix(),
+                        format!(
+                            "{}R x {}C",
+                            xtension.get_num_rows(),
+                            xtension.get_num_cols()
+                        ),
+                    )
+                }
+                hdu::HDU::XUnknown(hdu) => (
+                    hdu.get_header()
+                        .get_parsed::<Option<String>>("EXTNAME")?
+                        .unwrap_or_default(),
+                    "UnknownHDU",
+                    hdu.get_header().get_xtension().get_bitpix(),
+                    image_dimensions(hdu.get_header().get_xtension().get_naxis()),
+                ),
+            };
+
+            summary += &format!(
+                "{:<4} {:<16} {:<12} {:>6}  {}\n",
+                idx,
+                name,
+                kind,
+                bitpix.to_i64(),
+                dims
+            );
+        }
+
+        Ok(summary)
+    }
+}
+
+impl<'a, R> Fits<R>
+where
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + Debug
+        + 'a
+        + Seek,
+{
+    /// Check whether at least one extension follows the primary HDU
+    ///
+    /// Contrary to the `EXTEND` card (see [Header::get_extend]) which only states that extensions
+    /// may be present, this parses the header of the HDU following the primary one. A primary-only
+    /// file returns `false` even if `EXTEND = T` is given. The data units are skipped and not read.
+    pub fn has_extensions(mut self) -> Result<bool, Error> {
+        match self.next() {
+            Some(Ok(_)) => (),
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::StaticError("The primary HDU is missing")),
+        }
+
+        self.next().transpose().map(|hdu| hdu.is_some())
+    }
+}
+
+/// Format the dimensions of an image as `(NAXIS1, NAXIS2, ...)`
+fn image_dimensions(naxis: &[u64]) -> String {
+    let naxis = naxis.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+    format!("({})", naxis.join(", "))
+}
+
+impl<'a> From<&'a [u8]> for Fits<Cursor<&'a [u8]>> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<'a> Fits<Cursor<&'a [u8]>> {
+    /// Parse a FITS file stored in memory
+    ///
+    /// Any byte container can be given, e.g. a `Vec<u8>`, a `[u8; N]` array or a `Bytes` buffer.
+    /// For a `Cursor<Vec<u8>>`, pass its inner buffer with `Fits::from_bytes(cursor.get_ref())`.
+    ///
+    /// # Params
+    /// * `bytes` - the in-memory buffer storing the FITS file
+    pub fn from_bytes<B>(bytes: &'a B) -> Self
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        Self::from_reader(Cursor::new(bytes.as_ref()))
+    }
+
+    /// Parse a FITS file stored in memory with specific parsing options
+    ///
+    /// # Params
+    /// * `bytes` - the in-memory buffer storing the FITS file
+    /// * `options` - the parsing options
+    pub fn from_bytes_with<B>(bytes: &'a B, options: &FitsOptions) -> Self
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        Self::from_reader_with(Cursor::new(bytes.as_ref()), options.clone())
+    }
+
+    /// Compute the size a FITS file should have from its headers, e.g. to detect a truncated download
+    ///
+    /// The headers are parsed and the padded size of each data unit is added without reading it,
+    /// so that the returned size may exceed the length of `bytes`.
+    ///
+    /// # Params
+    /// * `bytes` - the in-memory buffer storing the possibly truncated FITS file
+    pub fn expected_file_size(bytes: &'a [u8]) -> Result<usize, Error> {
+        Fits::from_bytes(bytes).try_fold(0, |_, hdu| {
+            let hdu = hdu?;
+            let end_du = hdu.get_data_unit_byte_offset() + hdu.get_data_unit_byte_size();
+            Ok(end_du.next_multiple_of(2880) as usize)
+        })
+    }
+
+    /// Parse only the primary header of an in-memory FITS file, e.g. to index the metadata of many files
+    ///
+    /// The data unit is neither read nor decoded. Returns the bytes following the header, i.e.
+    /// starting with the data unit, together with the header.
+    ///
+    /// # Params
+    /// * `bytes` - the in-memory buffer storing the FITS file
+    pub fn primary_header(bytes: &'a [u8]) -> Result<(&'a [u8], Header<Image>), Error> {
+        let mut reader = Cursor::new(bytes);
+        let hdu::HDU::Primary(hdu) = hdu::HDU::new_primary(&mut reader, false)? else {
+            unreachable!("a primary HDU is always parsed as such");
+        };
+
+        let remaining = &bytes[(hdu.get_data_unit_byte_offset() as usize).min(bytes.len())..];
+        Ok((remaining, hdu.header))
+    }
+
+    /// Parse a FITS file embedded at a known byte offset within a larger buffer, e.g. a container format
+    ///
+    /// The byte offsets of the HDUs are given from the beginning of the embedded FITS file.
+    ///
+    /// # Params
+    /// * `bytes` - the in-memory buffer containing the FITS file
+    /// * `offset` - the byte index where the `SIMPLE` card of the FITS file starts
+    pub fn from_bytes_at<B>(bytes: &'a B, offset: usize) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        let bytes = bytes.as_ref();
+        let Some(buf) = bytes.get(offset..) else {
+            return Err(Error::DynamicError(format!(
+                "Offset {offset} is out of the {} bytes of the buffer",
+                bytes.len()
+            )));
+        };
+
+        if !starts_with_primary_header(buf) {
+            return Err(Error::DynamicError(format!(
+                "No `SIMPLE = T` card has been found at offset {offset}"
+            )));
+        }
+
+        Ok(Self::from_bytes(buf))
+    }
+
+    /// Parse a stream made of several complete FITS files concatenated one after the other
+    ///
+    /// Each FITS file embedded in the stream starts with its own primary HDU (i.e. a header whose first
+    /// card is `SIMPLE = T`). This is different from extensions which are HDUs following the primary
+    /// one and starting with a `XTENSION` card. Extensions belong to the FITS file they follow and are
+    /// therefore given by iterating over the yielded [Fits].
+    ///
+    /// The boundary between two files is detected by walking through the HDUs of the current file
+    /// and checking whether the next 2880 bytes block begins with a `SIMPLE = T` card.
+    /// The iterator stops after the first error encountered.
+    ///
+    /// # Params
+    /// * `buf` - an in-memory buffer storing the concatenated FITS files
+    pub fn parse_stream(buf: &'a [u8]) -> impl Iterator<Item = Result<Self, Error>> + 'a {
+        let mut pos = 0;
+        let mut error_parsing_encountered = false;
+
+        std::iter::from_fn(move || {
+            if error_parsing_encountered || pos >= buf.len() {
+                return None;
+            }
+
+            let rest = &buf[pos..];
+            match embedded_fits_byte_size(rest) {
+                Ok(num_bytes) => {
+                    pos += num_bytes;
+                    Some(Ok(Fits::from_reader(Cursor::new(&rest[..num_bytes]))))
+                }
+                Err(e) => {
+                    error_parsing_encountered = true;
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// Get the raw bytes of a HDU, i.e. its header, its data unit and the padding
+    /// bytes completing its last 2880 bytes block
+    ///
+    /// No copy is done, the bytes are borrowed from the in-memory buffer. Several HDUs can then be
+    /// concatenated into a new FITS file without decoding nor re-encoding them. The padding bytes
+    /// of the last HDU may be missing if the buffer does not contain them.
+    ///
+    /// # Params
+    /// * `hdu` - a HDU given by iterating over this [Fits]
+    pub fn raw_bytes(&self, hdu: &hdu::HDU) -> &'a [u8] {
+        let buf = self.get_buf();
+
+        let start = hdu.get_header_byte_offset() as usize;
+        let end_du = (hdu.get_data_unit_byte_offset() + hdu.get_data_unit_byte_size()) as usize;
+        let end = (end_du.div_ceil(2880) * 2880).min(buf.len());
+
+        &buf[start..end]
+    }
+
+    /// Get the undecoded bytes of the data unit of a HDU, without its padding bytes
+    ///
+    /// This gives access to the data whatever its `BITPIX`, e.g. to decode non-standard packed values.
+    /// The slice is truncated if the buffer does not contain the whole data unit.
+    ///
+    /// # Params
+    /// * `hdu` - a HDU given by iterating over this [Fits]
+    pub fn data_unit_bytes(&self, hdu: &hdu::HDU) -> &'a [u8] {
+        let buf = self.get_buf();
+
+        let start = (hdu.get_data_unit_byte_offset() as usize).min(buf.len());
+        let end = (start + hdu.get_data_unit_byte_size() as usize).min(buf.len());
+
+        &buf[start..end]
+    }
+
+    /// Get the whole in-memory buffer
+    pub(crate) fn get_buf(&self) -> &'a [u8] {
+        self.reader.get_ref()
+    }
+}
+
+/// Check that a HDU only uses the features allowed by a version of the FITS standard
+fn check_standard(hdu: &hdu::HDU, standard: FitsVersion) -> Result<(), Error> {
+    if standard >= FitsVersion::V4_0 {
+        return Ok(());
+    }
+
+    let not_allowed =
+        |feature: &str| Error::DynamicError(format!("{feature} is not allowed by {standard}"));
+
+    // The CONTINUE cards are allowed by the long string convention when declared
+    if has_undeclared_continuation(hdu) {
+        return Err(not_allowed("A long string CONTINUE card"));
+    }
+
+    if let hdu::HDU::XBinaryTable(hdu) = hdu {
+        let ctx = hdu.get_header().get_xtension();
+        if ctx
+            .tforms
+            .iter()
+            .any(|tform| matches!(tform, TFormType::Q { .. }))
+        {
+            return Err(not_allowed("A 64-bit array descriptor field (Q)"));
+        }
+        if ctx.get_tile_compressed_image().is_some() {
+            return Err(not_allowed("A tile compressed image"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that a binary table has exactly one `TFORMn` card per field declared by `TFIELDS`
+fn check_table_fields(hdu: &hdu::HDU) -> Result<(), Error> {
+    if !matches!(hdu, hdu::HDU::XBinaryTable(_)) {
+        return Ok(());
+    }
+
+    let values = hdu.get_values();
+    let declared = values.get_parsed::<usize>("TFIELDS")?;
+    let found = values.num_tforms();
+    let has_all_tforms = (1..=declared).all(|n| values.get(&format!("TFORM{n}")).is_some());
+    if found != declared || !has_all_tforms {
+        return Err(Error::TableFieldMismatch { declared, found });
+    }
+
+    Ok(())
+}
+
+/// Check whether a header has `CONTINUE` cards without declaring the long string convention with `LONGSTRN`
+fn has_undeclared_continuation(hdu: &hdu::HDU) -> bool {
+    let is_continuation = |card: &Card| matches!(card, Card::Continuation { .. });
+    let values = hdu.get_values();
+    let has_continuation = match hdu {
+        hdu::HDU::Primary(hdu) | hdu::HDU::XImage(hdu) => {
+            hdu.get_header().cards().any(is_continuation)
+        }
+        hdu::HDU::XBinaryTable(hdu) => hdu.get_header().cards().any(is_continuation),
+        hdu::HDU::XASCIITable(hdu) => hdu.get_header().cards().any(is_continuation),
+        hdu::HDU::XUnknown(hdu) => hdu.get_header().cards().any(is_continuation),
+    };
+
+    has_continuation && values.get_str("LONGSTRN").is_none()
+}
+
+/// Check whether the bytes begin with the `SIMPLE = T` card starting a primary header
+fn starts_with_primary_header(buf: &[u8]) -> bool {
+    buf.get(..80)
+        .and_then(|card| <&CardBuf>::try_from(card).ok())
+        .and_then(|card| Card::try_from(card).ok())
+        .is_some_and(|card| {
+            matches!(card, Card::Value { name, value: Value::Logical { value: true, .. } } if name == "SIMPLE")
+        })
+}
+
+/// Compute the number of bytes of the first FITS file contained in `buf`, i.e. until
+/// the end of buffer or until a new primary header is found
+fn embedded_fits_byte_size(buf: &[u8]) -> Result<usize, Error> {
+    let mut hdu_list = Fits::from_reader(Cursor::new(buf));
+    let mut num_bytes = 0;
+
+    loop {
+        // A new primary header means that a new FITS file begins
+        if num_bytes > 0
+            && (num_bytes >= buf.len() || starts_with_primary_header(&buf[num_bytes..]))
+        {
+            break;
+        }
+
+        match hdu_list.next() {
+            Some(Ok(hdu)) => {
+                let end_du = hdu.get_data_unit_byte_offset() + hdu.get_data_unit_byte_size();
+                num_bytes = (end_du.div_ceil(2880) * 2880) as usize;
+            }
+            // The primary HDU could not be parsed
+            Some(Err(e)) if num_bytes == 0 => return Err(e),
+            // The bytes following the last valid HDU are neither an extension nor a new
+            // primary header. They will be reported while parsing the next FITS file.
+            Some(Err(_)) | None => break,
+        }
+    }
+
+    Ok(num_bytes.min(buf.len()))
+}
+
 impl<R> Fits<R> {
     /// Get the byte index where the data for the current processed HDU is
     ///
@@ -89,7 +597,11 @@ impl<R> Fits<R> {
 
 impl<'a, R> Fits<R>
 where
-    R: FitsRead<'a, Image> + FitsRead<'a, AsciiTable> + FitsRead<'a, BinTable> + 'a,
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + 'a,
 {
     // Retrieve the iterator or in memory data from the reader
     // This has the effect of consuming the HDU
@@ -100,14 +612,576 @@ where
     {
         // Unroll the internal fits parsing parameters to give it to the data reader
         let header = &hdu.header;
-        self.reader
-            .read_data_unit(header, self.pos_start_cur_du as u64)
+        let data = self
+            .reader
+            .read_data_unit(header, self.pos_start_cur_du as u64);
+        <R as FitsRead<'a, X>>::with_options(data, &self.options)
+    }
+}
+
+impl<'a, R> Fits<R>
+where
+    R: Read + Debug + 'a,
+{
+    /// Get a reader decoding the data unit of an image HDU chunk by chunk, see [DataUnitReader]
+    ///
+    /// Like [Fits::get_data], the reading options (e.g. [FitsOptions::assume_little_endian]) are applied
+    /// to the reader returned.
+    ///
+    /// # Params
+    /// * `hdu` - an image HDU given by iterating over this [Fits]
+    pub fn get_data_chunks(&'a mut self, hdu: &HDU<Image>) -> DataUnitReader<&'a mut R> {
+        DataUnitReader::new(&mut self.reader, hdu.get_header().get_xtension())
+            .little_endian(self.options.is_assuming_little_endian())
+    }
+
+    /// Read the physical values of an image HDU together with its shape
+    ///
+    /// The pixels are scaled by the `BSCALE` and `BZERO` cards and undefined pixels (NaN values or
+    /// values equal to `BLANK` for integer images) are NaN. The values are stored in the FITS order,
+    /// i.e. `NAXIS1` varying the fastest, and the shape is given as `[NAXIS1, NAXIS2, ...]`.
+    ///
+    /// # Params
+    /// * `hdu` - a HDU given by iterating over this [Fits], it must be an image
+    pub fn image_f64(&'a mut self, hdu: &hdu::HDU) -> Result<(Vec<f64>, Vec<usize>), Error> {
+        let (hdu::HDU::Primary(hdu) | hdu::HDU::XImage(hdu)) = hdu else {
+            return Err(Error::StaticError("The HDU is not an image"));
+        };
+
+        let header = hdu.get_header();
+        let bscale = header.get_bscale()?;
+        let bzero = header.get_bzero()?;
+        let shape = header
+            .get_xtension()
+            .get_naxis()
+            .iter()
+            .map(|&size| size as usize)
+            .collect::<Vec<_>>();
+
+        let image = self.get_data(hdu);
+        let num_pixels = image.get_num_pixels();
+        let values = image.physical_values(bscale, bzero);
+        if values.len() != num_pixels {
+            return Err(Error::StaticError(
+                "The image data unit is truncated, not all of its pixels can be read",
+            ));
+        }
+
+        Ok((values, shape))
+    }
+
+    /// Read the physical values of an image HDU together with its shape in the C (row-major) order
+    ///
+    /// The shape is reversed compared to [Fits::image_f64], i.e. `[NAXIS2, NAXIS1]` for a 2D image and
+    /// `[NAXIS3, NAXIS2, NAXIS1]` for a cube, so that the last index varies the fastest. The pixel at
+    /// the 1-based FITS coordinates `(x, y, z)` is then found at the index `[z - 1][y - 1][x - 1]`, i.e.
+    /// at `values[((z - 1) * NAXIS2 + (y - 1)) * NAXIS1 + (x - 1)]`.
+    ///
+    /// As `NAXIS1` already varies the fastest in the data unit, no value is moved: reversing the shape
+    /// is the whole transformation from the FITS column-major convention to the row-major one.
+    ///
+    /// # Params
+    /// * `hdu` - a HDU given by iterating over this [Fits], it must be an image
+    pub fn image_c_order_f64(
+        &'a mut self,
+        hdu: &hdu::HDU,
+    ) -> Result<(Vec<f64>, Vec<usize>), Error> {
+        let (values, mut shape) = self.image_f64(hdu)?;
+        shape.reverse();
+
+        Ok((values, shape))
+    }
+
+    /// Read the physical values of an image HDU with its axes rearranged in the C (row-major) order
+    ///
+    /// The axis `i` of the returned array is the axis `order[i]` of the array given by
+    /// [Fits::image_c_order_f64], as with a transpose in numpy. For a cube of shape
+    /// `[NAXIS3, NAXIS2, NAXIS1]`, the order `[0, 1, 2]` keeps the C order and `[1, 2, 0]` gives the
+    /// shape `[NAXIS2, NAXIS1, NAXIS3]`, i.e. a `(y, x, channel)` array. Unlike the C order, the values
+    /// are moved and the last index varies the fastest in the new shape.
+    ///
+    /// An error is returned if `order` is not a permutation of `0..NAXIS`.
+    ///
+    /// # Params
+    /// * `hdu` - a HDU given by iterating over this [Fits], it must be an image
+    /// * `order` - the permutation of the axes of the C order array
+    pub fn image_permuted_f64(
+        &'a mut self,
+        hdu: &hdu::HDU,
+        order: &[usize],
+    ) -> Result<(Vec<f64>, Vec<usize>), Error> {
+        let (hdu::HDU::Primary(image) | hdu::HDU::XImage(image)) = hdu else {
+            return Err(Error::StaticError("The HDU is not an image"));
+        };
+
+        // Check the order before reading the whole data unit
+        let naxis = image.get_header().get_xtension().get_naxis().len();
+        let mut seen = vec![false; naxis];
+        let is_permutation = order.len() == naxis
+            && order
+                .iter()
+                .all(|&axis| axis < naxis && !std::mem::replace(&mut seen[axis], true));
+        if !is_permutation {
+            return Err(Error::DynamicError(format!(
+                "{order:?} is not a permutation of the {naxis} axes of the image"
+            )));
+        }
+
+        let (values, shape) = self.image_c_order_f64(hdu)?;
+
+        // Strides of the C order array
+        let mut strides = vec![1; naxis];
+        for i in (0..naxis.saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+        let permuted_shape = order.iter().map(|&axis| shape[axis]).collect::<Vec<_>>();
+        let permuted_strides = order.iter().map(|&axis| strides[axis]).collect::<Vec<_>>();
+
+        // Walk the permuted array in the C order, the last index varying the fastest
+        let mut permuted = Vec::with_capacity(values.len());
+        let mut idx = vec![0; naxis];
+        let mut offset = 0;
+        for _ in 0..values.len() {
+            permuted.push(values[offset]);
+
+            for axis in (0..naxis).rev() {
+                idx[axis] += 1;
+                offset += permuted_strides[axis];
+                if idx[axis] < permuted_shape[axis] {
+                    break;
+                }
+                offset -= idx[axis] * permuted_strides[axis];
+                idx[axis] = 0;
+            }
+        }
+
+        Ok((permuted, permuted_shape))
+    }
+
+    /// Read the strings of a character (`rA`) field of a binary table HDU
+    ///
+    /// Each value of the field is split into strings of the width given by the first dimension of its
+    /// `TDIMn` card, e.g. `TFORM1 = '64A'` and `TDIM1 = '(16,4)'` give 4 strings of 16 characters per row.
+    /// Without `TDIMn`, each row holds one string of `r` characters. The strings are given row after row,
+    /// ended at the first NUL byte and stripped of their trailing spaces.
+    ///
+    /// # Params
+    /// * `hdu` - a binary table HDU given by iterating over this [Fits]
+    /// * `col` - the index of the field starting from 0
+    pub fn column_strings(
+        &'a mut self,
+        hdu: &HDU<BinTable>,
+        col: usize,
+    ) -> Result<Vec<String>, Error> {
+        let header = hdu.get_header();
+        let ctx = header.get_xtension();
+        let Some(TFormType::A { repeat_count }) = ctx.tforms.get(col) else {
+            return Err(Error::DynamicError(format!(
+                "Field {col} is not a character field"
+            )));
+        };
+        let repeat_count = *repeat_count;
+
+        let width = header
+            .get_tdim(col)?
+            .and_then(|dims| dims.first().copied())
+            .unwrap_or(repeat_count);
+        if width == 0 || !repeat_count.is_multiple_of(width) {
+            return Err(Error::DynamicError(format!(
+                "Field {col} of {repeat_count} characters cannot be split into strings of {width} characters"
+            )));
+        }
+
+        let row_byte_size = ctx.naxis1 as usize;
+        let num_rows = ctx.get_num_rows();
+
+        let data = self.get_data(hdu).table_data();
+        let field_byte_offset = data.col_byte_offsets[col];
+        let mut reader = data.bytes();
+
+        let mut strings = Vec::with_capacity(num_rows * (repeat_count / width));
+        let mut row = vec![0; row_byte_size];
+        for _ in 0..num_rows {
+            reader.read_exact(&mut row)?;
+            let field = &row[field_byte_offset..field_byte_offset + repeat_count];
+            strings.extend(field.chunks(width).map(decode_fixed_width_string));
+        }
+
+        Ok(strings)
+    }
+
+    /// Read the physical values of a numeric field of a binary table HDU converted to a specific type
+    ///
+    /// The stored values of `B`, `I`, `J`, `K`, `E` and `D` fields are scaled by the `TSCALn` and `TZEROn`
+    /// cards, i.e. `TZEROn + TSCALn * stored`, and then converted to `T`, e.g. an `I` field to `Vec<f64>`.
+    /// A conversion to an integer type requires the physical value to be an integer within the range of
+    /// the type, otherwise an error is returned. `K` values are converted through a `f64` so that the ones
+    /// beyond 2^53 may lose precision.
+    ///
+    /// The undefined values are the stored integers equal to `TNULLn` and the NaN floating point values.
+    /// They are handled following `nulls`, i.e. replaced by `T::default()` or giving an error.
+    ///
+    /// The values are given row after row, a field with a repeat count `r` giving `r` values per row.
+    ///
+    /// # Params
+    /// * `hdu` - a binary table HDU given by iterating over this [Fits]
+    /// * `col` - the index of the field starting from 0
+    /// * `nulls` - the handling of the undefined values
+    pub fn column_as_vec<T>(
+        &'a mut self,
+        hdu: &HDU<BinTable>,
+        col: usize,
+        nulls: NullPolicy,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: ColumnValue,
+    {
+        let header = hdu.get_header();
+        let ctx = header.get_xtension();
+        let field = NumericField::new(header, col)?;
+
+        let row_byte_size = ctx.naxis1 as usize;
+        let num_rows = ctx.get_num_rows();
+
+        let mut reader = self.get_data(hdu).table_data().bytes();
+
+        let mut values = Vec::with_capacity(num_rows * field.repeat_count);
+        let mut row = vec![0; row_byte_size];
+        for row_idx in 0..num_rows {
+            reader.read_exact(&mut row)?;
+            for value in field.values(&row) {
+                let value = match (value, nulls) {
+                    (Some(value), _) => T::from_physical(value).ok_or_else(|| {
+                        Error::DynamicError(format!(
+                            "Value {value} of field {col} at row {row_idx} cannot be converted to {}",
+                            std::any::type_name::<T>()
+                        ))
+                    })?,
+                    (None, NullPolicy::Default) => T::default(),
+                    (None, NullPolicy::Error) => {
+                        return Err(Error::DynamicError(format!(
+                            "Field {col} has an undefined value at row {row_idx}"
+                        )))
+                    }
+                };
+                values.push(value);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Read the photon events of a binary table HDU
+    ///
+    /// The `TIME`, `X`, `Y` fields and the first of the `ENERGY`, `PI` or `PHA` fields are read in a
+    /// single pass over the rows. An error is returned if one of them is absent or is not a numeric
+    /// field with a repeat count of 1.
+    ///
+    /// # Params
+    /// * `hdu` - a binary table HDU given by iterating over this [Fits]
+    pub fn event_list(&'a mut self, hdu: &HDU<BinTable>) -> Result<EventList, Error> {
+        let header = hdu.get_header();
+        let ctx = header.get_xtension();
+        let columns = EventColumns::find(ctx)?;
+        let scalar_field = |col| {
+            let field = NumericField::new(header, col)?;
+            if field.repeat_count != 1 {
+                return Err(Error::DynamicError(format!(
+                    "Field {col} of the event list must have a repeat count of 1"
+                )));
+            }
+            Ok(field)
+        };
+        let time = scalar_field(columns.time)?;
+        let x = scalar_field(columns.x)?;
+        let y = scalar_field(columns.y)?;
+        let energy = scalar_field(columns.energy)?;
+
+        let num_rows = ctx.get_num_rows();
+        let mut events = EventList {
+            times: Vec::with_capacity(num_rows),
+            positions: Vec::with_capacity(num_rows),
+            energies: Vec::with_capacity(num_rows),
+        };
+
+        let mut reader = self.get_data(hdu).table_data().bytes();
+        let mut row = vec![0; ctx.naxis1 as usize];
+        let value = |field: &NumericField, row: &[u8]| {
+            field.values(row).next().flatten().unwrap_or(f64::NAN)
+        };
+        for _ in 0..num_rows {
+            reader.read_exact(&mut row)?;
+            events.times.push(value(&time, &row));
+            events.positions.push((value(&x, &row), value(&y, &row)));
+            events.energies.push(value(&energy, &row));
+        }
+
+        Ok(events)
+    }
+
+    /// Write the rows of a binary table HDU as CSV, e.g. to eyeball the content of a catalog
+    ///
+    /// The first line gives the names of the fields from their `TTYPEn` cards, `colN` being used for
+    /// the unnamed ones, then each row of the table is written on its own line. The numeric values are
+    /// scaled by the `TSCALn` and `TZEROn` cards and formatted following the `TDISPn` cards when
+    /// present, the padding being removed. The integers that are not scaled are written from their stored
+    /// value, so that 64-bit identifiers are kept exactly. Logical values are `T` or `F`, bits are given as a string
+    /// of `0` and `1` and complex values as `(re,im)`. The values of a field with a repeat count
+    /// greater than 1 are separated by spaces.
+    ///
+    /// Undefined values, i.e. integers equal to `TNULLn`, NaN floating point values and logical
+    /// values neither `T` nor `F`, are written as empty fields. The variable length arrays of `P` and
+    /// `Q` fields are not read from the heap and are left empty too. Fields containing a comma, a double
+    /// quote or a line break are quoted.
+    ///
+    /// # Params
+    /// * `hdu` - a binary table HDU given by iterating over this [Fits]
+    /// * `w` - where to write the CSV
+    pub fn table_to_csv<W: Write>(
+        &'a mut self,
+        hdu: &HDU<BinTable>,
+        mut w: W,
+    ) -> Result<(), Error> {
+        let header = hdu.get_header();
+        let ctx = header.get_xtension();
+        let num_cols = ctx.tforms.len();
+
+        let names = (0..num_cols)
+            .map(|col| match ctx.ttypes.get(col).and_then(Option::as_deref) {
+                Some(name) => name.trim().to_owned(),
+                None => format!("col{}", col + 1),
+            })
+            .collect::<Vec<_>>();
+        write_csv_line(&mut w, &names)?;
+
+        let formats = (0..num_cols)
+            .map(|col| {
+                header.get_tdisp(col).unwrap_or_else(|e| {
+                    warn!("{e}, the values of field {col} are not formatted");
+                    None
+                })
+            })
+            .collect::<Vec<_>>();
+        let numeric_fields = (0..num_cols)
+            .map(|col| NumericField::new(header, col).ok())
+            .collect::<Vec<_>>();
+        let format_number = |col: usize, value: f64| match &formats[col] {
+            Some(format) => format.format(value).trim().to_owned(),
+            None => value.to_string(),
+        };
+        let format_integer = |col: usize, value: i64| match &formats[col] {
+            Some(format) => format.format_integer(value).trim().to_owned(),
+            None => value.to_string(),
+        };
+
+        let num_rows = ctx.get_num_rows();
+        let mut reader = self.get_data(hdu).table_data().bytes();
+        let mut row = vec![0; ctx.naxis1 as usize];
+        let mut fields = Vec::with_capacity(num_cols);
+        for _ in 0..num_rows {
+            reader.read_exact(&mut row)?;
+
+            fields.clear();
+            for (col, tform) in ctx.tforms.iter().enumerate() {
+                let offset = ctx.column_offsets()[col];
+                let bytes = &row[offset..offset + tform.num_bytes_field()];
+                let values = match (tform, &numeric_fields[col]) {
+                    // 64-bit integers, e.g. identifiers, cannot be represented exactly by `f64`
+                    (_, Some(field)) if field.is_unscaled_integer() => field
+                        .integer_values(&row)
+                        .map(|value| value.map(|v| format_integer(col, v)).unwrap_or_default())
+                        .collect::<Vec<_>>(),
+                    (_, Some(field)) => field
+                        .values(&row)
+                        .map(|value| value.map(|v| format_number(col, v)).unwrap_or_default())
+                        .collect::<Vec<_>>(),
+                    (TFormType::L { .. }, _) => bytes
+                        .iter()
+                        .map(|b| match b {
+                            b'T' => "T".to_owned(),
+                            b'F' => "F".to_owned(),
+                            _ => String::new(),
+                        })
+                        .collect(),
+                    (TFormType::X { repeat_count }, _) => vec![(0..*repeat_count)
+                        .map(|i| {
+                            if bytes[i / 8] & (0x80 >> (i % 8)) != 0 {
+                                '1'
+                            } else {
+                                '0'
+                            }
+                        })
+                        .collect()],
+                    (TFormType::A { .. }, _) => vec![decode_fixed_width_string(bytes)],
+                    (TFormType::C { .. }, _) => bytes
+                        .chunks(8)
+                        .map(|c| {
+                            let re = f64::from(BigEndian::read_f32(&c[..4]));
+                            let im = f64::from(BigEndian::read_f32(&c[4..]));
+                            format!("({},{})", format_number(col, re), format_number(col, im))
+                        })
+                        .collect(),
+                    (TFormType::M { .. }, _) => bytes
+                        .chunks(16)
+                        .map(|c| {
+                            let re = BigEndian::read_f64(&c[..8]);
+                            let im = BigEndian::read_f64(&c[8..]);
+                            format!("({},{})", format_number(col, re), format_number(col, im))
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                fields.push(values.join(" "));
+            }
+            write_csv_line(&mut w, &fields)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the range of the physical values of an image HDU
+    ///
+    /// The `DATAMIN` and `DATAMAX` cards are used when both are present, avoiding to read the data unit.
+    /// Otherwise, the range is computed from the pixels, skipping the undefined ones, and scaled by
+    /// the `BSCALE` and `BZERO` cards. Returns `None` if the image does not contain any defined pixel.
+    ///
+    /// # Params
+    /// * `hdu` - an image HDU given by iterating over this [Fits]
+    pub fn data_range(&'a mut self, hdu: &HDU<Image>) -> Result<Option<DataRange>, Error> {
+        let header = hdu.get_header();
+        if let (Some(min), Some(max)) = (header.data_min(), header.data_max()) {
+            return Ok(Some(DataRange {
+                min,
+                max,
+                from_header: true,
+            }));
+        }
+
+        let bscale = header.get_bscale()?;
+        let bzero = header.get_bzero()?;
+        let range = self.get_data(hdu).range().map(|(min, max)| {
+            let (min, max) = (bzero + bscale * min, bzero + bscale * max);
+            DataRange {
+                min: min.min(max),
+                max: min.max(max),
+                from_header: false,
+            }
+        });
+
+        Ok(range)
+    }
+}
+
+impl<R> Fits<R>
+where
+    R: Read + Seek,
+{
+    /// Check a HDU that has just been parsed against the parsing options
+    fn check_options(&mut self, hdu: &hdu::HDU) -> Result<(), Error> {
+        if let Some(max_data_bytes) = self.options.get_max_data_bytes() {
+            let num_bytes = hdu.get_data_unit_byte_size();
+            if num_bytes > max_data_bytes {
+                return Err(Error::DynamicError(format!(
+                    "The data unit of {num_bytes} bytes exceeds the maximum of {max_data_bytes} bytes"
+                )));
+            }
+        }
+
+        if self.options.is_strict() {
+            check_standard(hdu, self.options.get_standard())?;
+            check_table_fields(hdu)?;
+        } else if self.options.get_standard() < FitsVersion::V4_0
+            && has_undeclared_continuation(hdu)
+        {
+            warn!(
+                "CONTINUE cards are found without the LONGSTRN card declaring the long string convention. They are still reassembled."
+            );
+        }
+
+        if self.options.is_verifying_checksum() {
+            self.verify_checksum(hdu)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the checksum of the data unit of a HDU, i.e. the value of its `DATASUM` card
+    ///
+    /// This is the raw 32-bit ones' complement sum of the data unit and its padding, computed in a single
+    /// pass without verifying the `CHECKSUM` card of the whole HDU. The stored `DATASUM` card is not read,
+    /// it is up to the caller to compare it. The reader is moved back to the beginning of the data unit.
+    ///
+    /// # Params
+    /// * `hdu` - a HDU given by iterating over this [Fits]
+    pub fn compute_datasum(&mut self, hdu: &hdu::HDU) -> Result<u32, Error> {
+        let data_unit_byte_offset = hdu.get_data_unit_byte_offset();
+        let num_data_blocks = hdu.get_data_unit_byte_size().div_ceil(2880);
+
+        let mut block = [0_u8; 2880];
+        self.reader.seek(SeekFrom::Start(data_unit_byte_offset))?;
+        let mut data_sum = 0;
+        for _ in 0..num_data_blocks {
+            self.reader.read_exact(&mut block)?;
+            data_sum = checksum(&block, data_sum);
+        }
+        self.reader.seek(SeekFrom::Start(data_unit_byte_offset))?;
+
+        Ok(data_sum)
+    }
+
+    /// Verify the `CHECKSUM` and `DATASUM` cards of a HDU if present
+    ///
+    /// The HDU is read block by block and the reader is moved back to the beginning of its data unit.
+    fn verify_checksum(&mut self, hdu: &hdu::HDU) -> Result<(), Error> {
+        let values = hdu.get_values();
+        let has_checksum = values.get_str("CHECKSUM").is_some();
+        let datasum = values
+            .get_str("DATASUM")
+            .map(|s| s.trim().parse::<u32>().ok());
+        if !has_checksum && datasum.is_none() {
+            return Ok(());
+        }
+
+        let header_byte_offset = hdu.get_header_byte_offset();
+        let data_unit_byte_offset = hdu.get_data_unit_byte_offset();
+        let num_header_blocks = (data_unit_byte_offset - header_byte_offset) / 2880;
+
+        let mut block = [0_u8; 2880];
+        self.reader.seek(SeekFrom::Start(header_byte_offset))?;
+        let mut header_sum = 0;
+        for _ in 0..num_header_blocks {
+            self.reader.read_exact(&mut block)?;
+            header_sum = checksum(&block, header_sum);
+        }
+        let data_sum = self.compute_datasum(hdu)?;
+
+        if datasum.is_some_and(|datasum| datasum != Some(data_sum)) {
+            return Err(Error::DynamicError(format!(
+                "DATASUM of the HDU starting at byte {header_byte_offset} does not match"
+            )));
+        }
+
+        // The sum of the whole HDU is negative zero in ones' complement
+        let sum = checksum(&data_sum.to_be_bytes(), header_sum);
+        if has_checksum && sum != 0xFFFFFFFF {
+            return Err(Error::DynamicError(format!(
+                "CHECKSUM of the HDU starting at byte {header_byte_offset} does not verify"
+            )));
+        }
+
+        Ok(())
     }
 }
 
 impl<'a, R> Iterator for Fits<R>
 where
-    R: FitsRead<'a, Image> + FitsRead<'a, AsciiTable> + FitsRead<'a, BinTable> + Debug + 'a + Seek,
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + Debug
+        + 'a
+        + Seek,
 {
     type Item = Result<hdu::HDU, Error>;
 
@@ -118,10 +1192,13 @@ where
             let n = if !self.start {
                 // We must consume the bytes until the next header is found
                 // if eof then the iterator finishes
-                match self.consume_until_next_hdu() {
+                match self
+                    .consume_until_next_hdu()
+                    .and_then(|()| self.check_next_header_alignment())
+                {
                     Ok(()) => {
                         let mut num_bytes_read = 0;
-                        match hdu::HDU::new_xtension(&mut self.reader, &mut num_bytes_read) {
+                        match hdu::HDU::new_xtension(&mut self.reader, &mut num_bytes_read, self.options.is_strict()) {
                                 Ok(hdu) => Some(Ok(hdu)),
                                 Err(Error::Io(kind))
                                     // an EOF has been encountered but the number of bytes read is 0
@@ -136,9 +1213,10 @@ where
                 }
             } else {
                 // primary HDU parsing
-                let hdu = hdu::HDU::new_primary(&mut self.reader);
+                let hdu = hdu::HDU::new_primary(&mut self.reader, self.options.is_strict());
                 Some(hdu)
             };
+            let n = n.map(|hdu| hdu.and_then(|hdu| self.check_options(&hdu).map(|()| hdu)));
 
             self.start = false;
 
@@ -161,6 +1239,192 @@ where
     }
 }
 
+impl<R> Fits<R> {
+    /// Consume the [Fits] and iterate over its HDUs, each one owning the bytes of its data unit
+    ///
+    /// Contrary to iterating over the [Fits] and calling [Fits::get_data], the items do not borrow
+    /// the reader. Only the data unit of the current HDU is held in memory so that dropping each
+    /// [OwnedHDU] once processed keeps the memory used bounded by the largest data unit of the file.
+    pub fn into_hdus(self) -> IntoHDUs<R> {
+        IntoHDUs { fits: self }
+    }
+}
+
+/// Write a line of CSV, quoting the fields that need it
+fn write_csv_line<W: Write>(w: &mut W, fields: &[String]) -> Result<(), Error> {
+    let line = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_owned()
+            }
+        })
+        .collect::<Vec<_>>();
+    writeln!(w, "{}", line.join(","))?;
+
+    Ok(())
+}
+
+/// A numeric field of a binary table decoded to its physical values
+struct NumericField {
+    tform: TFormType,
+    // The byte offset of the field inside a row
+    byte_offset: usize,
+    repeat_count: usize,
+    // The size in bytes of one stored value
+    byte_size: usize,
+    tscal: f64,
+    tzero: f64,
+    tnull: Option<i64>,
+}
+
+impl NumericField {
+    fn new(header: &Header<BinTable>, col: usize) -> Result<Self, Error> {
+        let ctx = header.get_xtension();
+        let (repeat_count, byte_size) = match ctx.tforms.get(col) {
+            Some(TFormType::B { repeat_count }) => (*repeat_count, 1),
+            Some(TFormType::I { repeat_count }) => (*repeat_count, 2),
+            Some(TFormType::J { repeat_count } | TFormType::E { repeat_count }) => {
+                (*repeat_count, 4)
+            }
+            Some(TFormType::K { repeat_count } | TFormType::D { repeat_count }) => {
+                (*repeat_count, 8)
+            }
+            _ => {
+                return Err(Error::DynamicError(format!(
+                    "Field {col} is not a numeric field"
+                )))
+            }
+        };
+
+        Ok(Self {
+            tform: ctx.tforms[col],
+            byte_offset: ctx.column_offsets()[col],
+            repeat_count,
+            byte_size,
+            tscal: header.get_tscal(col)?,
+            tzero: header.get_tzero(col)?,
+            tnull: header.get_tnull(col)?,
+        })
+    }
+
+    /// Whether the field stores integers that are not scaled, i.e. whose physical values are the stored ones
+    fn is_unscaled_integer(&self) -> bool {
+        let is_integer = matches!(
+            self.tform,
+            TFormType::B { .. } | TFormType::I { .. } | TFormType::J { .. } | TFormType::K { .. }
+        );
+        is_integer && self.tscal == 1.0 && self.tzero == 0.0
+    }
+
+    /// Get the bytes of the field in a row
+    fn bytes<'a>(&self, row: &'a [u8]) -> &'a [u8] {
+        &row[self.byte_offset..self.byte_offset + self.repeat_count * self.byte_size]
+    }
+
+    /// Decode the values of the field in a row, `None` being an undefined value
+    fn values<'a>(&'a self, row: &'a [u8]) -> impl Iterator<Item = Option<f64>> + 'a {
+        self.bytes(row)
+            .chunks(self.byte_size)
+            .map(move |bytes| self.decode(bytes))
+    }
+
+    /// Decode the stored integers of the field in a row without going through `f64`, `None` being
+    /// an undefined value. It must only be called for fields storing integers
+    fn integer_values<'a>(&'a self, row: &'a [u8]) -> impl Iterator<Item = Option<i64>> + 'a {
+        self.bytes(row).chunks(self.byte_size).map(move |bytes| {
+            self.decode_integer(bytes)
+                .filter(|&stored| Some(stored) != self.tnull)
+        })
+    }
+
+    /// Decode a stored integer, returning `None` for a floating point field
+    fn decode_integer(&self, bytes: &[u8]) -> Option<i64> {
+        match self.tform {
+            TFormType::B { .. } => Some(i64::from(bytes[0])),
+            TFormType::I { .. } => Some(i64::from(BigEndian::read_i16(bytes))),
+            TFormType::J { .. } => Some(i64::from(BigEndian::read_i32(bytes))),
+            TFormType::K { .. } => Some(BigEndian::read_i64(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Decode a stored value, returning `None` if it is undefined
+    fn decode(&self, bytes: &[u8]) -> Option<f64> {
+        let (stored, defined) = match (self.decode_integer(bytes), self.tform) {
+            (Some(stored), _) => (stored as f64, Some(stored) != self.tnull),
+            (None, TFormType::E { .. }) => {
+                let stored = f64::from(BigEndian::read_f32(bytes));
+                (stored, !stored.is_nan())
+            }
+            (None, _) => {
+                let stored = BigEndian::read_f64(bytes);
+                (stored, !stored.is_nan())
+            }
+        };
+
+        defined.then_some(self.tzero + self.tscal * stored)
+    }
+}
+
+/// A HDU together with the raw bytes of its data unit, given by [Fits::into_hdus]
+#[derive(Debug)]
+pub struct OwnedHDU {
+    /// The header of the HDU and the position of its data unit
+    pub hdu: hdu::HDU,
+    /// The bytes of the data unit in the FITS (big endian) order, excluding the padding bytes
+    pub data: Vec<u8>,
+}
+
+/// An iterator consuming a [Fits] and yielding [OwnedHDU]s, created by [Fits::into_hdus]
+#[derive(Debug)]
+pub struct IntoHDUs<R> {
+    fits: Fits<R>,
+}
+
+impl<'a, R> Iterator for IntoHDUs<R>
+where
+    R: FitsRead<'a, Image>
+        + FitsRead<'a, AsciiTable>
+        + FitsRead<'a, BinTable>
+        + FitsRead<'a, Unknown>
+        + Debug
+        + 'a
+        + Seek,
+{
+    type Item = Result<OwnedHDU, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hdu = match self.fits.next()? {
+            Ok(hdu) => hdu,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let num_bytes = hdu.get_data_unit_byte_size();
+        let mut data = Vec::new();
+        let read = (&mut self.fits.reader)
+            .take(num_bytes)
+            .read_to_end(&mut data)
+            .map_err(Error::from)
+            .and_then(|num_bytes_read| {
+                if num_bytes_read as u64 == num_bytes {
+                    Ok(())
+                } else {
+                    Err(Error::Io(std::io::ErrorKind::UnexpectedEof))
+                }
+            });
+        if let Err(e) = read {
+            // The data unit is truncated, the following HDUs cannot be found
+            self.fits.error_parsing_encountered = true;
+            return Some(Err(e));
+        }
+
+        Some(Ok(OwnedHDU { hdu, data }))
+    }
+}
+
 /// A generic over a HDU type
 #[derive(Debug, PartialEq)]
 pub struct HDU<X>
@@ -169,6 +1433,7 @@ where
 {
     /// The header part that stores all the cards
     header: Header<X>,
+    header_byte_offset: u64,
     data_unit_byte_offset: u64,
 }
 
@@ -176,8 +1441,16 @@ impl<X> HDU<X>
 where
     X: Xtension + std::fmt::Debug,
 {
+    /// Create a HDU from the cards of its header
+    ///
+    /// # Params
+    /// * `reader` - the reader positioned just after the `END` card
+    /// * `header_byte_offset` - the byte index where the header starts
+    /// * `num_bytes_read` - the number of bytes of the cards, excluding any line ending bytes skipped between them
+    /// * `cards` - the cards of the header
     pub fn new<'a, R>(
         reader: &mut R,
+        header_byte_offset: u64,
         num_bytes_read: &mut usize,
         cards: Vec<Card>,
     ) -> Result<Self, Error>
@@ -185,10 +1458,10 @@ where
         R: FitsRead<'a, X> + Seek + 'a,
     {
         /* 1. Parse the header first */
-        let header = Header::parse(cards)?;
+        let header = Header::parse(cards).map_err(|e| e.offset_by(header_byte_offset))?;
         /* 2. Skip the next bytes to a new 2880 multiple of bytes
         This is where the data block should start */
-        let is_remaining_bytes = ((*num_bytes_read) % 2880) > 0;
+        let is_remaining_bytes = !(*num_bytes_read).is_multiple_of(2880);
 
         // Skip the remaining bytes to set the reader where a new HDU begins
         if is_remaining_bytes {
@@ -206,14 +1479,28 @@ where
 
         Ok(Self {
             header,
+            header_byte_offset,
             data_unit_byte_offset,
         })
     }
 
+    /// Keep the bytes the cards of the header have been parsed from
+    pub(crate) fn with_raw_cards(self, raw_cards: Vec<CardBuf>) -> Self {
+        Self {
+            header: self.header.with_raw_cards(raw_cards),
+            ..self
+        }
+    }
+
     pub fn get_header(&self) -> &Header<X> {
         &self.header
     }
 
+    /// Get the byte index where the header of the HDU starts
+    pub fn get_header_byte_offset(&self) -> u64 {
+        self.header_byte_offset
+    }
+
     pub fn get_data_unit_byte_offset(&self) -> u64 {
         self.data_unit_byte_offset
     }
@@ -223,3 +1510,1324 @@ where
         xtension.get_num_bytes_data_block()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Fits;
+    use crate::card::CardBuf;
+    use crate::error::Error;
+    use crate::hdu::data::bintable::{ColumnValue, NullPolicy};
+    use crate::hdu::HDU;
+    use crate::options::{FitsOptions, FitsVersion};
+    use crate::test_utils::{bintable_cards, first_bintable, first_primary, mock_hdu};
+    use crate::Pixels;
+    use std::io::{Cursor, Read};
+
+    fn mock_fits_file(with_extension: bool) -> Vec<u8> {
+        let mut data = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                    8                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                   10                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 10],
+        );
+        if with_extension {
+            data.extend(mock_hdu(
+                &[
+                    b"XTENSION= 'IMAGE   '                                                            ",
+                    b"BITPIX  =                  -32                                                  ",
+                    b"NAXIS   =                    2                                                  ",
+                    b"NAXIS1  =                   40                                                  ",
+                    b"NAXIS2  =                   20                                                  ",
+                    b"END                                                                             ",
+                ],
+                &[0; 40 * 20 * 4],
+            ));
+        }
+        data
+    }
+
+    #[test]
+    fn parse_concatenated_fits_stream() {
+        let mut buf = mock_fits_file(true);
+        buf.extend(mock_fits_file(false));
+        buf.extend(mock_fits_file(true));
+
+        let num_hdus = Fits::parse_stream(&buf)
+            .map(|fits| {
+                let hdus = fits.unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+                assert!(matches!(hdus[0], HDU::Primary(_)));
+                hdus.len()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(num_hdus, [2, 1, 2]);
+    }
+
+    #[test]
+    fn parse_from_byte_containers() {
+        let buf = mock_fits_file(true);
+
+        assert_eq!(Fits::from_bytes(&buf).count(), 2);
+        assert_eq!(Fits::from(&buf[..]).count(), 2);
+
+        let cursor = Cursor::new(buf);
+        assert_eq!(Fits::from_bytes(cursor.get_ref()).count(), 2);
+    }
+
+    #[test]
+    fn parse_embedded_at_offset() {
+        let mut buf = b"CONTAINER HEADER".to_vec();
+        buf.extend(mock_fits_file(true));
+
+        let hdus = Fits::from_bytes_at(&buf, 16)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(hdus.len(), 2);
+        assert_eq!(hdus[1].get_header_byte_offset(), 2 * 2880);
+
+        assert!(Fits::from_bytes_at(&buf, 0).is_err());
+        assert!(Fits::from_bytes_at(&buf, 17).is_err());
+        assert!(Fits::from_bytes_at(&buf, buf.len() + 1).is_err());
+    }
+
+    #[test]
+    fn undecoded_data_unit() {
+        let mut buf = mock_fits_file(true);
+        buf[2880..2890].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        let mut hdu_list = Fits::from_bytes(&buf);
+        let primary = hdu_list.next().unwrap().unwrap();
+        let image = hdu_list.next().unwrap().unwrap();
+        assert_eq!(
+            hdu_list.data_unit_bytes(&primary),
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+        );
+        assert_eq!(hdu_list.data_unit_bytes(&image).len(), 40 * 20 * 4);
+
+        // Truncated data unit
+        let mut hdu_list = Fits::from_bytes(&buf[..2885]);
+        let primary = hdu_list.next().unwrap().unwrap();
+        assert_eq!(hdu_list.data_unit_bytes(&primary), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn character_fields() {
+        let mut buf = mock_fits_file(false);
+        let mut table = mock_hdu(
+            &bintable_cards(
+                24,
+                2,
+                0,
+                &[("NAME", "8A"), ("ID", "1J"), ("FILTERS", "12A")],
+                &["TDIM3   = '(4,3)   '"],
+            ),
+            &[0; 2 * 24],
+        );
+        table[2880..2880 + 48]
+            .copy_from_slice(b" NGC 1  \0\0\0\x01ab  c   d\0\0\0M31\0junk\0\0\0\x02  x yy  zzzz");
+        buf.extend(table);
+
+        let (mut hdu_list, hdu) = first_bintable(&buf);
+        assert_eq!(hdu.get_header().get_tdim(2).unwrap(), Some(vec![4, 3]));
+        assert_eq!(hdu.get_header().get_tdim(0).unwrap(), None);
+
+        assert_eq!(hdu_list.column_strings(&hdu, 0).unwrap(), [" NGC 1", "M31"]);
+        let (mut hdu_list, hdu) = first_bintable(&buf);
+        assert_eq!(
+            hdu_list.column_strings(&hdu, 2).unwrap(),
+            ["ab", "c", "d", "  x", "yy", "zzzz"]
+        );
+        let (mut hdu_list, hdu) = first_bintable(&buf);
+        assert!(hdu_list.column_strings(&hdu, 1).is_err());
+    }
+
+    #[test]
+    fn numeric_fields_as_vec() {
+        fn read<T: ColumnValue>(
+            buf: &[u8],
+            col: usize,
+            nulls: NullPolicy,
+        ) -> Result<Vec<T>, Error> {
+            let (mut hdu_list, hdu) = first_bintable(buf);
+            hdu_list.column_as_vec::<T>(&hdu, col, nulls)
+        }
+
+        let mut buf = mock_fits_file(false);
+        let mut table = mock_hdu(
+            &bintable_cards(
+                8,
+                3,
+                0,
+                &[("", "2I"), ("", "1E")],
+                &[
+                    "TSCAL1  =                  0.5",
+                    "TZERO1  =                   10",
+                    "TNULL1  =                   -1",
+                ],
+            ),
+            &[0; 3 * 8],
+        );
+        let rows = [(2_i16, 4_i16, 1.5_f32), (-1, 6, f32::NAN), (0, 8, 3.0)];
+        for (i, (a, b, c)) in rows.iter().enumerate() {
+            let row = &mut table[2880 + i * 8..2880 + (i + 1) * 8];
+            row[..2].copy_from_slice(&a.to_be_bytes());
+            row[2..4].copy_from_slice(&b.to_be_bytes());
+            row[4..].copy_from_slice(&c.to_be_bytes());
+        }
+        buf.extend(table);
+
+        // An integer field converted to floats
+        assert_eq!(
+            read::<f64>(&buf, 0, NullPolicy::Default).unwrap(),
+            [11.0, 12.0, 0.0, 13.0, 10.0, 14.0]
+        );
+        assert_eq!(
+            read::<u8>(&buf, 0, NullPolicy::Default).unwrap(),
+            [11, 12, 0, 13, 10, 14]
+        );
+        assert_eq!(
+            read::<f64>(&buf, 0, NullPolicy::Error).unwrap_err(),
+            Error::DynamicError("Field 0 has an undefined value at row 1".to_owned())
+        );
+
+        assert_eq!(
+            read::<f32>(&buf, 1, NullPolicy::Default).unwrap(),
+            [1.5, 0.0, 3.0]
+        );
+        assert!(read::<f32>(&buf, 1, NullPolicy::Error).is_err());
+        // 1.5 is not an integer
+        assert!(read::<i32>(&buf, 1, NullPolicy::Default).is_err());
+        assert!(read::<f64>(&buf, 2, NullPolicy::Default).is_err());
+    }
+
+    #[test]
+    fn photon_event_list() {
+        let mock_events = |energy_ttype: &str| {
+            let mut buf = mock_fits_file(false);
+            let mut table = mock_hdu(
+                &bintable_cards(
+                    14,
+                    2,
+                    0,
+                    &[
+                        ("TIME", "1D"),
+                        ("X", "1I"),
+                        ("Y", "1I"),
+                        (energy_ttype, "1I"),
+                    ],
+                    &[
+                        "TNULL3  =                   -1",
+                        "TZERO4  =                32768",
+                    ],
+                ),
+                &[0; 2 * 14],
+            );
+            let rows = [(1.25_f64, 100_i16, 200_i16, -32000_i16), (2.5, 101, -1, 0)];
+            for (i, (time, x, y, energy)) in rows.iter().enumerate() {
+                let row = &mut table[2880 + i * 14..2880 + (i + 1) * 14];
+                row[..8].copy_from_slice(&time.to_be_bytes());
+                row[8..10].copy_from_slice(&x.to_be_bytes());
+                row[10..12].copy_from_slice(&y.to_be_bytes());
+                row[12..14].copy_from_slice(&energy.to_be_bytes());
+            }
+            buf.extend(table);
+            buf
+        };
+        let read = |buf: &[u8]| {
+            let (mut hdu_list, hdu) = first_bintable(buf);
+            hdu_list.event_list(&hdu)
+        };
+
+        let events = read(&mock_events("pha")).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events.times(), [1.25, 2.5]);
+        assert_eq!(events.positions()[0], (100.0, 200.0));
+        assert_eq!(events.positions()[1].0, 101.0);
+        // Undefined values are NaN
+        assert!(events.positions()[1].1.is_nan());
+        assert_eq!(events.energies(), [768.0, 32768.0]);
+
+        assert_eq!(
+            read(&mock_events("FLUX")),
+            Err(Error::DynamicError(
+                "The table is not an event list, no ENERGY or PI or PHA field has been found"
+                    .to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn table_as_csv() {
+        let mut buf = mock_fits_file(false);
+        let mut table = mock_hdu(
+            &bintable_cards(
+                21,
+                2,
+                0,
+                &[
+                    ("NAME", "8A"),
+                    ("MAG", "1E"),
+                    ("FLAG", "1L"),
+                    ("ID", "1J"),
+                    ("", "2I"),
+                ],
+                &["TDISP2  = 'F6.2    '", "TNULL4  =                   -1"],
+            ),
+            &[0; 2 * 21],
+        );
+        let rows = [
+            (b"Vega, A ", 0.0312_f32, b'T', 1_i32, [1_i16, 2]),
+            (b"Sirius  ", f32::NAN, 0, -1, [3, -4]),
+        ];
+        for (i, (name, mag, flag, id, pair)) in rows.iter().enumerate() {
+            let row = &mut table[2880 + i * 21..2880 + (i + 1) * 21];
+            row[..8].copy_from_slice(*name);
+            row[8..12].copy_from_slice(&mag.to_be_bytes());
+            row[12] = *flag;
+            row[13..17].copy_from_slice(&id.to_be_bytes());
+            row[17..19].copy_from_slice(&pair[0].to_be_bytes());
+            row[19..21].copy_from_slice(&pair[1].to_be_bytes());
+        }
+        buf.extend(table);
+
+        let (mut hdu_list, hdu) = first_bintable(&buf);
+        let mut csv = Vec::new();
+        hdu_list.table_to_csv(&hdu, &mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "NAME,MAG,FLAG,ID,col5\n\"Vega, A\",0.03,T,1,1 2\nSirius,,,,3 -4\n"
+        );
+    }
+
+    #[test]
+    fn table_as_csv_64_bit_integers() {
+        let mut buf = mock_fits_file(false);
+        let mut table = mock_hdu(
+            &bintable_cards(
+                24,
+                2,
+                0,
+                &[("SOURCE_ID", "1K"), ("ID_FMT", "1K"), ("SCALED", "1K")],
+                &[
+                    "TNULL1  =                   -1",
+                    "TDISP2  = 'I20     '",
+                    "TSCAL3  =                  2.0",
+                ],
+            ),
+            &[0; 2 * 24],
+        );
+        // 2^53 + 1 is not representable by a f64
+        let rows = [
+            [5853498713190525696_i64, 9007199254740993, 3],
+            [-1, -9007199254740993, -4],
+        ];
+        for (i, row) in rows.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let start = 2880 + i * 24 + j * 8;
+                table[start..start + 8].copy_from_slice(&value.to_be_bytes());
+            }
+        }
+        buf.extend(table);
+
+        let (mut hdu_list, hdu) = first_bintable(&buf);
+        let mut csv = Vec::new();
+        hdu_list.table_to_csv(&hdu, &mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "SOURCE_ID,ID_FMT,SCALED\n5853498713190525696,9007199254740993,6\n,-9007199254740993,-8\n"
+        );
+    }
+
+    #[test]
+    fn mismatched_number_of_fields() {
+        let mock_table = |tfields: &CardBuf| {
+            let mut buf = mock_fits_file(false);
+            buf.extend(mock_hdu(
+                &[
+                    b"XTENSION= 'BINTABLE'                                                            ",
+                    b"BITPIX  =                    8                                                  ",
+                    b"NAXIS   =                    2                                                  ",
+                    b"NAXIS1  =                    6                                                  ",
+                    b"NAXIS2  =                    1                                                  ",
+                    b"PCOUNT  =                    0                                                  ",
+                    b"GCOUNT  =                    1                                                  ",
+                    tfields,
+                    b"TTYPE1  = 'ID      '                                                            ",
+                    b"TFORM1  = '1J      '                                                            ",
+                    b"TFORM2  = '1I      '                                                            ",
+                    b"END                                                                             ",
+                ],
+                &[0; 6],
+            ));
+            buf
+        };
+        let parse = |buf: &[u8], strict: bool| {
+            let options = FitsOptions::new().strict(strict);
+            match Fits::from_reader_with(Cursor::new(buf), options).nth(1) {
+                Some(Ok(HDU::XBinaryTable(hdu))) => {
+                    Ok(hdu.get_header().get_xtension().get_num_cols())
+                }
+                Some(Err(e)) => Err(e),
+                _ => panic!("expected a binary table"),
+            }
+        };
+
+        let buf = mock_table(
+            b"TFIELDS =                    2                                                  ",
+        );
+        assert_eq!(parse(&buf, true), Ok(2));
+
+        // Less TFORMn cards than declared
+        let buf = mock_table(
+            b"TFIELDS =                    3                                                  ",
+        );
+        assert_eq!(
+            parse(&buf, true),
+            Err(Error::TableFieldMismatch {
+                declared: 3,
+                found: 2
+            })
+        );
+        assert_eq!(parse(&buf, false), Ok(2));
+
+        // More TFORMn cards than declared
+        let buf = mock_table(
+            b"TFIELDS =                    1                                                  ",
+        );
+        assert_eq!(
+            parse(&buf, true),
+            Err(Error::TableFieldMismatch {
+                declared: 1,
+                found: 2
+            })
+        );
+        assert_eq!(parse(&buf, false), Ok(2));
+    }
+
+    #[test]
+    fn data_unit_sizes_with_pcount_gcount() {
+        let mut buf = mock_fits_file(false);
+        buf.extend(mock_hdu(
+            &[
+                b"XTENSION= 'IMAGE   '                                                            ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                 1000                                                  ",
+                b"PCOUNT  =                  500                                                  ",
+                b"GCOUNT  =                    2                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 2 * 2 * (500 + 1000)],
+        ));
+        buf.extend(mock_hdu(
+            &bintable_cards(8, 1, 2880, &[("", "1PB")], &[]),
+            &[0; 8 + 2880],
+        ));
+        buf.extend(mock_hdu(
+            &[
+                b"XTENSION= 'IMAGE   '                                                            ",
+                b"BITPIX  =                    8                                                  ",
+                b"NAXIS   =                    0                                                  ",
+                b"END                                                                             ",
+            ],
+            &[],
+        ));
+
+        let hdus = Fits::from_bytes(&buf)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let offsets = hdus
+            .iter()
+            .map(|hdu| (hdu.get_header_byte_offset(), hdu.get_data_unit_byte_size()))
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, [(0, 10), (5760, 6000), (17280, 2888), (25920, 0)]);
+    }
+
+    #[test]
+    fn misaligned_data_unit() {
+        let extension = mock_hdu(
+            &[
+                b"XTENSION= 'IMAGE   '                                                            ",
+                b"BITPIX  =                    8                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    4                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 4],
+        );
+        let primary = mock_fits_file(false);
+
+        // The padding of the primary data unit is missing or is followed by an extra block
+        let mut unpadded = primary[..2880 + 10].to_vec();
+        unpadded.extend(&extension);
+        let mut extra_block = primary.clone();
+        extra_block.extend([0; 2880]);
+        extra_block.extend(&extension);
+
+        for (buf, header_byte_offset) in [(unpadded, 2890), (extra_block, 8640)] {
+            let offsets = Fits::from_bytes(&buf)
+                .map(|hdu| hdu.map(|hdu| hdu.get_header_byte_offset()))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(offsets, [0, header_byte_offset]);
+
+            let mut hdu_list =
+                Fits::from_reader_with(Cursor::new(&buf[..]), FitsOptions::default().strict(true));
+            assert!(hdu_list.next().unwrap().is_ok());
+            let Some(Err(Error::DynamicError(msg))) = hdu_list.next() else {
+                panic!("expected the misaligned HDU to be rejected");
+            };
+            assert!(msg.contains("not aligned"));
+        }
+    }
+
+    #[test]
+    fn data_unit_starting_with_spaces() {
+        // Pixels equal to 0x20 must not be mistaken for the padding of the header
+        let mut buf = mock_fits_file(true);
+        buf[2880..2890].copy_from_slice(&[b' ', b' ', b' ', 1, 2, 3, 4, 5, b' ', 6]);
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert_eq!(hdu.get_data_unit_byte_offset(), 2880);
+        let Some(Pixels::U8(values)) = hdu_list.get_data(&hdu).as_1d() else {
+            panic!("expected the values of a vector");
+        };
+        assert_eq!(
+            values.collect::<Vec<_>>(),
+            [32, 32, 32, 1, 2, 3, 4, 5, 32, 6]
+        );
+
+        let Some(Ok(HDU::XImage(hdu))) = hdu_list.next() else {
+            panic!("expected an image extension");
+        };
+        assert_eq!(hdu.get_header_byte_offset(), 2 * 2880);
+    }
+
+    #[test]
+    fn vector_and_image_data_units() {
+        let buf = mock_fits_file(true);
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        let xtension = hdu.get_header().get_xtension();
+        assert_eq!(xtension.get_naxis(), &[10]);
+        assert!(xtension.is_vector() && !xtension.is_image());
+        let Some(Pixels::U8(values)) = hdu_list.get_data(&hdu).as_1d() else {
+            panic!("expected the values of a vector");
+        };
+        assert_eq!(values.count(), 10);
+
+        let Some(Ok(HDU::XImage(hdu))) = hdu_list.next() else {
+            panic!("expected an image extension");
+        };
+        let xtension = hdu.get_header().get_xtension();
+        assert!(xtension.is_image() && !xtension.is_vector());
+        assert!(hdu_list.get_data(&hdu).as_1d().is_none());
+    }
+
+    #[test]
+    fn file_size_from_headers() {
+        let buf = mock_fits_file(true);
+        assert_eq!(buf.len(), 14400);
+        assert_eq!(Fits::expected_file_size(&buf), Ok(14400));
+
+        // The truncated data unit of the extension
+        assert_eq!(Fits::expected_file_size(&buf[..12000]), Ok(14400));
+        // Without the extension
+        assert_eq!(Fits::expected_file_size(&buf[..5760]), Ok(5760));
+        // A truncated header cannot give the size
+        assert!(Fits::expected_file_size(&buf[..6000]).is_err());
+    }
+
+    #[test]
+    fn parse_primary_header_only() {
+        let mut buf = mock_fits_file(true);
+        buf[2880] = 42;
+
+        let (remaining, header) = Fits::primary_header(&buf).unwrap();
+        assert_eq!(header.get_xtension().get_naxis(), &[10]);
+        assert_eq!(remaining.len(), buf.len() - 2880);
+        assert_eq!(remaining[0], 42);
+
+        assert!(Fits::primary_header(&buf[2880..]).is_err());
+    }
+
+    #[test]
+    fn parse_stream_stops_on_error() {
+        let mut buf = mock_fits_file(false);
+        buf.extend(vec![b' '; 2880]);
+
+        let mut stream = Fits::parse_stream(&buf);
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn read_header_at_index() {
+        let buf = mock_fits_file(true);
+
+        let hdu = Fits::read_header(Cursor::new(&buf[..]), 1).unwrap();
+        let HDU::XImage(hdu) = hdu else {
+            panic!("expected an image extension");
+        };
+        assert_eq!(hdu.get_header().get_xtension().get_naxis(), [40, 20]);
+        assert_eq!(hdu.get_data_unit_byte_offset(), 3 * 2880);
+
+        let hdu = Fits::read_header(Cursor::new(&buf[..]), 0).unwrap();
+        assert!(matches!(hdu, HDU::Primary(_)));
+
+        assert!(Fits::read_header(Cursor::new(&buf[..]), 2).is_err());
+    }
+
+    #[test]
+    fn summary_of_fits_file() {
+        let mut buf = mock_fits_file(true);
+        buf.extend(mock_hdu(
+            &bintable_cards(8, 5, 0, &[("", "J"), ("", "E")], &["EXTNAME = 'EVENTS  '"]),
+            &[0; 8 * 5],
+        ));
+
+        let summary = Fits::from_reader(Cursor::new(&buf[..])).summary().unwrap();
+        assert_eq!(
+            summary,
+            "No.  Name             Type         BITPIX  Dimensions\n\
+             0    PRIMARY          PrimaryHDU        8  (10)\n\
+             1                     ImageHDU        -32  (40, 20)\n\
+             2    EVENTS           BinTableHDU       8  5R x 2C\n"
+        );
+    }
+
+    #[test]
+    fn has_extensions() {
+        let buf = mock_fits_file(true);
+        assert_eq!(
+            Fits::from_reader(Cursor::new(&buf[..])).has_extensions(),
+            Ok(true)
+        );
+
+        let buf = mock_fits_file(false);
+        assert_eq!(
+            Fits::from_reader(Cursor::new(&buf[..])).has_extensions(),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn raw_bytes_of_hdus() {
+        let buf = mock_fits_file(true);
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+
+        let primary = hdu_list.next().unwrap().unwrap();
+        let xtension = hdu_list.next().unwrap().unwrap();
+
+        assert_eq!(hdu_list.raw_bytes(&primary), &buf[..2 * 2880]);
+        assert_eq!(hdu_list.raw_bytes(&xtension), &buf[2 * 2880..]);
+        assert_eq!(xtension.get_header_byte_offset(), 2 * 2880);
+
+        // Build a new FITS file from the primary HDU only
+        let stripped = hdu_list.raw_bytes(&primary).to_vec();
+        assert_eq!(stripped, mock_fits_file(false));
+        assert_eq!(Fits::from_reader(Cursor::new(&stripped[..])).count(), 1);
+    }
+
+    #[test]
+    fn card_parse_error_position() {
+        let mut buf = mock_fits_file(false);
+        buf.extend(mock_hdu(
+            &[
+                b"XTENSION= 'IMAGE   '                                                            ",
+                b"BITPIX  =                  -32                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  = 'forty'                                                               ",
+                b"NAXIS2  =                   20                                                  ",
+                b"END                                                                             ",
+            ],
+            &[],
+        ));
+
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        assert!(hdu_list.next().unwrap().is_ok());
+
+        let Some(Err(Error::CardParse {
+            card_index,
+            keyword,
+            offset,
+            ..
+        })) = hdu_list.next()
+        else {
+            panic!("expected a card parsing error");
+        };
+        assert_eq!(card_index, 3);
+        assert_eq!(keyword, "NAXIS1");
+        assert_eq!(offset, 2 * 2880 + 3 * 80);
+    }
+
+    #[test]
+    fn random_groups_not_supported() {
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                  -32                                                  ",
+                b"NAXIS   =                    3                                                  ",
+                b"NAXIS1  =                    0                                                  ",
+                b"NAXIS2  =                    3                                                  ",
+                b"NAXIS3  =                    4                                                  ",
+                b"GROUPS  =                    T                                                  ",
+                b"PCOUNT  =                    2                                                  ",
+                b"GCOUNT  =                   10                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; (2 + 3 * 4) * 10 * 4],
+        );
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        assert_eq!(
+            hdu_list.next(),
+            Some(Err(Error::StaticError(
+                "Random groups (GROUPS = T and NAXIS1 = 0) are not supported"
+            )))
+        );
+
+        // Without GROUPS, an image with a zero sized axis is a valid empty image
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                  -32                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                    0                                                  ",
+                b"NAXIS2  =                    3                                                  ",
+                b"GROUPS  =                    F                                                  ",
+                b"END                                                                             ",
+            ],
+            &[],
+        );
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert_eq!(hdu.get_data_unit_byte_size(), 0);
+        assert!(hdu_list.next().is_none());
+    }
+
+    #[test]
+    fn image_data_unit_size_overflow() {
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                  -64                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =           4294967296                                                  ",
+                b"NAXIS2  =           4294967296                                                  ",
+                b"END                                                                             ",
+            ],
+            &[],
+        );
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        assert!(matches!(hdu_list.next(), Some(Err(Error::StaticError(_)))));
+    }
+
+    #[test]
+    fn num_pixels_of_image_data() {
+        let buf = mock_fits_file(true);
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+
+        let _primary = hdu_list.next().unwrap().unwrap();
+        let Some(Ok(HDU::XImage(hdu))) = hdu_list.next() else {
+            panic!("expected an image extension");
+        };
+
+        let image = hdu_list.get_data(&hdu);
+        assert_eq!(image.get_num_pixels(), 40 * 20);
+
+        let pixels = image.pixels();
+        assert_eq!(pixels.get_num_items(), 40 * 20);
+        let Pixels::F32(it) = pixels else {
+            panic!("expected f32 pixels");
+        };
+        assert_eq!(it.count(), 40 * 20);
+    }
+
+    #[test]
+    fn axis_size_is_one_based() {
+        let buf = mock_fits_file(true);
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+
+        let _primary = hdu_list.next().unwrap().unwrap();
+        let Some(Ok(HDU::XImage(hdu))) = hdu_list.next() else {
+            panic!("expected an image extension");
+        };
+
+        let image = hdu.get_header().get_xtension();
+        assert_eq!(image.axis_size(0), None);
+        assert_eq!(image.axis_size(1), Some(40));
+        assert_eq!(image.axis_size(2), Some(20));
+        assert_eq!(image.axis_size(3), None);
+        assert_eq!(image.axis_size(1), Some(image.get_naxis()[0] as usize));
+    }
+
+    #[test]
+    fn image_physical_values_and_shape() {
+        let mut buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                    3                                                  ",
+                b"NAXIS2  =                    2                                                  ",
+                b"BSCALE  =                  0.5                                                  ",
+                b"BZERO   =              32768.0                                                  ",
+                b"BLANK   =                   -1                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 3 * 2 * 2],
+        );
+        for (i, v) in [-32768_i16, -2, -1, 0, 2, 32767].iter().enumerate() {
+            buf[2880 + 2 * i..2880 + 2 * i + 2].copy_from_slice(&v.to_be_bytes());
+        }
+        buf.extend(mock_hdu(&bintable_cards(0, 0, 0, &[], &[]), &[]));
+
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        let primary = hdu_list.next().unwrap().unwrap();
+        let (values, shape) = hdu_list.image_f64(&primary).unwrap();
+        assert_eq!(shape, [3, 2]);
+        assert_eq!(values[..2], [16384.0, 32767.0]);
+        assert!(values[2].is_nan());
+        assert_eq!(values[3..], [32768.0, 32769.0, 32768.0 + 32767.0 / 2.0]);
+
+        let table = hdu_list.next().unwrap().unwrap();
+        assert!(hdu_list.image_f64(&table).is_err());
+    }
+
+    #[test]
+    fn image_in_c_order() {
+        // 4 columns and 2 rows, each pixel being 10 * y + x
+        let mut buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                    8                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                    4                                                  ",
+                b"NAXIS2  =                    2                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 4 * 2],
+        );
+        buf[2880..2880 + 8].copy_from_slice(&[11, 12, 13, 14, 21, 22, 23, 24]);
+
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        let hdu = hdu_list.next().unwrap().unwrap();
+        let (values, shape) = hdu_list.image_c_order_f64(&hdu).unwrap();
+        assert_eq!(shape, [2, 4]);
+        let (x, y) = (3, 2);
+        assert_eq!(values[(y - 1) * shape[1] + (x - 1)], 23.0);
+        let rows = values.chunks(shape[1]).collect::<Vec<_>>();
+        assert_eq!(rows, [[11.0, 12.0, 13.0, 14.0], [21.0, 22.0, 23.0, 24.0]]);
+    }
+
+    #[test]
+    fn image_with_permuted_axes() {
+        // A cube of 3 columns, 2 rows and 2 channels, each pixel being 100 * z + 10 * y + x
+        let mut buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    3                                                  ",
+                b"NAXIS1  =                    3                                                  ",
+                b"NAXIS2  =                    2                                                  ",
+                b"NAXIS3  =                    2                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 3 * 2 * 2 * 2],
+        );
+        let mut i = 2880;
+        for z in 1..=2_i16 {
+            for y in 1..=2 {
+                for x in 1..=3 {
+                    buf[i..i + 2].copy_from_slice(&(100 * z + 10 * y + x).to_be_bytes());
+                    i += 2;
+                }
+            }
+        }
+
+        // The data unit is read once per HDU list
+        let permuted = |order: &[usize]| {
+            let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+            let hdu = hdu_list.next().unwrap().unwrap();
+            hdu_list.image_permuted_f64(&hdu, order)
+        };
+
+        // The identity gives the C order
+        let (values, shape) = permuted(&[0, 1, 2]).unwrap();
+        assert_eq!(shape, [2, 2, 3]);
+        assert_eq!(values[..4], [111.0, 112.0, 113.0, 121.0]);
+
+        // (y, x, channel)
+        let (values, shape) = permuted(&[1, 2, 0]).unwrap();
+        assert_eq!(shape, [2, 3, 2]);
+        let (x, y, z) = (3, 2, 1);
+        assert_eq!(
+            values[((y - 1) * shape[1] + (x - 1)) * shape[2] + (z - 1)],
+            123.0
+        );
+        assert_eq!(values[..6], [111.0, 211.0, 112.0, 212.0, 113.0, 213.0]);
+
+        // (x, y, channel), i.e. the FITS order of the axes
+        let (values, shape) = permuted(&[2, 1, 0]).unwrap();
+        assert_eq!(shape, [3, 2, 2]);
+        assert_eq!(values[..4], [111.0, 211.0, 121.0, 221.0]);
+
+        for order in [&[0, 1][..], &[0, 1, 1], &[0, 1, 3], &[0, 1, 2, 3]].iter() {
+            assert!(permuted(order).is_err());
+        }
+
+        // The order is checked before reading the data unit, here missing
+        let mut hdu_list = Fits::from_bytes(&buf[..2880]);
+        let hdu = hdu_list.next().unwrap().unwrap();
+        assert!(matches!(
+            hdu_list.image_permuted_f64(&hdu, &[0, 0, 1]),
+            Err(Error::DynamicError(msg)) if msg.contains("not a permutation")
+        ));
+    }
+
+    #[test]
+    fn data_range_from_header_or_pixels() {
+        let cards: [&CardBuf; 8] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                   16                                                  ",
+            b"NAXIS   =                    1                                                  ",
+            b"NAXIS1  =                    4                                                  ",
+            b"BSCALE  =                 -2.0                                                  ",
+            b"BLANK   =                    0                                                  ",
+            b"DATAMIN =                -10.0                                                  ",
+            b"DATAMAX =                 10.0                                                  ",
+        ];
+        let end: &CardBuf =
+            b"END                                                                             ";
+        let pixels = [3_i16, 0, -7, 5];
+
+        let mock = |cards: &[&CardBuf]| {
+            let mut buf = mock_hdu(cards, &[0; 8]);
+            for (i, v) in pixels.iter().enumerate() {
+                buf[2880 + 2 * i..2880 + 2 * i + 2].copy_from_slice(&v.to_be_bytes());
+            }
+            buf
+        };
+
+        let buf = mock(&[
+            cards[0], cards[1], cards[2], cards[3], cards[4], cards[5], cards[6], cards[7], end,
+        ]);
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert_eq!(hdu.get_header().data_min(), Some(-10.0));
+        let range = hdu_list.data_range(&hdu).unwrap().unwrap();
+        assert_eq!(
+            (range.min, range.max, range.from_header),
+            (-10.0, 10.0, true)
+        );
+
+        // Only DATAMIN is given, the range is computed
+        let buf = mock(&[
+            cards[0], cards[1], cards[2], cards[3], cards[4], cards[5], cards[6], end,
+        ]);
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert_eq!(hdu.get_header().data_max(), None);
+        let range = hdu_list.data_range(&hdu).unwrap().unwrap();
+        assert_eq!(
+            (range.min, range.max, range.from_header),
+            (-10.0, 14.0, false)
+        );
+    }
+
+    #[test]
+    fn parsing_options() {
+        let cards: [&CardBuf; 7] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    1                                                  ",
+            b"NAXIS1  =                   10                                                  ",
+            b"CHECKSUM= 'mAhLo9gKmAgKm7gK'                                                    ",
+            b"DATASUM = '252840460'                                                           ",
+            b"END                                                                             ",
+        ];
+        let mut buf = mock_hdu(&cards, &[0; 10]);
+        buf[2880..2890].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        buf.extend(mock_hdu(
+            &[
+                b"XTENSION= 'IMAGE   '                                                            ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                 2000                                                  ",
+                b"STRKEY  = 'unterminated                                                         ",
+                b"END                                                                             ",
+            ],
+            &[0; 4000],
+        ));
+
+        // Default options
+        let options = FitsOptions::new();
+        assert!(!options.is_strict() && !options.is_verifying_checksum());
+        assert_eq!(options.get_max_data_bytes(), None);
+        assert!(Fits::from_bytes_with(&buf, &options).all(|hdu| hdu.is_ok()));
+
+        let options = FitsOptions::new().verify_checksum(true);
+        assert!(Fits::from_bytes_with(&buf, &options).all(|hdu| hdu.is_ok()));
+
+        let options = FitsOptions::new().max_data_bytes(3000);
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        assert!(hdu_list.next().unwrap().is_ok());
+        assert!(matches!(hdu_list.next(), Some(Err(Error::DynamicError(_)))));
+
+        let options = FitsOptions::new().strict(true);
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        assert!(hdu_list.next().unwrap().is_ok());
+        let Some(Err(Error::CardParse {
+            card_index,
+            keyword,
+            offset,
+            ..
+        })) = hdu_list.next()
+        else {
+            panic!("expected a card parsing error");
+        };
+        assert_eq!((card_index, keyword.as_str()), (4, "STRKEY"));
+        assert_eq!(offset, 2 * 2880 + 4 * 80);
+
+        // Corrupt the data unit of the primary HDU
+        buf[2880] = 0;
+        let options = FitsOptions::new().verify_checksum(true);
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        assert!(hdu_list.next().unwrap().is_err());
+        assert!(hdu_list.next().is_none());
+    }
+
+    #[test]
+    fn little_endian_data_unit() {
+        let mut buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                  -32                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    3                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 3 * 4],
+        );
+        for (i, v) in [1.5_f32, -2.0, 300.25].iter().enumerate() {
+            buf[2880 + 4 * i..2880 + 4 * i + 4].copy_from_slice(&v.to_le_bytes());
+        }
+
+        let options = FitsOptions::new().assume_little_endian(true);
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        let hdu = hdu_list.next().unwrap().unwrap();
+        let (values, _) = hdu_list.image_f64(&hdu).unwrap();
+        assert_eq!(values, [1.5, -2.0, 300.25]);
+
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+        let range = hdu_list.data_range(&hdu).unwrap().unwrap();
+        assert_eq!((range.min, range.max), (-2.0, 300.25));
+
+        // The byte order is applied to the data given by `get_data`
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+        let pixels = hdu_list.get_data(&hdu).pixels();
+        assert_eq!(pixels.as_pixel_data().value_f64(1), Some(-2.0));
+        let Pixels::F32(it) = pixels else {
+            panic!("expected f32 pixels");
+        };
+        assert_eq!(it.collect::<Vec<_>>(), [1.5, -2.0, 300.25]);
+
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+        let histogram = hdu_list
+            .get_data(&hdu)
+            .histogram(2, Some((-2.0, 300.25)))
+            .unwrap();
+        assert_eq!(histogram, [2, 1]);
+
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+        let mut chunks = hdu_list.get_data_chunks(&hdu);
+        let mut values = Vec::new();
+        assert_eq!(chunks.next_chunk(&mut values), Ok(3));
+        assert_eq!(values, [1.5, -2.0, 300.25]);
+
+        // The standard byte order is kept by default and in strict mode
+        let options = options.strict(true);
+        assert!(!options.is_assuming_little_endian());
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        let hdu = hdu_list.next().unwrap().unwrap();
+        let (values, _) = hdu_list.image_f64(&hdu).unwrap();
+        assert_ne!(values[0], 1.5);
+    }
+
+    /// A primary HDU followed by an `A3DTABLE` extension and an image extension
+    fn mock_fits_file_with_unknown_extension() -> Vec<u8> {
+        let mut buf = mock_fits_file(false);
+        let mut unknown = mock_hdu(
+            &[
+                b"XTENSION= 'A3DTABLE'           / obsolete binary table                          ",
+                b"BITPIX  =                    8                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                    6                                                  ",
+                b"NAXIS2  =                  500                                                  ",
+                b"PCOUNT  =                   12                                                  ",
+                b"GCOUNT  =                    1                                                  ",
+                b"EXTNAME = 'OLDTABLE'                                                            ",
+                b"END                                                                             ",
+            ],
+            &[0; 6 * 500 + 12],
+        );
+        unknown[2880..2883].copy_from_slice(b"raw");
+        buf.extend(unknown);
+        buf.extend(mock_hdu(
+            &[
+                b"XTENSION= 'IMAGE   '                                                            ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    4                                                  ",
+                b"PCOUNT  =                    0                                                  ",
+                b"GCOUNT  =                    1                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0; 8],
+        ));
+        buf
+    }
+
+    #[test]
+    fn unknown_extension_type() {
+        let buf = mock_fits_file_with_unknown_extension();
+        let image_byte_offset = (buf.len() - 2 * 2880) as u64;
+
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        let _primary = hdu_list.next().unwrap().unwrap();
+        let Some(Ok(HDU::XUnknown(hdu))) = hdu_list.next() else {
+            panic!("expected an unknown extension");
+        };
+        let xtension = hdu.get_header().get_xtension();
+        assert_eq!(xtension.get_name(), "A3DTABLE");
+        assert_eq!(xtension.get_naxis(), [6, 500]);
+        assert_eq!(hdu.get_data_unit_byte_size(), 3012);
+        let mut bytes = vec![];
+        hdu_list.get_data(&hdu).read_to_end(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 3012);
+        assert_eq!(&bytes[..3], b"raw");
+
+        // The HDUs following the unknown extension are still parsed
+        let Some(Ok(HDU::XImage(hdu))) = hdu_list.next() else {
+            panic!("expected an image extension");
+        };
+        assert_eq!(hdu.get_header_byte_offset(), image_byte_offset);
+        assert!(hdu_list.next().is_none());
+
+        let summary = Fits::from_bytes(&buf).summary().unwrap();
+        assert_eq!(
+            summary.lines().nth(2),
+            Some("1    OLDTABLE         UnknownHDU        8  (6, 500)")
+        );
+    }
+
+    #[test]
+    fn datasum_of_data_units() {
+        let mut buf = mock_fits_file(true);
+        buf[2880..2890].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+
+        let primary = hdu_list.next().unwrap().unwrap();
+        assert_eq!(hdu_list.compute_datasum(&primary).unwrap(), 252840460);
+        let image = hdu_list.next().unwrap().unwrap();
+        assert_eq!(hdu_list.compute_datasum(&image).unwrap(), 0);
+        assert!(hdu_list.next().is_none());
+
+        // Truncated data unit
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..2885]));
+        let primary = hdu_list.next().unwrap().unwrap();
+        assert!(hdu_list.compute_datasum(&primary).is_err());
+    }
+
+    #[test]
+    fn owned_hdus() {
+        let mut buf = mock_fits_file(true);
+        buf[2880..2890].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        let hdus = Fits::from_reader(Cursor::new(&buf[..]))
+            .into_hdus()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(hdus.len(), 2);
+        assert!(matches!(hdus[0].hdu, HDU::Primary(_)));
+        assert_eq!(hdus[0].data, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert!(matches!(hdus[1].hdu, HDU::XImage(_)));
+        assert_eq!(hdus[1].data.len(), 40 * 20 * 4);
+
+        // Truncated data unit
+        let mut hdus = Fits::from_reader(Cursor::new(&buf[..2885])).into_hdus();
+        assert_eq!(
+            hdus.next().unwrap().unwrap_err(),
+            Error::Io(std::io::ErrorKind::UnexpectedEof)
+        );
+        assert!(hdus.next().is_none());
+    }
+
+    #[test]
+    fn standard_version() {
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   64                                                  ",
+                b"NAXIS   =                    0                                                  ",
+                b"LONGSTR = 'a long string that is &'                                             ",
+                b"CONTINUE  'continued on the next card'                                          ",
+                b"END                                                                             ",
+            ],
+            &[],
+        );
+
+        assert_eq!(FitsOptions::new().get_standard(), FitsVersion::V4_0);
+        let options = FitsOptions::new().strict(true);
+        assert!(Fits::from_bytes_with(&buf, &options).all(|hdu| hdu.is_ok()));
+
+        // The FITS 3.0 rules are only enforced in strict mode
+        let options = FitsOptions::new().standard(FitsVersion::V3_0);
+        assert!(Fits::from_bytes_with(&buf, &options).all(|hdu| hdu.is_ok()));
+
+        let options = options.strict(true);
+        let Some(Err(Error::DynamicError(message))) = Fits::from_bytes_with(&buf, &options).next()
+        else {
+            panic!("expected the CONTINUE card to be rejected");
+        };
+        assert_eq!(
+            message,
+            "A long string CONTINUE card is not allowed by FITS 3.0"
+        );
+    }
+
+    #[test]
+    fn declared_long_string_convention() {
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   64                                                  ",
+                b"NAXIS   =                    0                                                  ",
+                b"LONGSTRN= 'OGIP 1.0'           / The OGIP long string convention may be used.   ",
+                b"LONGSTR = 'a long string that is &'                                             ",
+                b"CONTINUE  'continued on the next card'                                          ",
+                b"END                                                                             ",
+            ],
+            &[],
+        );
+
+        let options = FitsOptions::new().standard(FitsVersion::V3_0).strict(true);
+        let Some(Ok(HDU::Primary(hdu))) = Fits::from_bytes_with(&buf, &options).next() else {
+            panic!("expected the declared CONTINUE card to be accepted");
+        };
+        let header = hdu.get_header();
+        assert_eq!(header.long_string_convention(), Some("OGIP 1.0"));
+        assert_eq!(
+            header.get_str("LONGSTR"),
+            Some("a long string that is continued on the next card")
+        );
+    }
+
+    // A primary HDU of 10 bytes whose cards are separated by CRLF bytes like an ASCII mode transfer would do
+    fn mock_hdu_with_line_endings() -> Vec<u8> {
+        let cards: [&CardBuf; 5] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    1                                                  ",
+            b"NAXIS1  =                   10                                                  ",
+            b"END                                                                             ",
+        ];
+        let mut buf = mock_hdu(&cards, &[0; 10]);
+        buf[2880..2890].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        for i in (1..cards.len()).rev() {
+            buf.splice(i * 80..i * 80, *b"\r\n");
+        }
+        buf
+    }
+
+    #[test]
+    fn line_endings_between_cards() {
+        let buf = mock_hdu_with_line_endings();
+
+        let mut hdu_list = Fits::from_bytes(&buf);
+        let hdu = hdu_list.next().unwrap().unwrap();
+        assert_eq!(hdu.get_header_byte_offset(), 0);
+        assert_eq!(hdu.get_data_unit_byte_offset(), 2880 + 8);
+        let HDU::Primary(hdu) = hdu else {
+            panic!("expected a primary HDU");
+        };
+        assert_eq!(hdu.get_header().get_xtension().get_naxis(), &[10]);
+        let (values, _) = hdu_list.image_f64(&HDU::Primary(hdu)).unwrap();
+        assert_eq!(values, (1..=10).map(f64::from).collect::<Vec<_>>());
+
+        let options = FitsOptions::new().strict(true);
+        let mut hdu_list = Fits::from_bytes_with(&buf, &options);
+        assert!(matches!(
+            hdu_list.next(),
+            Some(Err(Error::CardParse {
+                card_index: 1,
+                offset: 80,
+                ..
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn line_endings_between_cards_async() {
+        use crate::async_fits::AsyncFits;
+        use crate::hdu::data::stream::DataStream;
+        use crate::hdu::AsyncHDU;
+        use futures::StreamExt;
+
+        let buf = mock_hdu_with_line_endings();
+        let mut hdu_list = AsyncFits::from_reader(futures::io::BufReader::new(&buf[..]));
+        let Some(Ok(AsyncHDU::Primary(hdu))) = hdu_list.next().await else {
+            panic!("expected a primary HDU");
+        };
+        assert_eq!(hdu.get_header().get_xtension().get_naxis(), &[10]);
+        let DataStream::U8(st) = hdu_list.get_data(&hdu) else {
+            panic!("expected a BITPIX 8 image");
+        };
+        let values = st.map(|v| v.unwrap()[0]).collect::<Vec<_>>().await;
+        assert_eq!(values, (1..=10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn unknown_extension_type_async() {
+        use crate::async_fits::AsyncFits;
+        use crate::hdu::AsyncHDU;
+        use futures::StreamExt;
+
+        let buf = mock_fits_file_with_unknown_extension();
+        let mut hdu_list = AsyncFits::from_reader(futures::io::BufReader::new(&buf[..]));
+        let _primary = hdu_list.next().await.unwrap().unwrap();
+        let Some(Ok(AsyncHDU::XUnknown(hdu))) = hdu_list.next().await else {
+            panic!("expected an unknown extension");
+        };
+        assert_eq!(hdu.get_header().get_xtension().get_name(), "A3DTABLE");
+        let bytes = hdu_list
+            .get_data(&hdu)
+            .map(|v| v.unwrap()[0])
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(bytes.len(), 3012);
+        assert_eq!(&bytes[..3], b"raw");
+
+        // The HDUs following the unknown extension are still parsed
+        let Some(Ok(AsyncHDU::XImage(hdu))) = hdu_list.next().await else {
+            panic!("expected an image extension");
+        };
+        assert_eq!(hdu.get_header().get_xtension().get_naxis(), &[4]);
+        assert!(hdu_list.next().await.is_none());
+    }
+}
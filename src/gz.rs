@@ -9,7 +9,7 @@ use crate::hdu::header::extension::image::Image;
 use std::io::Cursor;
 #[derive(Debug)]
 pub enum GzReader<R> {
-    /// If the the file refers to a gzip file then unzip it entirely and store it in-memory
+    /// If the the file refers to a gzip, bzip2 or xz file then decompress it entirely and store it in-memory
     /// This will later be read and seekable (thanks to Cursor)
     GzReader(Cursor<Box<[u8]>>),
     Reader(R),
@@ -86,6 +86,7 @@ where
     }
 }
 */
+use crate::compression::{self, Compression};
 use crate::error::Error;
 use std::io::SeekFrom;
 impl<R> GzReader<R>
@@ -93,28 +94,105 @@ where
     R: Read + Seek,
 {
     /// Open a fits file from a path. Can be gzip-compressed
-    pub fn new(reader: R) -> Result<Self, Error> {
-        let mut gz = GzDecoder::new(reader);
+    ///
+    /// The compression is detected from the first bytes of the file with [compression::detect].
+    /// Files compressed with bzip2 or xz are decompressed if the `bzip2` or `xz2` feature is enabled
+    /// respectively, otherwise they give an error.
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let mut magic_bytes = Vec::with_capacity(3);
+        (&mut reader).take(3).read_to_end(&mut magic_bytes)?;
+        reader.seek(SeekFrom::Start(0))?;
 
-        match gz.header() {
+        let mut buf = vec![];
+        match compression::detect(&magic_bytes) {
             // `path` points to a file that is gzip-compressed.
-            Some(_) => {
-                let mut buf = vec![];
-                gz.read_to_end(&mut buf)?;
-
-                Ok(GzReader::GzReader(Cursor::new(buf.into_boxed_slice())))
+            Some(Compression::Gzip) => GzDecoder::new(reader).read_to_end(&mut buf)?,
+            #[cfg(feature = "bzip2")]
+            Some(Compression::Bzip2) => {
+                bzip2::read::BzDecoder::new(reader).read_to_end(&mut buf)?
             }
-            // `path` points to a plain text file.
-            None => {
-                let mut r = gz.into_inner();
-                // Since the `GzDecoder` already moved some bytes out of f
-                // by trying to decompress it, the file must be rewinded
-                // TODO There may be a better way instead of reading the same
-                // file twice.
-                r.seek(SeekFrom::Start(0))?;
-
-                Ok(GzReader::Reader(r))
+            #[cfg(feature = "xz2")]
+            Some(Compression::Xz) => xz2::read::XzDecoder::new(reader).read_to_end(&mut buf)?,
+            #[allow(unreachable_patterns)]
+            Some(compression) => {
+                return Err(Error::DynamicError(format!(
+                    "The file is compressed with {compression:?}, enable the corresponding feature of fitsrs to decompress it"
+                )))
             }
-        }
+            // `path` points to a plain text file.
+            None => return Ok(GzReader::Reader(reader)),
+        };
+
+        Ok(GzReader::GzReader(Cursor::new(buf.into_boxed_slice())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GzReader;
+    use crate::error::Error;
+    use crate::hdu::HDU;
+    use crate::test_utils::mock_hdu;
+    use crate::Fits;
+    use std::io::{Cursor, Write};
+
+    fn mock_fits_file() -> Vec<u8> {
+        mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                    8                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    4                                                  ",
+                b"END                                                                             ",
+            ],
+            &[1, 2, 3, 4],
+        )
+    }
+
+    fn read_pixels(compressed: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let reader = GzReader::new(Cursor::new(compressed))?;
+        let mut hdu_list = Fits::from_reader(reader);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+        let (values, _) = hdu_list.image_f64(&HDU::Primary(hdu))?;
+        Ok(values.into_iter().map(|v| v as u8).collect())
+    }
+
+    #[test]
+    fn gzip_file() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&mock_fits_file()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(read_pixels(compressed).unwrap(), [1, 2, 3, 4]);
+        assert_eq!(read_pixels(mock_fits_file()).unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn bzip2_file() {
+        let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+        encoder.write_all(&mock_fits_file()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(read_pixels(compressed).unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "xz2")]
+    #[test]
+    fn xz_file() {
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(&mock_fits_file()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(read_pixels(compressed).unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[cfg(not(feature = "bzip2"))]
+    #[test]
+    fn bzip2_file_without_feature() {
+        let compressed = b"BZh91AY&SY".to_vec();
+        assert!(GzReader::new(Cursor::new(compressed)).is_err());
     }
 }
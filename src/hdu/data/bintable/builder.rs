@@ -0,0 +1,426 @@
+//! Module building a binary table HDU from Rust values, e.g. to generate a catalog
+
+use std::convert::TryFrom;
+
+use crate::card::{Card, Value};
+use crate::error::Error;
+use crate::hdu::header::extension::bintable::BinTable;
+use crate::hdu::header::extension::XtensionType;
+use crate::hdu::header::Header;
+
+/// A value of a row given to a [BinTableBuilder]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// A value of a `B`, `I`, `J`, `K`, `E` or `D` field
+    Integer(i64),
+    /// A value of an `E` or `D` field
+    Float(f64),
+    /// A value of a `rA` field, padded with spaces to `r` characters
+    String(String),
+}
+
+impl From<i64> for CellValue {
+    fn from(value: i64) -> Self {
+        CellValue::Integer(value)
+    }
+}
+
+impl From<i32> for CellValue {
+    fn from(value: i32) -> Self {
+        CellValue::Integer(value.into())
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        CellValue::Float(value)
+    }
+}
+
+impl From<f32> for CellValue {
+    fn from(value: f32) -> Self {
+        CellValue::Float(value.into())
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        CellValue::String(value.to_owned())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        CellValue::String(value)
+    }
+}
+
+/// The storage of a field supported by the builder, given by its `TFORMn`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    /// `rA` with its number of characters
+    String(usize),
+    /// `B`, `I`, `J` or `K` with the byte size of the integer
+    Integer(usize),
+    /// `E`
+    Float,
+    /// `D`
+    Double,
+}
+
+impl FieldKind {
+    /// Parse a `TFORMn` value, only the string fields may have a repeat count
+    fn parse(tform: &str) -> Option<Self> {
+        let tform = tform.trim();
+        let num_digits = tform.bytes().take_while(u8::is_ascii_digit).count();
+        let (repeat_count, code) = tform.split_at(num_digits);
+        let repeat_count = if repeat_count.is_empty() {
+            1
+        } else {
+            repeat_count.parse().ok()?
+        };
+
+        let kind = match code {
+            "A" => return (repeat_count > 0).then_some(FieldKind::String(repeat_count)),
+            "B" => FieldKind::Integer(1),
+            "I" => FieldKind::Integer(2),
+            "J" => FieldKind::Integer(4),
+            "K" => FieldKind::Integer(8),
+            "E" => FieldKind::Float,
+            "D" => FieldKind::Double,
+            _ => return None,
+        };
+
+        (repeat_count == 1).then_some(kind)
+    }
+
+    fn byte_size(&self) -> usize {
+        match self {
+            FieldKind::String(width) => *width,
+            FieldKind::Integer(byte_size) => *byte_size,
+            FieldKind::Float => 4,
+            FieldKind::Double => 8,
+        }
+    }
+}
+
+/// A field of the table
+#[derive(Debug, Clone)]
+struct Column {
+    name: String,
+    tform: String,
+    unit: Option<String>,
+    kind: FieldKind,
+}
+
+/// A builder of a binary table HDU
+///
+/// The fields are defined first, then the rows are pushed one after the other, their values being
+/// encoded in big endian as they are pushed. String fields (`rA`) and scalar numeric fields (`B`, `I`,
+/// `J`, `K`, `E` and `D`) are supported.
+///
+/// ```
+///     # use fitsrs::BinTableBuilder;
+///     let mut builder = BinTableBuilder::new()
+///         .column("NAME", "8A", None)
+///         .unwrap()
+///         .column("MAG", "E", Some("mag"))
+///         .unwrap();
+///     builder.push_row(&["Vega".into(), 0.03.into()]).unwrap();
+///
+///     let bytes = builder.to_bytes().unwrap();
+///     assert_eq!(bytes.len(), 2 * 2880);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BinTableBuilder {
+    columns: Vec<Column>,
+    /// The big endian bytes of the rows pushed so far
+    data: Vec<u8>,
+    num_rows: usize,
+}
+
+impl BinTableBuilder {
+    /// Create a builder of a table without any field
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define the next field of the table
+    ///
+    /// The fields must be defined before pushing any row. An error is returned if the format is not
+    /// supported or if the name or the unit does not fit in a card.
+    ///
+    /// # Params
+    /// * `name` - the name of the field given by the `TTYPEn` card
+    /// * `tform` - the format of the field given by the `TFORMn` card, e.g. `16A`, `J` or `D`
+    /// * `unit` - the unit of the field given by the `TUNITn` card
+    pub fn column(mut self, name: &str, tform: &str, unit: Option<&str>) -> Result<Self, Error> {
+        if self.num_rows > 0 {
+            return Err(Error::StaticError(
+                "The fields must be defined before pushing rows",
+            ));
+        }
+        let kind = FieldKind::parse(tform).ok_or_else(|| {
+            Error::DynamicError(format!(
+                "TFORM '{tform}' is not supported, expected rA, B, I, J, K, E or D"
+            ))
+        })?;
+
+        let column = Column {
+            name: name.to_owned(),
+            tform: tform.trim().to_owned(),
+            unit: unit.map(str::to_owned),
+            kind,
+        };
+        // Check that the cards of the field can be written
+        for card in column_cards(self.columns.len() + 1, &column) {
+            card.to_bytes()?;
+        }
+        self.columns.push(column);
+
+        Ok(self)
+    }
+
+    /// Get the number of bytes of a row, i.e. the value of `NAXIS1`
+    pub fn get_row_byte_size(&self) -> usize {
+        self.columns.iter().map(|col| col.kind.byte_size()).sum()
+    }
+
+    /// Get the number of rows pushed so far, i.e. the value of `NAXIS2`
+    pub fn get_num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Append a row to the table
+    ///
+    /// The row must give one value per field, in the order of their definition. Integers are
+    /// accepted by all the numeric fields as long as they fit in the integer type, floats only by
+    /// the `E` and `D` fields and strings, of at most `r` ASCII characters, only by the `rA` fields.
+    /// The row is not appended if one of its values is not valid.
+    ///
+    /// # Params
+    /// * `row` - the values of the row
+    pub fn push_row(&mut self, row: &[CellValue]) -> Result<(), Error> {
+        if row.len() != self.columns.len() {
+            return Err(Error::DynamicError(format!(
+                "A row of {} values is given for a table of {} fields",
+                row.len(),
+                self.columns.len()
+            )));
+        }
+
+        let mut bytes = Vec::with_capacity(self.get_row_byte_size());
+        for (column, value) in self.columns.iter().zip(row) {
+            let invalid = || {
+                Error::DynamicError(format!(
+                    "{value:?} cannot be stored in field {} of format {}",
+                    column.name, column.tform
+                ))
+            };
+
+            match (column.kind, value) {
+                (FieldKind::String(width), CellValue::String(s)) => {
+                    if !s.is_ascii() || s.len() > width {
+                        return Err(invalid());
+                    }
+                    bytes.extend(s.bytes());
+                    bytes.resize(bytes.len() + width - s.len(), b' ');
+                }
+                (FieldKind::Integer(1), CellValue::Integer(v)) => {
+                    bytes.push(u8::try_from(*v).map_err(|_| invalid())?)
+                }
+                (FieldKind::Integer(2), CellValue::Integer(v)) => {
+                    bytes.extend(i16::try_from(*v).map_err(|_| invalid())?.to_be_bytes())
+                }
+                (FieldKind::Integer(4), CellValue::Integer(v)) => {
+                    bytes.extend(i32::try_from(*v).map_err(|_| invalid())?.to_be_bytes())
+                }
+                (FieldKind::Integer(_), CellValue::Integer(v)) => bytes.extend(v.to_be_bytes()),
+                (FieldKind::Float, CellValue::Integer(v)) => {
+                    bytes.extend((*v as f32).to_be_bytes())
+                }
+                (FieldKind::Float, CellValue::Float(v)) => bytes.extend((*v as f32).to_be_bytes()),
+                (FieldKind::Double, CellValue::Integer(v)) => {
+                    bytes.extend((*v as f64).to_be_bytes())
+                }
+                (FieldKind::Double, CellValue::Float(v)) => bytes.extend(v.to_be_bytes()),
+                _ => return Err(invalid()),
+            }
+        }
+
+        self.data.extend(bytes);
+        self.num_rows += 1;
+
+        Ok(())
+    }
+
+    /// Build the header of the table
+    ///
+    /// It contains the mandatory cards, i.e. `NAXIS1`, `NAXIS2`, `TFIELDS` and `TFORMn`, followed by
+    /// the `TTYPEn` and `TUNITn` cards of each field.
+    pub fn header(&self) -> Result<Header<BinTable>, Error> {
+        let integer = |value: usize| Value::Integer {
+            value: value as i64,
+            comment: None,
+        };
+        let value_card = |name: &str, value| Card::Value {
+            name: name.to_owned(),
+            value,
+        };
+
+        let mut cards = vec![
+            Card::Xtension {
+                x: XtensionType::BinTable,
+                comment: None,
+            },
+            value_card("BITPIX", integer(8)),
+            value_card("NAXIS", integer(2)),
+            value_card("NAXIS1", integer(self.get_row_byte_size())),
+            value_card("NAXIS2", integer(self.num_rows)),
+            value_card("PCOUNT", integer(0)),
+            value_card("GCOUNT", integer(1)),
+            value_card("TFIELDS", integer(self.columns.len())),
+        ];
+        for (i, column) in self.columns.iter().enumerate() {
+            cards.extend(column_cards(i + 1, column));
+        }
+        cards.push(Card::End);
+
+        Header::parse(cards)
+    }
+
+    /// Serialize the HDU, i.e. the header followed by the data unit, both padded to a multiple of
+    /// 2880 bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = self.header()?.to_bytes()?;
+        bytes.extend(&self.data);
+        bytes.resize(bytes.len().next_multiple_of(2880), 0);
+
+        Ok(bytes)
+    }
+}
+
+/// The `TTYPEn`, `TFORMn` and `TUNITn` cards of a field
+fn column_cards(n: usize, column: &Column) -> Vec<Card> {
+    let string = |name: String, value: &str| Card::Value {
+        name,
+        value: Value::String {
+            value: value.to_owned(),
+            comment: None,
+        },
+    };
+
+    let mut cards = vec![
+        string(format!("TTYPE{n}"), &column.name),
+        string(format!("TFORM{n}"), &column.tform),
+    ];
+    if let Some(unit) = &column.unit {
+        cards.push(string(format!("TUNIT{n}"), unit));
+    }
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinTableBuilder, CellValue};
+    use crate::fits::Fits;
+    use crate::hdu::HDU;
+    use crate::NullPolicy;
+
+    #[test]
+    fn write_and_read_table() {
+        let mut builder = BinTableBuilder::new()
+            .column("NAME", "8A", None)
+            .unwrap()
+            .column("ID", "J", None)
+            .unwrap()
+            .column("RA", "D", Some("deg"))
+            .unwrap()
+            .column("MAG", "E", Some("mag"))
+            .unwrap();
+        let rows = [
+            ("Vega", 1, 279.2347, 0.03),
+            ("Deneb", 2, 310.3580, 1.25),
+            ("Altair", 3, 297.6958, 0.77),
+        ];
+        for (name, id, ra, mag) in rows.iter() {
+            builder
+                .push_row(&[(*name).into(), (*id).into(), (*ra).into(), (*mag).into()])
+                .unwrap();
+        }
+        assert_eq!(builder.get_row_byte_size(), 8 + 4 + 8 + 4);
+        assert_eq!(builder.get_num_rows(), 3);
+
+        // Invalid rows are rejected
+        assert!(builder.push_row(&["Sirius".into(), 4.into()]).is_err());
+        assert!(builder
+            .push_row(&["Betelgeuse".into(), 5.into(), 88.79.into(), 0.5.into()])
+            .is_err());
+        assert!(builder
+            .push_row(&["Rigel".into(), 6.5.into(), 78.63.into(), 0.13.into()])
+            .is_err());
+        assert!(builder
+            .push_row(&[
+                "Rigel".into(),
+                (1_i64 << 40).into(),
+                78.63.into(),
+                0.13.into()
+            ])
+            .is_err());
+        assert_eq!(builder.get_num_rows(), 3);
+        assert!(builder.clone().column("FLAG", "L", None).is_err());
+        assert!(BinTableBuilder::new().column("FLUX", "3E", None).is_err());
+
+        let header = builder.header().unwrap();
+        assert_eq!(header.get_str("TFORM1"), Some("8A"));
+        assert_eq!(header.get_str("TUNIT3"), Some("deg"));
+        assert!(header.get("TUNIT1").is_none());
+
+        // Write a FITS file and read it back
+        let mut bytes = vec![b' '; 2880];
+        for (i, card) in [
+            b"SIMPLE  =                    T",
+            b"BITPIX  =                    8",
+            b"NAXIS   =                    0",
+            b"EXTEND  =                    T",
+        ]
+        .iter()
+        .enumerate()
+        {
+            bytes[i * 80..i * 80 + 30].copy_from_slice(*card);
+        }
+        bytes[320..323].copy_from_slice(b"END");
+        bytes.extend(builder.to_bytes().unwrap());
+        assert_eq!(bytes.len(), 3 * 2880);
+
+        let read = |col: usize| {
+            let mut hdu_list = Fits::from_bytes(&bytes);
+            let HDU::XBinaryTable(hdu) = hdu_list.nth(1).unwrap().unwrap() else {
+                panic!("expected a binary table");
+            };
+            if col == 0 {
+                hdu_list
+                    .column_strings(&hdu, 0)
+                    .map(|names| names.into_iter().map(CellValue::String).collect::<Vec<_>>())
+            } else {
+                hdu_list
+                    .column_as_vec::<f64>(&hdu, col, NullPolicy::Error)
+                    .map(|values| values.into_iter().map(CellValue::Float).collect())
+            }
+        };
+
+        assert_eq!(
+            read(0).unwrap(),
+            ["Vega", "Deneb", "Altair"].map(CellValue::from)
+        );
+        assert_eq!(read(1).unwrap(), [1.0, 2.0, 3.0].map(CellValue::Float));
+        assert_eq!(
+            read(2).unwrap(),
+            [279.2347, 310.3580, 297.6958].map(CellValue::Float)
+        );
+        assert_eq!(
+            read(3).unwrap(),
+            [0.03_f32, 1.25, 0.77].map(|v| CellValue::Float(v.into()))
+        );
+    }
+}
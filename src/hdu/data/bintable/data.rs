@@ -195,18 +195,8 @@ impl<R> TableData<R> {
         let ctx = header.get_xtension();
         let state = DataReaderState::MainTable;
 
-        // Compute an byte offset for each columns to know at which byte index does the column
-        // starts inside a row
-        let mut cumul_num_bytes = 0;
-        let col_byte_offsets = ctx
-            .tforms
-            .iter()
-            .map(|tform| {
-                let col_byte_offset = cumul_num_bytes;
-                cumul_num_bytes += tform.num_bytes_field();
-                col_byte_offset
-            })
-            .collect();
+        // The byte index where each column starts inside a row
+        let col_byte_offsets = ctx.column_offsets().to_vec();
 
         let cols_idx = (0..(ctx.tforms.len())).collect();
 
@@ -277,12 +267,36 @@ impl<R> TableData<R> {
         ).collect();
 
         self.cols_idx.sort_unstable();
+        // A field given twice is only read once
+        self.cols_idx.dedup();
 
         // We must go to the first column at this point
         self.seek_to_first_col = true;
 
         self
     }
+
+    /// Restrict the reading to some fields given by their `TTYPEn` names
+    ///
+    /// This is [TableData::select_fields] with the names resolved by [BinTable::column]: the other
+    /// fields are skipped with a seek and the selected ones keep their index in the whole table.
+    /// Contrary to [TableData::select_fields], the names are compared case-insensitively, need not
+    /// be `'static`, and a name that is not found gives an error instead of being discarded.
+    /// This must be called before reading any value.
+    ///
+    /// # Params
+    /// * `names` - the names of the fields to read
+    pub fn select(mut self, names: &[&str]) -> Result<Self, Error> {
+        let cols = self
+            .ctx
+            .columns(names)?
+            .into_iter()
+            .map(ColumnId::Index)
+            .collect::<Vec<_>>();
+        self.select_fields(&cols);
+
+        Ok(self)
+    }
 }
 
 impl<R> TableData<R>
@@ -361,6 +375,37 @@ where
         }
     }
 
+    /// Restrict the table to `count` rows starting from the row `start`
+    ///
+    /// No bytes are copied: the reader is moved to the beginning of the row `start` and the
+    /// context is replaced by [BinTable::row_slice], the variable length arrays being still read
+    /// from the heap of the whole table. The reading restarts from the first row of the sub-table.
+    ///
+    /// # Params
+    /// * `start` - the index of the first row of the sub-table
+    /// * `count` - the number of rows of the sub-table
+    pub fn row_slice(mut self, start: usize, count: usize) -> Result<Self, Error> {
+        let ctx = self.ctx.row_slice(start, count)?;
+
+        self.jump_to_main_table()?;
+
+        let new_byte_offset = (start as i64) * (self.ctx.naxis1 as i64);
+        self.reader
+            .seek_relative(new_byte_offset - self.byte_offset as i64)?;
+
+        self.start_pos += new_byte_offset as u64;
+        self.main_data_table_byte_size = (ctx.naxis1 * ctx.naxis2) as usize;
+        self.ctx = ctx;
+        self.byte_offset = 0;
+        self.row_idx = 0;
+        self.col_idx = 0;
+        self.item_idx = 0;
+        // We must go to the first selected column of the first row
+        self.seek_to_first_col = true;
+
+        Ok(self)
+    }
+
     /// Jump to the heap at a specific offset in the HEAP associated to the binary table
     ///
     /// This method takes the ownership to change the state of it
@@ -0,0 +1,90 @@
+//! A thin layer over binary tables storing photon events, as in X-ray and gamma-ray observations
+
+use crate::error::Error;
+use crate::hdu::header::extension::bintable::BinTable;
+
+/// The name of the field storing the arrival times
+pub const TIME_COLUMN: &str = "TIME";
+/// The names of the fields storing the positions on the sky or the detector
+pub const POSITION_COLUMNS: (&str, &str) = ("X", "Y");
+/// The names of the field storing the energies, looked for in that order
+///
+/// `ENERGY` is usually given in eV or keV while `PI` and `PHA` are channels of the detector.
+pub const ENERGY_COLUMNS: &[&str] = &["ENERGY", "PI", "PHA"];
+
+/// The photon events of a binary table, e.g. an `EVENTS` extension
+///
+/// The fields are found by probing their standard names case-insensitively, see [TIME_COLUMN],
+/// [POSITION_COLUMNS] and [ENERGY_COLUMNS]. Their values are the physical ones, i.e. scaled by the
+/// `TSCALn` and `TZEROn` cards, an undefined value being NaN.
+/// It is read with [Fits::event_list](crate::Fits::event_list).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventList {
+    pub(crate) times: Vec<f64>,
+    pub(crate) positions: Vec<(f64, f64)>,
+    pub(crate) energies: Vec<f64>,
+}
+
+impl EventList {
+    /// Get the arrival time of each event
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Get the `(x, y)` position of each event
+    pub fn positions(&self) -> &[(f64, f64)] {
+        &self.positions
+    }
+
+    /// Get the energy, or the energy channel, of each event
+    pub fn energies(&self) -> &[f64] {
+        &self.energies
+    }
+
+    /// Get the number of events
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Check if the list has no event
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+}
+
+/// The indices of the fields of an event list
+#[derive(Debug, PartialEq)]
+pub(crate) struct EventColumns {
+    pub(crate) time: usize,
+    pub(crate) x: usize,
+    pub(crate) y: usize,
+    pub(crate) energy: usize,
+}
+
+impl EventColumns {
+    /// Find the fields of an event list, giving an error naming the first one absent
+    pub(crate) fn find(ctx: &BinTable) -> Result<Self, Error> {
+        let missing = |names: String| {
+            Error::DynamicError(format!(
+                "The table is not an event list, no {names} field has been found"
+            ))
+        };
+
+        let time = ctx
+            .column(TIME_COLUMN)
+            .ok_or_else(|| missing(TIME_COLUMN.to_owned()))?;
+        let (x_name, y_name) = POSITION_COLUMNS;
+        let x = ctx
+            .column(x_name)
+            .ok_or_else(|| missing(x_name.to_owned()))?;
+        let y = ctx
+            .column(y_name)
+            .ok_or_else(|| missing(y_name.to_owned()))?;
+        let energy = ENERGY_COLUMNS
+            .iter()
+            .find_map(|name| ctx.column(name))
+            .ok_or_else(|| missing(ENERGY_COLUMNS.join(" or ")))?;
+
+        Ok(Self { time, x, y, energy })
+    }
+}
@@ -1,14 +1,29 @@
 #![allow(clippy::upper_case_acronyms)]
 
+pub mod builder;
 pub mod data;
+pub mod events;
 pub mod row;
 pub mod tile_compressed;
 
+pub use builder::{BinTableBuilder, CellValue};
 pub use data::TableData;
+pub use events::EventList;
 pub use row::TableRowData;
 
 use std::fmt::Debug;
 
+/// Decode the bytes of a fixed width string stored in a character (`A`) field
+///
+/// The string ends at the first NUL byte, if any, and its trailing spaces are removed.
+/// Leading spaces are significant and kept.
+pub fn decode_fixed_width_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end])
+        .trim_end_matches(' ')
+        .to_owned()
+}
+
 /// A data structure refering to a column in a table
 #[derive(Debug)]
 pub enum ColumnId {
@@ -140,3 +155,48 @@ pub enum DataValue {
         offset_byte: u64,
     },
 }
+
+/// The handling of the undefined values of a field, see [Fits::column_as_vec](crate::Fits::column_as_vec)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullPolicy {
+    /// An undefined value is replaced by the default value of the requested type, e.g. `0` or `0.0`
+    #[default]
+    Default,
+    /// An undefined value gives an error
+    Error,
+}
+
+/// A type into which the physical values of a numeric field can be converted,
+/// see [Fits::column_as_vec](crate::Fits::column_as_vec)
+pub trait ColumnValue: Default + Sized {
+    /// Convert a physical value, returning `None` if it cannot be represented by the type
+    fn from_physical(value: f64) -> Option<Self>;
+}
+
+impl ColumnValue for f64 {
+    fn from_physical(value: f64) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl ColumnValue for f32 {
+    fn from_physical(value: f64) -> Option<Self> {
+        Some(value as f32)
+    }
+}
+
+macro_rules! impl_column_value_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl ColumnValue for $t {
+                /// The value must be an integer within the range of the type, no rounding is done
+                fn from_physical(value: f64) -> Option<Self> {
+                    let in_range = value >= <$t>::MIN as f64 && value <= <$t>::MAX as f64;
+                    (value.fract() == 0.0 && in_range).then_some(value as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_column_value_for_integer!(u8, i8, u16, i16, u32, i32, u64, i64);
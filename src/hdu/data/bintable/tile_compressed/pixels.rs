@@ -185,10 +185,12 @@ where
             let ctx = self.row_it.get_ctx();
             let row_idx = self.row_it.get_row_idx();
 
-            // Update the tile compressed currently decompressed
-            let num_pixels = tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx)
-                .iter()
-                .product::<usize>() as u64;
+            // Update the tile compressed currently decompressed.
+            // The row index has already been incremented by reading the row of the tile
+            let num_pixels =
+                tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx - 1)
+                    .iter()
+                    .product::<usize>() as u64;
             self.desc.n_pixels = num_pixels;
             self.desc.remaining_pixels = num_pixels;
 
@@ -213,7 +215,8 @@ where
                         // For GZIP2, the byte shuffling is done in the next method
                         ZCmpType::Gzip1 | ZCmpType::Gzip2 => {
                             let mut gz = GzDecoder::new(reader);
-                            gz.read_exact(&mut buf[..])?;
+                            // Tiles on the border of the image can be smaller than the buffer
+                            gz.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // FIXME support bytepix
                         ZCmpType::Rice { blocksize, .. } => {
@@ -222,7 +225,7 @@ where
                                 blocksize as i32,
                                 num_pixels as i32,
                             );
-                            rice.read_exact(&mut buf[..])?;
+                            rice.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // Other compression not supported, when parsing the bintable extension keywords
                         // we ensured that z_image is `None` for other compressions than GZIP or RICE
@@ -287,10 +290,12 @@ where
             let ctx = self.row_it.get_ctx();
             let row_idx = self.row_it.get_row_idx();
 
-            // Update the tile compressed currently decompressed
-            let num_pixels = tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx)
-                .iter()
-                .product::<usize>() as u64;
+            // Update the tile compressed currently decompressed.
+            // The row index has already been incremented by reading the row of the tile
+            let num_pixels =
+                tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx - 1)
+                    .iter()
+                    .product::<usize>() as u64;
             self.desc.n_pixels = num_pixels;
             self.desc.remaining_pixels = num_pixels;
             // We jump to the heap at the position of the tile
@@ -314,7 +319,8 @@ where
                         // For GZIP2, the byte shuffling is done in the next method
                         ZCmpType::Gzip1 | ZCmpType::Gzip2 => {
                             let mut gz = GzDecoder::new(reader);
-                            gz.read_exact(&mut buf[..])?;
+                            // Tiles on the border of the image can be smaller than the buffer
+                            gz.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // FIXME support bytepix
                         ZCmpType::Rice { blocksize, .. } => {
@@ -323,7 +329,7 @@ where
                                 blocksize as i32,
                                 num_pixels as i32,
                             );
-                            rice.read_exact(&mut buf[..])?;
+                            rice.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // Other compression not supported, when parsing the bintable extension keywords
                         // we ensured that z_image is `None` for other compressions than GZIP or RICE
@@ -350,8 +356,7 @@ where
             ZCmpType::Gzip2 => {
                 // We need to get the byte index in the buffer storing u32, i.e. 4 bytes per elements
                 // read from BigEndian, i.e. the most significant byte is at first and the least one is at last position
-                let num_bytes = self.buf.len();
-                let step_msb = num_bytes / 4;
+                let step_msb = self.desc.n_pixels as usize;
                 (self.buf[3 * step_msb + idx] as i16) | ((self.buf[2 * step_msb + idx] as i16) << 8)
             }
             ZCmpType::Rice { .. } => {
@@ -395,10 +400,12 @@ where
             let ctx = self.row_it.get_ctx();
             let row_idx = self.row_it.get_row_idx();
 
-            // Update the tile compressed currently decompressed
-            let num_pixels = tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx)
-                .iter()
-                .product::<usize>() as u64;
+            // Update the tile compressed currently decompressed.
+            // The row index has already been incremented by reading the row of the tile
+            let num_pixels =
+                tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx - 1)
+                    .iter()
+                    .product::<usize>() as u64;
             self.desc.n_pixels = num_pixels;
             self.desc.remaining_pixels = num_pixels;
             // We jump to the heap at the position of the tile
@@ -422,7 +429,8 @@ where
                         // For GZIP2, the byte shuffling is done in the next method
                         ZCmpType::Gzip1 | ZCmpType::Gzip2 => {
                             let mut gz = GzDecoder::new(reader);
-                            gz.read_exact(&mut buf[..])?;
+                            // Tiles on the border of the image can be smaller than the buffer
+                            gz.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // FIXME support bytepix
                         ZCmpType::Rice { blocksize, .. } => {
@@ -431,7 +439,7 @@ where
                                 blocksize as i32,
                                 num_pixels as i32,
                             );
-                            rice.read_exact(&mut buf[..])?;
+                            rice.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // Other compression not supported, when parsing the bintable extension keywords
                         // we ensured that z_image is `None` for other compressions than GZIP or RICE
@@ -462,8 +470,7 @@ where
             ZCmpType::Gzip2 => {
                 // We need to get the byte index in the buffer storing u32, i.e. 4 bytes per elements
                 // read from BigEndian, i.e. the most significant byte is at first and the least one is at last position
-                let num_bytes = self.buf.len();
-                let step_msb = num_bytes / 4;
+                let step_msb = self.desc.n_pixels as usize;
                 ((self.buf[idx] as i32) << 24)
                     | ((self.buf[idx + step_msb] as i32) << 16)
                     | ((self.buf[idx + 2 * step_msb] as i32) << 8)
@@ -515,10 +522,12 @@ where
             let ctx = self.row_it.get_ctx();
             let row_idx = self.row_it.get_row_idx();
 
-            // Update the tile compressed currently decompressed
-            let num_pixels = tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx)
-                .iter()
-                .product::<usize>() as u64;
+            // Update the tile compressed currently decompressed.
+            // The row index has already been incremented by reading the row of the tile
+            let num_pixels =
+                tile_size_from_row_idx(&self.z_tile[..], &self.z_naxis[..], row_idx - 1)
+                    .iter()
+                    .product::<usize>() as u64;
             self.desc.n_pixels = num_pixels;
             self.desc.remaining_pixels = num_pixels;
 
@@ -595,7 +604,8 @@ where
                         // For GZIP2, the byte shuffling is done in the next method
                         ZCmpType::Gzip1 | ZCmpType::Gzip2 => {
                             let mut gz = GzDecoder::new(reader);
-                            gz.read_exact(&mut buf[..])?;
+                            // Tiles on the border of the image can be smaller than the buffer
+                            gz.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // FIXME support bytepix
                         ZCmpType::Rice { blocksize, .. } => {
@@ -604,7 +614,7 @@ where
                                 blocksize as i32,
                                 num_pixels as i32,
                             );
-                            rice.read_exact(&mut buf[..])?;
+                            rice.read_exact(&mut buf[..4 * num_pixels as usize])?;
                         }
                         // Other compression not supported, when parsing the bintable extension keywords
                         // we ensured that z_image is `None` for other compressions than GZIP or RICE
@@ -637,8 +647,7 @@ where
             ZCmpType::Gzip2 => {
                 // We need to get the byte index in the buffer storing u32, i.e. 4 bytes per elements
                 // read from BigEndian, i.e. the most significant byte is at first and the least one is at last position
-                let num_bytes = self.buf.len();
-                let step_msb = num_bytes / 4;
+                let step_msb = self.desc.n_pixels as usize;
                 let value = ((self.buf[idx] as i32) << 24)
                     | ((self.buf[idx + step_msb] as i32) << 16)
                     | ((self.buf[idx + 2 * step_msb] as i32) << 8)
@@ -687,3 +696,87 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Pixels;
+
+    use crate::hdu::data::bintable::data::BinaryTableData;
+
+    use crate::test_utils::{bintable_cards, first_bintable, mock_extension};
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// Build a FITS file storing a 4x3 GZIP_1 tile compressed image of 32-bit integers
+    ///
+    /// # Params
+    /// * `z_tile2` - the number of rows of a tile
+    /// * `pixels` - the pixels of the image
+    fn mock_gzip1_image(z_tile2: usize, pixels: &[i32]) -> Vec<u8> {
+        // Compress each tile and store it in the heap
+        let mut heap = vec![];
+        let mut descriptors = vec![];
+        for tile in pixels.chunks(4 * z_tile2) {
+            let mut gz = GzEncoder::new(vec![], Compression::default());
+            for p in tile {
+                gz.write_all(&p.to_be_bytes()).unwrap();
+            }
+            let compressed = gz.finish().unwrap();
+
+            descriptors.extend((compressed.len() as i32).to_be_bytes());
+            descriptors.extend((heap.len() as i32).to_be_bytes());
+            heap.extend(compressed);
+        }
+        let num_tiles = descriptors.len() / 8;
+
+        let z_tile2 = format!("ZTILE2  = {z_tile2:>20}");
+        let cards = bintable_cards(
+            8,
+            num_tiles,
+            heap.len(),
+            &[("COMPRESSED_DATA", "1PB")],
+            &[
+                "ZIMAGE  =                    T",
+                "ZCMPTYPE= 'GZIP_1  '",
+                "ZBITPIX =                   32",
+                "ZNAXIS  =                    2",
+                "ZNAXIS1 =                    4",
+                "ZNAXIS2 =                    3",
+                "ZTILE1  =                    4",
+                &z_tile2,
+            ],
+        );
+
+        let mut data = descriptors;
+        data.extend(heap);
+        mock_extension(&cards, &data)
+    }
+
+    fn decompress(buf: &[u8]) -> Vec<i32> {
+        let (mut hdu_list, hdu) = first_bintable(buf);
+
+        let BinaryTableData::TileCompressed(Pixels::I32(pixels)) = hdu_list.get_data(&hdu) else {
+            panic!("expected a tile compressed image of 32-bit integers");
+        };
+        pixels.collect()
+    }
+
+    #[test]
+    fn gzip1_single_tile_image() {
+        let pixels = (0..12).map(|p| p * 1000 - 5000).collect::<Vec<_>>();
+        let buf = mock_gzip1_image(3, &pixels);
+
+        assert_eq!(decompress(&buf), pixels);
+    }
+
+    #[test]
+    fn gzip1_image_with_smaller_last_tile() {
+        let pixels = (0..12).map(|p| p * 1000 - 5000).collect::<Vec<_>>();
+        // Tiles of 4x2 pixels, the last one being 4x1
+        let buf = mock_gzip1_image(2, &pixels);
+
+        assert_eq!(decompress(&buf), pixels);
+    }
+}
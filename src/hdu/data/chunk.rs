@@ -0,0 +1,199 @@
+use std::io::Read;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::error::Error;
+use crate::hdu::header::extension::image::Image;
+use crate::hdu::header::Bitpix;
+
+/// A reader decoding the data unit of an image HDU chunk by chunk
+///
+/// Contrary to [ImageData](super::ImageData) that gives an iterator over the pixels,
+/// this reader fills a buffer provided by the user with a fixed number of values
+/// converted to `f64`. By default, a chunk corresponds to one row of the image (i.e. `NAXIS1` values)
+/// so that an arbitrarily large image can be processed with a bounded amount of memory.
+///
+/// The reader given must be positioned at the beginning of the data unit, see
+/// [Fits::get_data_chunks](crate::Fits::get_data_chunks) to create one from a [Fits](crate::Fits).
+#[derive(Debug)]
+pub struct DataUnitReader<R> {
+    /// The reader
+    reader: R,
+    /// Type of the values stored in the data unit
+    bitpix: Bitpix,
+    /// Number of values decoded per chunk
+    chunk_len: usize,
+    /// Number of values remaining to read in the data unit
+    num_remaining_values: u64,
+    /// Whether the values are decoded in the little endian byte order
+    little_endian: bool,
+    /// Intern buffer where the raw bytes of a chunk are read
+    buf: Vec<u8>,
+}
+
+impl<R> DataUnitReader<R>
+where
+    R: Read,
+{
+    /// Create a new chunked reader over the data unit
+    ///
+    /// # Params
+    /// * `reader` - a reader positioned at the beginning of the data unit
+    /// * `ctx` - the image mandatory keywords parsed from the header
+    pub fn new(reader: R, ctx: &Image) -> Self {
+        let chunk_len = ctx.get_naxis().first().copied().unwrap_or(0) as usize;
+        let bitpix = ctx.get_bitpix();
+        let num_remaining_values = ctx.get_num_pixels();
+
+        Self {
+            reader,
+            bitpix,
+            chunk_len,
+            num_remaining_values,
+            little_endian: false,
+            buf: vec![],
+        }
+    }
+
+    /// Set the number of values decoded for each chunk. By default, it is equal to `NAXIS1`
+    ///
+    /// A chunk length of 0 would never make progress through the data unit so it is clamped to 1.
+    pub fn with_chunk_len(mut self, chunk_len: usize) -> Self {
+        self.chunk_len = chunk_len.max(1);
+        self
+    }
+
+    /// Decode the values in the little endian byte order instead of the big endian one of the FITS standard,
+    /// see [FitsOptions::assume_little_endian](crate::FitsOptions::assume_little_endian)
+    pub fn little_endian(mut self, little_endian: bool) -> Self {
+        self.little_endian = little_endian;
+        self
+    }
+
+    /// Get the number of values decoded for each chunk
+    pub fn get_chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    /// Get the number of values that remain to be read from the data unit
+    pub fn get_num_remaining_values(&self) -> u64 {
+        self.num_remaining_values
+    }
+
+    /// Read and decode the next chunk of values
+    ///
+    /// `out` is cleared and filled with the decoded values of the chunk. The last chunk
+    /// of the data unit can be smaller than the chunk length.
+    /// Returns the number of values written, `0` meaning that the whole data unit has been read.
+    pub fn next_chunk(&mut self, out: &mut Vec<f64>) -> Result<usize, Error> {
+        out.clear();
+
+        let num_values = (self.chunk_len as u64).min(self.num_remaining_values) as usize;
+        if num_values == 0 {
+            return Ok(0);
+        }
+
+        let byte_size = self.bitpix.byte_size();
+        self.buf.resize(num_values * byte_size, 0);
+        self.reader.read_exact(&mut self.buf)?;
+
+        if self.little_endian {
+            decode::<LittleEndian>(self.bitpix, &self.buf, out);
+        } else {
+            decode::<BigEndian>(self.bitpix, &self.buf, out);
+        }
+
+        self.num_remaining_values -= num_values as u64;
+
+        Ok(num_values)
+    }
+}
+
+/// Decode the raw bytes of a chunk in the byte order `B`
+fn decode<B: ByteOrder>(bitpix: Bitpix, buf: &[u8], out: &mut Vec<f64>) {
+    let chunks = buf.chunks_exact(bitpix.byte_size());
+    match bitpix {
+        Bitpix::U8 => out.extend(chunks.map(|b| b[0] as f64)),
+        Bitpix::I16 => out.extend(chunks.map(|b| B::read_i16(b) as f64)),
+        Bitpix::I32 => out.extend(chunks.map(|b| B::read_i32(b) as f64)),
+        Bitpix::I64 => out.extend(chunks.map(|b| B::read_i64(b) as f64)),
+        Bitpix::F32 => out.extend(chunks.map(|b| B::read_f32(b) as f64)),
+        Bitpix::F64 => out.extend(chunks.map(B::read_f64)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataUnitReader;
+    use crate::card::CardBuf;
+    use crate::fits::Fits;
+    use crate::hdu::HDU;
+    use crate::test_utils::mock_hdu;
+
+    use std::io::{Cursor, Seek, SeekFrom};
+
+    fn mock_image_i16() -> Vec<u8> {
+        let cards: [&CardBuf; 6] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                   16                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                    3                                                  ",
+            b"NAXIS2  =                    2                                                  ",
+            b"END                                                                             ",
+        ];
+        let du = [1_i16, -2, 3, 400, -500, 600]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        mock_hdu(&cards, &du)
+    }
+
+    #[test]
+    fn read_image_row_by_row() {
+        let mut reader = Cursor::new(mock_image_i16());
+        let mut hdu_list = Fits::from_reader(&mut reader);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+
+        reader
+            .seek(SeekFrom::Start(hdu.get_data_unit_byte_offset()))
+            .unwrap();
+        let mut chunks = DataUnitReader::new(&mut reader, hdu.get_header().get_xtension());
+
+        let mut row = Vec::new();
+        assert_eq!(chunks.next_chunk(&mut row), Ok(3));
+        assert_eq!(row, [1.0, -2.0, 3.0]);
+        assert_eq!(chunks.next_chunk(&mut row), Ok(3));
+        assert_eq!(row, [400.0, -500.0, 600.0]);
+        assert_eq!(chunks.next_chunk(&mut row), Ok(0));
+        assert!(row.is_empty());
+    }
+
+    #[test]
+    fn read_image_custom_chunk_len() {
+        let mut reader = Cursor::new(mock_image_i16());
+        let mut hdu_list = Fits::from_reader(&mut reader);
+        let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+            panic!("expected a primary HDU");
+        };
+
+        reader
+            .seek(SeekFrom::Start(hdu.get_data_unit_byte_offset()))
+            .unwrap();
+        let mut chunks =
+            DataUnitReader::new(&mut reader, hdu.get_header().get_xtension()).with_chunk_len(4);
+
+        let mut buf = Vec::new();
+        assert_eq!(chunks.next_chunk(&mut buf), Ok(4));
+        assert_eq!(buf, [1.0, -2.0, 3.0, 400.0]);
+        assert_eq!(chunks.next_chunk(&mut buf), Ok(2));
+        assert_eq!(buf, [-500.0, 600.0]);
+        assert_eq!(chunks.get_num_remaining_values(), 0);
+
+        // A chunk length of 0 is clamped to 1
+        let chunks =
+            DataUnitReader::new(&mut reader, hdu.get_header().get_xtension()).with_chunk_len(0);
+        assert_eq!(chunks.get_chunk_len(), 1);
+    }
+}
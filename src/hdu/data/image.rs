@@ -1,17 +1,19 @@
 //pub use super::Access;
 //use super::DataAsyncBufRead;
 
-use crate::hdu::data::iter::It;
+use crate::error::Error;
+use crate::hdu::data::iter::{It, Value};
 use crate::hdu::header::{Bitpix, Header, Xtension};
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use serde::Serialize;
-use std::io::Read;
+use std::io::{Read, Seek};
 
 use super::super::AsyncDataBufRead;
 use super::DataStream;
 use crate::hdu::header::extension::image::Image;
 use crate::hdu::FitsRead;
+use crate::FitsOptions;
 use std::fmt::Debug;
 
 impl<'a, R> FitsRead<'a, Image> for R
@@ -23,15 +25,32 @@ where
     fn read_data_unit(&'a mut self, header: &Header<Image>, start_pos: u64) -> Self::Data {
         ImageData::new(header.get_xtension(), self, start_pos)
     }
+
+    fn with_options(data: Self::Data, options: &FitsOptions) -> Self::Data {
+        data.little_endian(options.is_assuming_little_endian())
+    }
 }
 
 #[derive(Serialize, Debug)]
 pub struct ImageData<R> {
     start_pos: u64,
     num_bytes_data_block: u64,
+    blank: Option<i64>,
+    is_vector: bool,
     pixels: Pixels<R>,
 }
 
+/// Range of the physical values of an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DataRange {
+    /// The minimum value
+    pub min: f64,
+    /// The maximum value
+    pub max: f64,
+    /// Whether the range is given by the `DATAMIN` and `DATAMAX` cards or computed from the pixels
+    pub from_header: bool,
+}
+
 /// An iterator on the data array
 /// This is an enum whose content depends on the
 /// bitpix value found in the header part of the HDU
@@ -55,27 +74,456 @@ where
 {
     pub(crate) fn new(ctx: &Image, reader: R, start_pos: u64) -> Self {
         let limit = ctx.get_num_bytes_data_block();
-
-        let pixels = match ctx.get_bitpix() {
-            Bitpix::U8 => Pixels::U8(It::new(reader, limit)),
-            Bitpix::I16 => Pixels::I16(It::new(reader, limit)),
-            Bitpix::I32 => Pixels::I32(It::new(reader, limit)),
-            Bitpix::I64 => Pixels::I64(It::new(reader, limit)),
-            Bitpix::F32 => Pixels::F32(It::new(reader, limit)),
-            Bitpix::F64 => Pixels::F64(It::new(reader, limit)),
-        };
+        let num_bytes_pixels = ctx.get_bitpix().byte_size() as u64 * ctx.get_num_pixels();
+        let pixels = Pixels::new(ctx.get_bitpix(), reader, num_bytes_pixels).starting_at(start_pos);
 
         Self {
             start_pos,
             num_bytes_data_block: limit,
+            blank: ctx.get_blank(),
+            is_vector: ctx.is_vector(),
             pixels,
         }
     }
 
+    /// Read the pixels in the little endian byte order, see [Pixels::little_endian]
+    pub fn little_endian(mut self, little_endian: bool) -> Self {
+        self.pixels = self.pixels.little_endian(little_endian);
+        self
+    }
+
     /// Get the pixels iterator of the image
     pub fn pixels(self) -> Pixels<R> {
         self.pixels
     }
+
+    /// Get the values of a one dimensional data unit, e.g. a spectrum
+    ///
+    /// Returns `None` if `NAXIS` is not 1
+    pub fn as_1d(self) -> Option<Pixels<R>> {
+        self.is_vector.then_some(self.pixels)
+    }
+
+    /// Get the number of pixels in the data unit, i.e. the product of the `NAXISn` values
+    pub fn get_num_pixels(&self) -> usize {
+        self.pixels.get_num_items()
+    }
+
+    /// Compute the display limits `(z1, z2)` of the image following the IRAF zscale algorithm
+    ///
+    /// At most 1000 pixels regularly spaced among the defined ones are sampled, the undefined ones being
+    /// NaN values or values equal to the `BLANK` card for integer images. A line is
+    /// iteratively fitted to the sorted samples, rejecting the outliers, and its slope divided by
+    /// the contrast (`0.25`) gives the limits around the median of the samples. The limits are
+    /// bounded by the minimum and maximum of the samples.
+    ///
+    /// Returns `None` if the image does not contain any defined pixel
+    pub fn zscale(self) -> Option<(f64, f64)> {
+        let blank = self.blank;
+        let is_defined = |v: i64| Some(v) != blank;
+        let samples = match self.pixels {
+            Pixels::U8(it) => zscale_samples(it, |v| is_defined(v as i64).then_some(v as f64)),
+            Pixels::I16(it) => zscale_samples(it, |v| is_defined(v as i64).then_some(v as f64)),
+            Pixels::I32(it) => zscale_samples(it, |v| is_defined(v as i64).then_some(v as f64)),
+            Pixels::I64(it) => zscale_samples(it, |v| is_defined(v).then_some(v as f64)),
+            Pixels::F32(it) => zscale_samples(it, |v| (!v.is_nan()).then_some(v as f64)),
+            Pixels::F64(it) => zscale_samples(it, |v| (!v.is_nan()).then_some(v)),
+        };
+
+        zscale(samples)
+    }
+
+    /// Compute the minimum and maximum of the pixel values
+    ///
+    /// Undefined pixels, i.e. NaN values or values equal to the `BLANK` card for integer images, are skipped.
+    /// The values are the stored ones, i.e. `BSCALE` and `BZERO` are not applied.
+    /// Returns `None` if the image does not contain any defined pixel
+    pub fn range(self) -> Option<(f64, f64)> {
+        let blank = self.blank;
+        let is_defined = |v: i64| Some(v) != blank;
+        match self.pixels {
+            Pixels::U8(it) => min_max(it, |v| is_defined(v as i64).then_some(v as f64)),
+            Pixels::I16(it) => min_max(it, |v| is_defined(v as i64).then_some(v as f64)),
+            Pixels::I32(it) => min_max(it, |v| is_defined(v as i64).then_some(v as f64)),
+            Pixels::I64(it) => min_max(it, |v| is_defined(v).then_some(v as f64)),
+            Pixels::F32(it) => min_max(it, |v| (!v.is_nan()).then_some(v as f64)),
+            Pixels::F64(it) => min_max(it, |v| (!v.is_nan()).then_some(v)),
+        }
+    }
+
+    /// Read all the pixels and convert them to physical values, i.e. `BZERO + BSCALE * pixel`
+    ///
+    /// Undefined pixels, i.e. NaN values or values equal to the `BLANK` card for integer images, are NaN.
+    /// The reading stops at the first read error so that less values than pixels can be returned.
+    ///
+    /// # Params
+    /// * `bscale` - the value of the `BSCALE` card
+    /// * `bzero` - the value of the `BZERO` card
+    pub fn physical_values(self, bscale: f64, bzero: f64) -> Vec<f64> {
+        let blank = self.blank;
+        let physical = |v: i64| {
+            if Some(v) == blank {
+                f64::NAN
+            } else {
+                bzero + bscale * (v as f64)
+            }
+        };
+        match self.pixels {
+            Pixels::U8(it) => it.map(|v| physical(v as i64)).collect(),
+            Pixels::I16(it) => it.map(|v| physical(v as i64)).collect(),
+            Pixels::I32(it) => it.map(|v| physical(v as i64)).collect(),
+            Pixels::I64(it) => it.map(physical).collect(),
+            Pixels::F32(it) => it.map(|v| bzero + bscale * (v as f64)).collect(),
+            Pixels::F64(it) => it.map(|v| bzero + bscale * v).collect(),
+        }
+    }
+}
+
+impl<R> Pixels<R>
+where
+    R: Read,
+{
+    /// Create the pixels iterator of the variant matching a bitpix
+    ///
+    /// # Params
+    /// * `bitpix` - the type of the pixels
+    /// * `reader` - the reader positioned at the first pixel
+    /// * `num_bytes` - the number of bytes of the pixels to read
+    pub fn new(bitpix: Bitpix, reader: R, num_bytes: u64) -> Self {
+        match bitpix {
+            Bitpix::U8 => Pixels::U8(It::new(reader, num_bytes)),
+            Bitpix::I16 => Pixels::I16(It::new(reader, num_bytes)),
+            Bitpix::I32 => Pixels::I32(It::new(reader, num_bytes)),
+            Bitpix::I64 => Pixels::I64(It::new(reader, num_bytes)),
+            Bitpix::F32 => Pixels::F32(It::new(reader, num_bytes)),
+            Bitpix::F64 => Pixels::F64(It::new(reader, num_bytes)),
+        }
+    }
+
+    /// Create a pixels iterator without any pixel of the variant matching a bitpix
+    pub fn empty(bitpix: Bitpix, reader: R) -> Self {
+        Self::new(bitpix, reader, 0)
+    }
+
+    /// Get the narrowest type holding all the remaining pixels losslessly, e.g. to re-encode an image
+    ///
+    /// The pixels are read to find their minimum and maximum. Floating point pixels are never narrowed
+    /// to an integer type, their type is returned unchanged.
+    pub fn min_fit_type(self) -> Bitpix {
+        let range = match self {
+            Pixels::U8(_) => return Bitpix::U8,
+            Pixels::F32(_) => return Bitpix::F32,
+            Pixels::F64(_) => return Bitpix::F64,
+            Pixels::I16(it) => int_range(it.map(i64::from)),
+            Pixels::I32(it) => int_range(it.map(i64::from)),
+            Pixels::I64(it) => int_range(it),
+        };
+
+        match range {
+            None => Bitpix::U8,
+            Some((min, max)) if min >= u8::MIN as i64 && max <= u8::MAX as i64 => Bitpix::U8,
+            Some((min, max)) if min >= i16::MIN as i64 && max <= i16::MAX as i64 => Bitpix::I16,
+            Some((min, max)) if min >= i32::MIN as i64 && max <= i32::MAX as i64 => Bitpix::I32,
+            Some(_) => Bitpix::I64,
+        }
+    }
+}
+
+impl<R> Pixels<R> {
+    /// Read the pixels in the little endian byte order
+    ///
+    /// **This violates the FITS standard**, which requires big endian pixels. It is only meant to decode
+    /// the data units of known nonconforming producers, see [It::little_endian].
+    pub fn little_endian(self, little_endian: bool) -> Self {
+        match self {
+            Pixels::U8(it) => Pixels::U8(it.little_endian(little_endian)),
+            Pixels::I16(it) => Pixels::I16(it.little_endian(little_endian)),
+            Pixels::I32(it) => Pixels::I32(it.little_endian(little_endian)),
+            Pixels::I64(it) => Pixels::I64(it.little_endian(little_endian)),
+            Pixels::F32(it) => Pixels::F32(it.little_endian(little_endian)),
+            Pixels::F64(it) => Pixels::F64(it.little_endian(little_endian)),
+        }
+    }
+
+    /// Set the byte position of the first pixel in the reader, see [It::starting_at]
+    pub fn starting_at(self, start_pos: u64) -> Self {
+        match self {
+            Pixels::U8(it) => Pixels::U8(it.starting_at(start_pos)),
+            Pixels::I16(it) => Pixels::I16(it.starting_at(start_pos)),
+            Pixels::I32(it) => Pixels::I32(it.starting_at(start_pos)),
+            Pixels::I64(it) => Pixels::I64(it.starting_at(start_pos)),
+            Pixels::F32(it) => Pixels::F32(it.starting_at(start_pos)),
+            Pixels::F64(it) => Pixels::F64(it.starting_at(start_pos)),
+        }
+    }
+
+    /// Get the type of the pixels
+    pub fn get_bitpix(&self) -> Bitpix {
+        match self {
+            Pixels::U8(_) => Bitpix::U8,
+            Pixels::I16(_) => Bitpix::I16,
+            Pixels::I32(_) => Bitpix::I32,
+            Pixels::I64(_) => Bitpix::I64,
+            Pixels::F32(_) => Bitpix::F32,
+            Pixels::F64(_) => Bitpix::F64,
+        }
+    }
+
+    /// Get the total number of pixels of the iterator, i.e. including the ones already read
+    pub fn get_num_items(&self) -> usize {
+        match self {
+            Pixels::U8(it) => it.get_num_items(),
+            Pixels::I16(it) => it.get_num_items(),
+            Pixels::I32(it) => it.get_num_items(),
+            Pixels::I64(it) => it.get_num_items(),
+            Pixels::F32(it) => it.get_num_items(),
+            Pixels::F64(it) => it.get_num_items(),
+        }
+    }
+}
+
+impl<R> ImageData<R>
+where
+    R: Read + Seek,
+{
+    /// Compute the histogram of the pixel values
+    ///
+    /// Undefined pixels, i.e. NaN values or values equal to the `BLANK` card for integer images, are skipped
+    /// as well as values outside of the range. The pixels are read lazily: if no range is given, they are
+    /// read a first time to get their minimum and maximum and a second time to fill the histogram.
+    ///
+    /// # Params
+    /// * `bins` - the number of bins dividing the range uniformly
+    /// * `range` - the `(min, max)` range of the histogram. Defaults to the range of the pixel values
+    pub fn histogram(self, bins: usize, range: Option<(f64, f64)>) -> Result<Vec<u64>, Error> {
+        let blank = self.blank;
+        let is_defined = |v: i64| Some(v) != blank;
+        match self.pixels {
+            Pixels::U8(it) => histogram(it, bins, range, |v| {
+                is_defined(v as i64).then_some(v as f64)
+            }),
+            Pixels::I16(it) => histogram(it, bins, range, |v| {
+                is_defined(v as i64).then_some(v as f64)
+            }),
+            Pixels::I32(it) => histogram(it, bins, range, |v| {
+                is_defined(v as i64).then_some(v as f64)
+            }),
+            Pixels::I64(it) => histogram(it, bins, range, |v| is_defined(v).then_some(v as f64)),
+            Pixels::F32(it) => histogram(it, bins, range, |v| (!v.is_nan()).then_some(v as f64)),
+            Pixels::F64(it) => histogram(it, bins, range, |v| (!v.is_nan()).then_some(v)),
+        }
+    }
+}
+
+/// Compute the minimum and maximum of the values given by a data iterator
+///
+/// # Params
+/// * `it` - the data iterator
+/// * `value` - converts an item to `f64`, returning `None` for undefined items
+fn min_max<T>(it: impl Iterator<Item = T>, value: impl Fn(T) -> Option<f64>) -> Option<(f64, f64)> {
+    it.filter_map(value).fold(None, |range, v| match range {
+        None => Some((v, v)),
+        Some((min, max)) => Some((v.min(min), v.max(max))),
+    })
+}
+
+/// Compute the minimum and maximum of integer values without loss of precision
+fn int_range(it: impl Iterator<Item = i64>) -> Option<(i64, i64)> {
+    it.fold(None, |range, v| match range {
+        None => Some((v, v)),
+        Some((min, max)) => Some((v.min(min), v.max(max))),
+    })
+}
+
+/// Compute the histogram of the values given by a data iterator
+///
+/// # Params
+/// * `it` - the data iterator
+/// * `bins` - the number of bins
+/// * `range` - the range of the histogram, computed from the values if not given
+/// * `value` - converts an item to `f64`, returning `None` for undefined items
+fn histogram<R, T>(
+    mut it: It<R, T>,
+    bins: usize,
+    range: Option<(f64, f64)>,
+    value: impl Fn(T) -> Option<f64>,
+) -> Result<Vec<u64>, Error>
+where
+    R: Read + Seek,
+    T: Value,
+{
+    let mut hist = vec![0; bins];
+
+    let (min, max) = if let Some(range) = range {
+        range
+    } else {
+        let range = min_max(it.by_ref(), &value);
+        it.rewind()?;
+
+        if let Some(range) = range {
+            range
+        } else {
+            // No defined values
+            return Ok(hist);
+        }
+    };
+
+    // Also handles a NaN bound
+    if bins == 0 || min.partial_cmp(&max).is_none_or(|ord| ord.is_gt()) {
+        return Ok(hist);
+    }
+
+    let bin_width = (max - min) / (bins as f64);
+    for v in it.filter_map(value) {
+        if v < min || v > max {
+            continue;
+        }
+
+        let idx = if bin_width > 0.0 {
+            (((v - min) / bin_width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        hist[idx] += 1;
+    }
+
+    Ok(hist)
+}
+
+/// Number of pixels sampled by the zscale algorithm
+const ZSCALE_NUM_SAMPLES: usize = 1000;
+/// Contrast dividing the slope of the fitted line
+const ZSCALE_CONTRAST: f64 = 0.25;
+/// Maximum fraction of samples that can be rejected
+const ZSCALE_MAX_REJECT: f64 = 0.5;
+/// Minimum number of samples remaining after the rejection
+const ZSCALE_MIN_NUM_PIXELS: usize = 5;
+/// Rejection threshold in units of the standard deviation of the residuals
+const ZSCALE_KREJ: f64 = 2.5;
+/// Maximum number of fitting/rejection iterations
+const ZSCALE_MAX_ITERATIONS: usize = 5;
+
+/// Sample the values given by a data iterator for the zscale algorithm
+///
+/// The undefined values are filtered out before taking every n-th value so that they never take the
+/// place of a sample. The stride being computed from the total number of values, fewer samples are
+/// taken when many values are undefined.
+///
+/// # Params
+/// * `it` - the data iterator
+/// * `value` - converts an item to `f64`, returning `None` for undefined items
+fn zscale_samples<R, T>(it: It<R, T>, value: impl Fn(T) -> Option<f64>) -> Vec<f64>
+where
+    R: Read,
+    T: Value,
+{
+    let stride = (it.get_num_items() / ZSCALE_NUM_SAMPLES).max(1);
+    it.filter_map(value)
+        .step_by(stride)
+        .take(ZSCALE_NUM_SAMPLES)
+        .collect()
+}
+
+/// Fit a line `y = intercept + slope * x` to the samples whose mask is `true`
+///
+/// Returns `(intercept, slope)`
+fn fit_line(samples: &[f64], good: &[bool]) -> (f64, f64) {
+    let (mut n, mut sx, mut sy) = (0.0, 0.0, 0.0);
+    for (x, &y) in samples.iter().enumerate().filter(|(i, _)| good[*i]) {
+        n += 1.0;
+        sx += x as f64;
+        sy += y;
+    }
+    let (mx, my) = (sx / n, sy / n);
+
+    let (mut sxx, mut sxy) = (0.0, 0.0);
+    for (x, &y) in samples.iter().enumerate().filter(|(i, _)| good[*i]) {
+        let dx = x as f64 - mx;
+        sxx += dx * dx;
+        sxy += dx * (y - my);
+    }
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+
+    (my - slope * mx, slope)
+}
+
+/// Compute the zscale limits of unsorted samples
+fn zscale(mut samples: Vec<f64>) -> Option<(f64, f64)> {
+    samples.sort_unstable_by(|a, b| a.total_cmp(b));
+
+    let npix = samples.len();
+    let (mut zmin, mut zmax) = (*samples.first()?, *samples.last()?);
+    let median = if npix % 2 == 1 {
+        samples[npix / 2]
+    } else {
+        0.5 * (samples[npix / 2 - 1] + samples[npix / 2])
+    };
+
+    let minpix = ZSCALE_MIN_NUM_PIXELS.max((npix as f64 * ZSCALE_MAX_REJECT) as usize);
+    let ngrow = 1_usize.max((npix as f64 * 0.01) as usize);
+
+    let mut good = vec![true; npix];
+    let mut ngoodpix = npix;
+    let mut last_ngoodpix = npix + 1;
+    let mut slope = 0.0;
+
+    for _ in 0..ZSCALE_MAX_ITERATIONS {
+        if ngoodpix >= last_ngoodpix || ngoodpix < minpix {
+            break;
+        }
+
+        let (intercept, s) = fit_line(&samples, &good);
+        slope = s;
+
+        // Residuals of the fit and their standard deviation on the good samples
+        let flat = samples
+            .iter()
+            .enumerate()
+            .map(|(x, y)| y - (intercept + slope * x as f64))
+            .collect::<Vec<_>>();
+        let n = ngoodpix as f64;
+        let mean = flat
+            .iter()
+            .zip(&good)
+            .filter(|(_, g)| **g)
+            .map(|(f, _)| f)
+            .sum::<f64>()
+            / n;
+        let var = flat
+            .iter()
+            .zip(&good)
+            .filter(|(_, g)| **g)
+            .map(|(f, _)| (f - mean) * (f - mean))
+            .sum::<f64>()
+            / n;
+        let threshold = ZSCALE_KREJ * var.sqrt();
+
+        // Reject the outliers and grow the rejected regions to their neighbours
+        let bad = flat
+            .iter()
+            .zip(&good)
+            .map(|(f, g)| !g || f.abs() > threshold)
+            .collect::<Vec<_>>();
+        for (i, g) in good.iter_mut().enumerate() {
+            let lo = i.saturating_sub(ngrow / 2);
+            let hi = (i + (ngrow - 1) / 2).min(npix - 1);
+            *g = !bad[lo..=hi].iter().any(|b| *b);
+        }
+
+        last_ngoodpix = ngoodpix;
+        ngoodpix = good.iter().filter(|g| **g).count();
+    }
+
+    if ngoodpix >= minpix {
+        if ZSCALE_CONTRAST > 0.0 {
+            slope /= ZSCALE_CONTRAST;
+        }
+        let center = ((npix - 1) / 2) as f64;
+        zmin = zmin.max(median - (center - 1.0) * slope);
+        zmax = zmax.min(median + (npix as f64 - center) * slope);
+    }
+
+    Some((zmin, zmax))
 }
 
 use std::io::Cursor;
@@ -102,6 +550,130 @@ where
     }
 }
 
+#[cfg(feature = "ndarray")]
+impl<'a, R> ImageData<&'a mut Cursor<R>>
+where
+    R: AsRef<[u8]> + 'a,
+{
+    /// Borrow the pixels of an in-memory `BITPIX = 8` image as an array, e.g. a mask or a thumbnail
+    ///
+    /// The array borrows the bytes of the buffer, e.g. a memory mapped file, so that no pixel is
+    /// copied nor allocated. This is only possible for bytes: the pixels of the other types are stored
+    /// in big endian and must be swapped, i.e. copied, see [Fits::image_f64](crate::Fits::image_f64).
+    /// The stored values are given, i.e. `BSCALE`, `BZERO` and `BLANK` are not applied.
+    ///
+    /// An error is returned if the image is not a `BITPIX = 8` one or if `shape` does not match its
+    /// number of pixels.
+    ///
+    /// # Params
+    /// * `shape` - the shape of the array in the C order, i.e. `[NAXIS2, NAXIS1]` for a 2D image
+    pub fn as_array_view(self, shape: &[usize]) -> Result<ndarray::ArrayViewD<'a, u8>, Error> {
+        if self.pixels.get_bitpix() != Bitpix::U8 {
+            return Err(Error::StaticError(
+                "Only the pixels of a BITPIX = 8 image can be borrowed",
+            ));
+        }
+
+        let num_pixels = self.get_num_pixels();
+        let bytes = self.raw_bytes();
+        let num_pixels = num_pixels.min(bytes.len());
+        ndarray::ArrayViewD::from_shape(shape, &bytes[..num_pixels])
+            .map_err(|e| Error::DynamicError(format!("Invalid shape {shape:?}: {e}")))
+    }
+}
+
+/// Random access to the pixels of an in-memory image whatever their type, e.g. behind a `dyn` reference
+pub trait PixelData {
+    /// Get the number of pixels
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there is no pixel
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the type of the pixels
+    fn bitpix(&self) -> Bitpix;
+
+    /// Get the value of a pixel converted to `f64`, `None` if the index is out of bounds
+    ///
+    /// # Params
+    /// * `i` - the index of the pixel in the data unit
+    fn value_f64(&self, i: usize) -> Option<f64>;
+}
+
+/// A pixel type, giving its bitpix and its conversion to `f64`
+trait Pixel: Value {
+    const BITPIX: Bitpix;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_pixel {
+    ($t:ty, $bitpix:expr) => {
+        impl Pixel for $t {
+            const BITPIX: Bitpix = $bitpix;
+
+            #[allow(trivial_numeric_casts)]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    };
+}
+
+impl_pixel!(u8, Bitpix::U8);
+impl_pixel!(i16, Bitpix::I16);
+impl_pixel!(i32, Bitpix::I32);
+impl_pixel!(i64, Bitpix::I64);
+impl_pixel!(f32, Bitpix::F32);
+impl_pixel!(f64, Bitpix::F64);
+
+impl<B, T> PixelData for It<&'_ mut Cursor<B>, T>
+where
+    B: AsRef<[u8]>,
+    T: Pixel,
+{
+    fn len(&self) -> usize {
+        self.get_num_items()
+    }
+
+    fn bitpix(&self) -> Bitpix {
+        T::BITPIX
+    }
+
+    fn value_f64(&self, i: usize) -> Option<f64> {
+        if i >= self.get_num_items() {
+            return None;
+        }
+
+        // Indexed from the first pixel whatever the items already read by the iterator
+        let t_bytes = std::mem::size_of::<T>();
+        let start = self.get_start_pos() as usize;
+        let mut bytes = self.bytes().get(start + i * t_bytes..)?;
+        T::read_be(&mut bytes)
+            .ok()
+            .map(|v| self.to_data_order(v).to_f64())
+    }
+}
+
+impl<B> Pixels<&'_ mut Cursor<B>>
+where
+    B: AsRef<[u8]>,
+{
+    /// Get the in-memory pixels as a trait object to access them without matching their type
+    pub fn as_pixel_data(&self) -> &dyn PixelData {
+        match self {
+            Pixels::U8(it) => it,
+            Pixels::I16(it) => it,
+            Pixels::I32(it) => it,
+            Pixels::I64(it) => it,
+            Pixels::F32(it) => it,
+            Pixels::F64(it) => it,
+        }
+    }
+}
+
 use super::stream;
 #[async_trait(?Send)]
 impl<'a, R> AsyncDataBufRead<'a, Image> for futures::io::BufReader<R>
@@ -126,3 +698,409 @@ where
         }
     }
 }
+
+/// Assemble a mosaic from 2D image tiles placed on a grid, e.g. HiPS or survey tiles
+///
+/// Each tile is given by its column and row in the grid together with its physical values in the
+/// FITS order, as given by [Fits::image_f64](crate::Fits::image_f64). The tiles are not given as
+/// [Fits](crate::Fits) of the same `BITPIX` because reading their data units requires a mutable
+/// borrow, and their physical values allow tiles of different `BITPIX`, `BSCALE` or `BZERO` to be
+/// stitched together. The tile at column `0` and row `0` is placed at the first pixel of the mosaic
+/// so that rows go up the `NAXIS2` axis. The mosaic spans the grid up to the last column and row
+/// given and its pixels not covered by a tile are NaN.
+///
+/// Returns the values of the mosaic in the FITS order together with its shape `[width, height]`.
+/// An error is returned if the mosaic is too large to be allocated.
+///
+/// # Params
+/// * `tiles` - the column, row and values of each tile
+/// * `tile_w` - the width of each tile, i.e. its `NAXIS1`
+/// * `tile_h` - the height of each tile, i.e. its `NAXIS2`
+pub fn stitch(
+    tiles: &[(usize, usize, &[f64])],
+    tile_w: usize,
+    tile_h: usize,
+) -> Result<(Vec<f64>, Vec<usize>), Error> {
+    let too_large = || Error::StaticError("The mosaic is too large to be allocated");
+    let grid_size = |idx: usize| idx.checked_add(1).ok_or_else(too_large);
+
+    let (mut num_cols, mut num_rows) = (0, 0);
+    for &(col, row, _) in tiles {
+        num_cols = num_cols.max(grid_size(col)?);
+        num_rows = num_rows.max(grid_size(row)?);
+    }
+    let width = num_cols.checked_mul(tile_w).ok_or_else(too_large)?;
+    let height = num_rows.checked_mul(tile_h).ok_or_else(too_large)?;
+    let num_pixels = width.checked_mul(height).ok_or_else(too_large)?;
+    let num_tiles = num_cols.checked_mul(num_rows).ok_or_else(too_large)?;
+
+    let mut values = Vec::new();
+    values
+        .try_reserve_exact(num_pixels)
+        .map_err(|_| too_large())?;
+    values.resize(num_pixels, f64::NAN);
+    let mut is_placed = Vec::new();
+    is_placed
+        .try_reserve_exact(num_tiles)
+        .map_err(|_| too_large())?;
+    is_placed.resize(num_tiles, false);
+
+    for &(col, row, tile) in tiles {
+        if Some(tile.len()) != tile_w.checked_mul(tile_h) {
+            return Err(Error::DynamicError(format!(
+                "Tile ({col}, {row}) has {} pixels instead of {tile_w}x{tile_h}",
+                tile.len()
+            )));
+        }
+        if std::mem::replace(&mut is_placed[row * num_cols + col], true) {
+            return Err(Error::DynamicError(format!(
+                "Tile ({col}, {row}) is given more than once"
+            )));
+        }
+
+        for (y, tile_line) in tile.chunks(tile_w.max(1)).enumerate() {
+            let start = (row * tile_h + y) * width + col * tile_w;
+            values[start..start + tile_w].copy_from_slice(tile_line);
+        }
+    }
+
+    Ok((values, vec![width, height]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stitch, Pixels};
+
+    use crate::hdu::header::Bitpix;
+
+    use crate::test_utils::{first_primary, mock_hdu};
+    use std::io::Cursor;
+
+    #[test]
+    fn pixels_of_bitpix() {
+        for bitpix in [
+            Bitpix::U8,
+            Bitpix::I16,
+            Bitpix::I32,
+            Bitpix::I64,
+            Bitpix::F32,
+            Bitpix::F64,
+        ] {
+            let pixels = Pixels::new(bitpix, Cursor::new(&[0_u8; 16][..]), 16);
+            assert_eq!(pixels.get_bitpix(), bitpix);
+            assert_eq!(pixels.get_num_items(), 16 / bitpix.byte_size());
+
+            let pixels = Pixels::empty(bitpix, Cursor::new(&[][..]));
+            assert_eq!(pixels.get_bitpix(), bitpix);
+            assert_eq!(pixels.get_num_items(), 0);
+        }
+    }
+
+    #[test]
+    fn pixels_behind_trait_object() {
+        let bytes = [-1.5_f32, 0.0, 2.25]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        let mut reader = Cursor::new(&bytes[..]);
+        let mut pixels = Pixels::new(Bitpix::F32, &mut reader, 12);
+
+        let data = pixels.as_pixel_data();
+        assert_eq!((data.len(), data.bitpix()), (3, Bitpix::F32));
+        assert_eq!(data.value_f64(0), Some(-1.5));
+        assert_eq!(data.value_f64(2), Some(2.25));
+        assert_eq!(data.value_f64(3), None);
+
+        // Reading the iterator does not move the indices
+        if let Pixels::F32(it) = &mut pixels {
+            assert_eq!(it.next(), Some(-1.5));
+        }
+        assert_eq!(pixels.as_pixel_data().value_f64(1), Some(0.0));
+
+        let bytes = [0x80_u8, 0x00];
+        let mut reader = Cursor::new(&bytes[..]);
+        let pixels = Pixels::new(Bitpix::I16, &mut reader, 2);
+        let data = pixels.as_pixel_data();
+        assert_eq!(
+            (data.bitpix(), data.value_f64(0)),
+            (Bitpix::I16, Some(-32768.0))
+        );
+        assert!(!data.is_empty());
+    }
+
+    #[test]
+    fn pixels_of_truncated_data_unit() {
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    4                                                  ",
+                b"END                                                                             ",
+            ],
+            &[0x00, 0x01, 0xff, 0xfe],
+        );
+        // Only the first 2 of the 4 pixels are present
+        let (mut hdu_list, hdu) = first_primary(&buf[..2880 + 4]);
+
+        let mut pixels = hdu_list.get_data(&hdu).pixels();
+        // The failed reads of the missing pixels do not move the indices
+        if let Pixels::I16(it) = &mut pixels {
+            assert_eq!(it.collect::<Vec<_>>(), [1, -2]);
+        }
+        let data = pixels.as_pixel_data();
+        assert_eq!(data.value_f64(0), Some(1.0));
+        assert_eq!(data.value_f64(1), Some(-2.0));
+        assert_eq!(data.value_f64(2), None);
+
+        let pixels = hdu_list.get_data(&hdu).little_endian(true).pixels();
+        assert_eq!(pixels.as_pixel_data().value_f64(0), Some(256.0));
+    }
+
+    #[test]
+    fn narrowest_lossless_type() {
+        let min_fit_type = |bitpix, values: &[i64]| {
+            let bytes = values
+                .iter()
+                .flat_map(|v| v.to_be_bytes()[8 - bitpix as usize / 8..].to_vec())
+                .collect::<Vec<_>>();
+            Pixels::new(bitpix, Cursor::new(&bytes[..]), bytes.len() as u64).min_fit_type()
+        };
+
+        assert_eq!(min_fit_type(Bitpix::I32, &[0, 12, 255]), Bitpix::U8);
+        assert_eq!(min_fit_type(Bitpix::I32, &[-1, 12, 255]), Bitpix::I16);
+        assert_eq!(min_fit_type(Bitpix::I32, &[0, 32768]), Bitpix::I32);
+        assert_eq!(min_fit_type(Bitpix::I16, &[-32768, 32767]), Bitpix::I16);
+        assert_eq!(
+            min_fit_type(Bitpix::I64, &[-(1 << 31), 1 << 20]),
+            Bitpix::I32
+        );
+        assert_eq!(min_fit_type(Bitpix::I64, &[1 << 31]), Bitpix::I64);
+        assert_eq!(min_fit_type(Bitpix::I64, &[]), Bitpix::U8);
+
+        // Floats are left unchanged
+        let bytes = [1.0_f32, 2.0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        let pixels = Pixels::new(Bitpix::F32, Cursor::new(&bytes[..]), 8);
+        assert_eq!(pixels.min_fit_type(), Bitpix::F32);
+    }
+
+    fn histogram(buf: &[u8], bins: usize, range: Option<(f64, f64)>) -> Vec<u64> {
+        let (mut hdu_list, hdu) = first_primary(buf);
+        hdu_list.get_data(&hdu).histogram(bins, range).unwrap()
+    }
+
+    #[test]
+    fn zscale_of_noisy_gradient() {
+        // 100x100 gradient with a pseudo random noise, hot pixels and blank pixels
+        let mut seed = 12345_u64;
+        let mut data = Vec::with_capacity(2 * 100 * 100);
+        for y in 0..100_i16 {
+            for x in 0..100_i16 {
+                seed = (seed * 1103515245 + 12345) % (1 << 31);
+                let noise = ((seed >> 16) % 64) as i16;
+                let v = if (x + y * 100) % 250 == 3 {
+                    -1
+                } else if (x * 7 + y * 13) % 97 == 0 {
+                    30000
+                } else {
+                    1000 + 3 * x + 2 * y + noise
+                };
+                data.extend(v.to_be_bytes());
+            }
+        }
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                  100                                                  ",
+                b"NAXIS2  =                  100                                                  ",
+                b"BLANK   =                   -1                                                  ",
+                b"END                                                                             ",
+            ],
+            &data,
+        );
+
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        let (z1, z2) = hdu_list.get_data(&hdu).zscale().unwrap();
+
+        // Reference values given by fitting the samples with the IRAF algorithm as implemented by
+        // astropy's `ZScaleInterval`
+        assert_eq!(z1, 1021.0);
+        assert!((z2 - 1961.492581103429).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zscale_skips_blank_pixels() {
+        // Every pixel that a stride of 2 over the whole data unit would sample is blank
+        let data = (0..2000_i16)
+            .flat_map(|v| if v % 2 == 0 { -1 } else { v }.to_be_bytes())
+            .collect::<Vec<_>>();
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                 2000                                                  ",
+                b"BLANK   =                   -1                                                  ",
+                b"END                                                                             ",
+            ],
+            &data,
+        );
+
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        let (z1, z2) = hdu_list.get_data(&hdu).zscale().unwrap();
+        assert_eq!((z1, z2), (1.0, 1997.0));
+    }
+
+    #[test]
+    fn zscale_of_undefined_image() {
+        let data = [f32::NAN; 4]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                  -32                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    4                                                  ",
+                b"END                                                                             ",
+            ],
+            &data,
+        );
+
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert_eq!(hdu_list.get_data(&hdu).zscale(), None);
+    }
+
+    #[test]
+    fn histogram_of_uniform_image() {
+        // 10x10 image whose values are 0..100 with 3 blank pixels
+        let data = (0..100_i16)
+            .map(|v| if v % 40 == 39 { -1 } else { v })
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                   16                                                  ",
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                   10                                                  ",
+                b"NAXIS2  =                   10                                                  ",
+                b"BLANK   =                   -1                                                  ",
+                b"END                                                                             ",
+            ],
+            &data,
+        );
+
+        // Range of the defined values is 0..=99
+        let hist = histogram(&buf, 10, None);
+        assert_eq!(hist, [10, 10, 10, 9, 10, 10, 10, 9, 10, 10]);
+
+        let hist = histogram(&buf, 2, Some((0.0, 19.0)));
+        assert_eq!(hist, [10, 10]);
+    }
+
+    #[test]
+    fn histogram_skips_nan() {
+        let data = [1.0_f32, f32::NAN, 2.0, 3.0, f32::NAN, 4.0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        let buf = mock_hdu(
+            &[
+                b"SIMPLE  =                    T                                                  ",
+                b"BITPIX  =                  -32                                                  ",
+                b"NAXIS   =                    1                                                  ",
+                b"NAXIS1  =                    6                                                  ",
+                b"END                                                                             ",
+            ],
+            &data,
+        );
+
+        assert_eq!(histogram(&buf, 3, None), [1, 1, 2]);
+        assert_eq!(histogram(&buf, 0, None), []);
+    }
+
+    #[test]
+    fn stitch_grid_of_tiles() {
+        // 2x2 tiles of 3x2 pixels, the value giving the tile and the pixel index
+        let tiles = (0..4)
+            .map(|t| (0..6).map(|i| (10 * t + i) as f64).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let grid = [
+            (0, 0, &tiles[0][..]),
+            (1, 0, &tiles[1][..]),
+            (0, 1, &tiles[2][..]),
+            (1, 1, &tiles[3][..]),
+        ];
+
+        let (values, shape) = stitch(&grid, 3, 2).unwrap();
+        assert_eq!(shape, [6, 4]);
+        #[rustfmt::skip]
+        assert_eq!(
+            values,
+            [
+                0.0, 1.0, 2.0, 10.0, 11.0, 12.0,
+                3.0, 4.0, 5.0, 13.0, 14.0, 15.0,
+                20.0, 21.0, 22.0, 30.0, 31.0, 32.0,
+                23.0, 24.0, 25.0, 33.0, 34.0, 35.0,
+            ]
+        );
+
+        // A missing tile gives NaN pixels
+        let (values, shape) = stitch(&grid[1..2], 3, 2).unwrap();
+        assert_eq!(shape, [6, 2]);
+        assert!(values[..3].iter().all(|v| v.is_nan()));
+        assert_eq!(values[3..6], [10.0, 11.0, 12.0]);
+
+        // Invalid tiles
+        assert!(stitch(&[(0, 0, &tiles[0][..4])], 3, 2).is_err());
+        assert!(stitch(&[grid[0], grid[0]], 3, 2).is_err());
+
+        // Grid positions overflowing the size of the mosaic
+        assert!(stitch(&[(usize::MAX, 0, &tiles[0][..])], 3, 2).is_err());
+        assert!(stitch(&[(usize::MAX / 2, 0, &tiles[0][..])], 3, 2).is_err());
+        assert!(stitch(&[(1 << 30, 1 << 30, &tiles[0][..])], 3, 2).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn u8_image_as_array_view() {
+        let mock_image = |bitpix: &crate::card::CardBuf| {
+            let cards: [&crate::card::CardBuf; 6] = [
+                b"SIMPLE  =                    T                                                  ",
+                bitpix,
+                b"NAXIS   =                    2                                                  ",
+                b"NAXIS1  =                    3                                                  ",
+                b"NAXIS2  =                    2                                                  ",
+                b"END                                                                             ",
+            ];
+            mock_hdu(&cards, &[1, 2, 3, 4, 5, 6])
+        };
+
+        let buf = mock_image(
+            b"BITPIX  =                    8                                                  ",
+        );
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        let view = hdu_list.get_data(&hdu).as_array_view(&[2, 3]).unwrap();
+        assert_eq!(view.shape(), [2, 3]);
+        assert_eq!(view[[0, 2]], 3);
+        assert_eq!(view[[1, 0]], 4);
+        // The pixels are borrowed from the buffer
+        assert_eq!(view.as_ptr(), buf[2880..].as_ptr());
+
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert!(hdu_list.get_data(&hdu).as_array_view(&[4, 2]).is_err());
+
+        let buf = mock_image(
+            b"BITPIX  =                   16                                                  ",
+        );
+        let (mut hdu_list, hdu) = first_primary(&buf);
+        assert!(hdu_list.get_data(&hdu).as_array_view(&[2, 3]).is_err());
+    }
+}
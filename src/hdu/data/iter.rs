@@ -1,50 +1,136 @@
 use std::fmt::Debug;
 use std::io::Read;
 
-use byteorder::BigEndian;
+use byteorder::{BigEndian, ByteOrder};
 use serde::Serialize;
 
 use byteorder::ReadBytesExt;
 
 use crate::hdu::Error;
-pub trait Value: Sized {
+pub trait Value: Sized + Copy {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error>;
+
+    /// Read and convert enough big endian values to fill `dst`
+    ///
+    /// The raw bytes are read directly into `dst` and then converted in place. The
+    /// conversion is a no-op on big endian hosts and a byte swap over the whole slice
+    /// on little endian ones which is much cheaper than reading the values one by one.
+    /// A reader holding fewer bytes than `dst` returns an [Error::Io] error, it never panics.
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error>;
+
+    /// Convert values to big endian bytes, `dst` being exactly as long as the bytes of `src`
+    fn write_be_into(src: &[Self], dst: &mut [u8]);
+
+    /// Reverse the byte order of the value
+    fn swap_bytes(self) -> Self;
 }
 impl Value for u8 {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error> {
         Ok(reader.read_u8()?)
     }
+
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error> {
+        Ok(reader.read_exact(dst)?)
+    }
+
+    fn write_be_into(src: &[Self], dst: &mut [u8]) {
+        dst.copy_from_slice(src);
+    }
+
+    fn swap_bytes(self) -> Self {
+        self
+    }
 }
 impl Value for i16 {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error> {
         Ok(reader.read_i16::<BigEndian>()?)
     }
+
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error> {
+        Ok(reader.read_i16_into::<BigEndian>(dst)?)
+    }
+
+    fn write_be_into(src: &[Self], dst: &mut [u8]) {
+        BigEndian::write_i16_into(src, dst);
+    }
+
+    fn swap_bytes(self) -> Self {
+        i16::swap_bytes(self)
+    }
 }
 impl Value for i32 {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error> {
         Ok(reader.read_i32::<BigEndian>()?)
     }
+
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error> {
+        Ok(reader.read_i32_into::<BigEndian>(dst)?)
+    }
+
+    fn write_be_into(src: &[Self], dst: &mut [u8]) {
+        BigEndian::write_i32_into(src, dst);
+    }
+
+    fn swap_bytes(self) -> Self {
+        i32::swap_bytes(self)
+    }
 }
 impl Value for i64 {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error> {
         Ok(reader.read_i64::<BigEndian>()?)
     }
+
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error> {
+        Ok(reader.read_i64_into::<BigEndian>(dst)?)
+    }
+
+    fn write_be_into(src: &[Self], dst: &mut [u8]) {
+        BigEndian::write_i64_into(src, dst);
+    }
+
+    fn swap_bytes(self) -> Self {
+        i64::swap_bytes(self)
+    }
 }
 impl Value for f32 {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error> {
         Ok(reader.read_f32::<BigEndian>()?)
     }
+
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error> {
+        Ok(reader.read_f32_into::<BigEndian>(dst)?)
+    }
+
+    fn write_be_into(src: &[Self], dst: &mut [u8]) {
+        BigEndian::write_f32_into(src, dst);
+    }
+
+    fn swap_bytes(self) -> Self {
+        f32::from_bits(self.to_bits().swap_bytes())
+    }
 }
 impl Value for f64 {
     fn read_be<R: ReadBytesExt>(reader: &mut R) -> Result<Self, Error> {
         Ok(reader.read_f64::<BigEndian>()?)
     }
+
+    fn read_be_into<R: ReadBytesExt>(reader: &mut R, dst: &mut [Self]) -> Result<(), Error> {
+        Ok(reader.read_f64_into::<BigEndian>(dst)?)
+    }
+
+    fn write_be_into(src: &[Self], dst: &mut [u8]) {
+        BigEndian::write_f64_into(src, dst);
+    }
+
+    fn swap_bytes(self) -> Self {
+        f64::from_bits(self.to_bits().swap_bytes())
+    }
 }
 
 /// An iterator over the data.
 ///
 /// Values are read and interpreted as the FITS standard big endian
-/// byte order.
+/// byte order, unless [It::little_endian] is set.
 #[derive(Debug, Serialize)]
 pub struct It<R, T> {
     /// The reader
@@ -52,7 +138,13 @@ pub struct It<R, T> {
     /// The number of items the reader must read
     num_items: usize,
     /// Number of item read
-    cur_idx: usize,
+    pub(crate) cur_idx: usize,
+    /// Index following the last item not yet read from the back
+    end_idx: usize,
+    /// Whether the values are read in the little endian byte order
+    little_endian: bool,
+    /// The byte position of the first item in the reader, for the random access to in-memory data
+    start_pos: u64,
     /// The type of element read from the reader
     _t: std::marker::PhantomData<T>,
 }
@@ -98,12 +190,58 @@ where
         Self {
             reader,
             cur_idx: 0,
+            end_idx: num_items,
             num_items,
+            little_endian: false,
+            start_pos: 0,
             _t: std::marker::PhantomData,
         }
     }
 }
 
+impl<R, T> It<R, T> {
+    /// Get the total number of items in the data, i.e. including the ones already read
+    pub fn get_num_items(&self) -> usize {
+        self.num_items
+    }
+
+    /// Read the values in the little endian byte order
+    ///
+    /// **This violates the FITS standard**, which requires big endian values. It is an escape hatch
+    /// for the data units written by known nonconforming producers, see
+    /// [FitsOptions::assume_little_endian](crate::FitsOptions::assume_little_endian).
+    pub fn little_endian(mut self, little_endian: bool) -> Self {
+        self.little_endian = little_endian;
+        self
+    }
+
+    /// Set the byte position of the first item in the reader, 0 by default
+    ///
+    /// It is only used to access the items of an in-memory reader randomly, see
+    /// [PixelData](crate::hdu::data::PixelData).
+    pub fn starting_at(mut self, start_pos: u64) -> Self {
+        self.start_pos = start_pos;
+        self
+    }
+
+    /// Get the byte position of the first item in the reader
+    pub(crate) fn get_start_pos(&self) -> u64 {
+        self.start_pos
+    }
+
+    /// Convert a value read in the big endian byte order to the byte order of the data
+    pub(crate) fn to_data_order(&self, value: T) -> T
+    where
+        T: Value,
+    {
+        if self.little_endian {
+            value.swap_bytes()
+        } else {
+            value
+        }
+    }
+}
+
 impl<R, T> Iterator for It<R, T>
 where
     R: Read,
@@ -112,28 +250,221 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cur_idx == self.num_items {
+        if self.cur_idx == self.end_idx {
             None
         } else {
             let byte = T::read_be(&mut self.reader);
             self.cur_idx += 1;
 
-            byte.ok()
+            byte.ok().map(|v| self.to_data_order(v))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let num_remaining_items = self.end_idx - self.cur_idx;
+        (num_remaining_items, Some(num_remaining_items))
+    }
+}
+
+impl<R, T> ExactSizeIterator for It<R, T>
+where
+    R: Read,
+    T: Value,
+{
+}
+
+impl<R, T> DoubleEndedIterator for It<R, T>
+where
+    R: Read + Seek,
+    T: Value,
+{
+    /// Read the last item not read yet
+    ///
+    /// The reader seeks to the item and then back to the current position
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cur_idx == self.end_idx {
+            None
+        } else {
+            let t_bytes = std::mem::size_of::<T>() as i64;
+            let off = (self.end_idx - 1 - self.cur_idx) as i64 * t_bytes;
+
+            self.reader.seek_relative(off).ok()?;
+            let value = T::read_be(&mut self.reader);
+            self.reader.seek_relative(-off - t_bytes).ok()?;
+            self.end_idx -= 1;
+
+            value.ok().map(|v| self.to_data_order(v))
+        }
+    }
+}
+
+/// Report the end of the reader reached before the end of the data unit as a truncated data unit
+fn truncated(e: Error) -> Error {
+    match e {
+        Error::Io(std::io::ErrorKind::UnexpectedEof) => Error::TruncatedData,
+        e => e,
+    }
+}
+
+impl<R, T> It<R, T>
+where
+    R: Read,
+    T: Value,
+{
+    /// Read the next values into `dst` in one go
+    ///
+    /// This is the fast path to prefer over iterating when a lot of values need to be read.
+    /// Returns the number of values read, which is less than `dst.len()` when the end
+    /// of the data is reached. A reader holding fewer bytes than declared by the header
+    /// returns an [Error::TruncatedData] error.
+    pub fn read_into(&mut self, dst: &mut [T]) -> Result<usize, Error> {
+        let num_values = dst.len().min(self.end_idx - self.cur_idx);
+        T::read_be_into(&mut self.reader, &mut dst[..num_values]).map_err(truncated)?;
+        if self.little_endian {
+            for v in &mut dst[..num_values] {
+                *v = v.swap_bytes();
+            }
         }
+        self.cur_idx += num_values;
+
+        Ok(num_values)
     }
 }
 
-use std::io::Seek;
+impl<R, T> It<R, T>
+where
+    R: Read,
+    T: Value + Default + Clone,
+{
+    /// Iterate over the remaining values row by row, e.g. the rows of an image of `NAXIS1 = width`
+    ///
+    /// The number of remaining values must be a multiple of `width`.
+    pub fn rows(self, width: usize) -> Result<Rows<R, T>, Error> {
+        let num_remaining_items = self.end_idx - self.cur_idx;
+        if width == 0 || !num_remaining_items.is_multiple_of(width) {
+            return Err(Error::DynamicError(format!(
+                "{num_remaining_items} values cannot be split into rows of {width} values"
+            )));
+        }
+
+        Ok(Rows { it: self, width })
+    }
+}
+
+/// An iterator over the rows of the data returned by [It::rows]
+///
+/// A row that cannot be read, e.g. because the data unit is truncated, gives an error that ends
+/// the iteration.
+#[derive(Debug)]
+pub struct Rows<R, T> {
+    it: It<R, T>,
+    width: usize,
+}
+
+impl<R, T> Iterator for Rows<R, T>
+where
+    R: Read,
+    T: Value + Default + Clone,
+{
+    type Item = Result<Vec<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.it.cur_idx == self.it.end_idx {
+            None
+        } else {
+            let mut row = vec![T::default(); self.width];
+            if let Err(e) = self.it.read_into(&mut row) {
+                self.it.cur_idx = self.it.end_idx;
+                return Some(Err(e));
+            }
+            Some(Ok(row))
+        }
+    }
+}
+
+impl<R> It<R, u8>
+where
+    R: Read,
+{
+    /// Reinterpret the remaining bytes as big endian values of another type, e.g. 16-bit integers
+    /// packed in a data unit declared with `BITPIX = 8`
+    ///
+    /// This is a low-level escape hatch for non-standard files: the header is not consulted, it is up
+    /// to the caller to know that the bytes actually hold values of type `T`. The number of remaining
+    /// bytes must be a multiple of the size of `T`.
+    pub fn reinterpret_as<T>(mut self) -> Result<Vec<T>, Error>
+    where
+        T: Value + Default + Clone,
+    {
+        let num_remaining_bytes = self.end_idx - self.cur_idx;
+        let t_bytes = std::mem::size_of::<T>();
+        if !num_remaining_bytes.is_multiple_of(t_bytes) {
+            return Err(Error::DynamicError(format!(
+                "{num_remaining_bytes} bytes cannot be reinterpreted as values of {t_bytes} bytes"
+            )));
+        }
+
+        let mut values = vec![T::default(); num_remaining_bytes / t_bytes];
+        T::read_be_into(&mut self.reader, &mut values).map_err(truncated)?;
+        if self.little_endian {
+            for v in &mut values {
+                *v = v.swap_bytes();
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+use std::io::{Seek, SeekFrom};
 impl<R, T> It<R, T>
 where
     R: Read + Seek,
     T: Value,
 {
+    /// Move the iterator back to the first item, the items read from the back being available again
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        let t_bytes = std::mem::size_of::<T>() as i64;
+        self.reader
+            .seek_relative(-(self.cur_idx as i64) * t_bytes)?;
+        self.cur_idx = 0;
+        self.end_idx = self.num_items;
+
+        Ok(())
+    }
+
+    /// Read at most `limit` values from the current position, e.g. to preview the beginning of a large image
+    ///
+    /// Only `min(limit, remaining values)` values are decoded but the reader is first checked to contain
+    /// all the remaining values of the data so that a truncated data unit gives an error.
+    pub fn read_limited(&mut self, limit: usize) -> Result<Vec<T>, Error>
+    where
+        T: Default + Clone,
+    {
+        let t_bytes = std::mem::size_of::<T>() as u64;
+        let num_remaining_bytes = (self.num_items - self.cur_idx) as u64 * t_bytes;
+
+        let cur_pos = self.reader.stream_position()?;
+        let end_pos = self.reader.seek(SeekFrom::End(0))?;
+        self.reader.seek(SeekFrom::Start(cur_pos))?;
+        if end_pos.saturating_sub(cur_pos) < num_remaining_bytes {
+            return Err(Error::StaticError(
+                "The data unit is truncated, not all of its values can be read",
+            ));
+        }
+
+        let mut values = vec![T::default(); limit.min(self.end_idx - self.cur_idx)];
+        self.read_into(&mut values)?;
+
+        Ok(values)
+    }
+
     /// Returns the value of the item from a data iterator
     ///
     /// This internally perform a seek on the inner reader to directly
     /// targets and read the value
     /// This should be faster than reading the whole stream until the idx
+    /// The iterator is moved just after the item, the items read from the back being available again
     pub fn read_value(&mut self, idx: usize) -> Result<T, Error> {
         if idx >= self.num_items {
             Err(Error::StaticError("Value to retrieve is out of bounds"))
@@ -144,9 +475,147 @@ where
 
             self.reader.seek_relative(off)?;
             self.cur_idx = idx;
+            self.end_idx = self.num_items;
 
             self.next()
                 .ok_or(Error::StaticError("Value to retrieve is out of bounds"))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::It;
+    use crate::error::Error;
+    use std::io::Cursor;
+
+    #[test]
+    fn decode_big_endian_values() {
+        let buf: [u8; 16] = [
+            0x3f, 0x80, 0x00, 0x00, // 1.0
+            0xc0, 0x49, 0x0f, 0xdb, // -3.1415927
+            0x7f, 0x7f, 0xff, 0xff, // f32::MAX
+            0x00, 0x00, 0x00, 0x01, // smallest subnormal
+        ];
+        let expected = [1.0, -std::f32::consts::PI, f32::MAX, f32::from_bits(1)];
+
+        let it = It::<_, f32>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert_eq!(it.collect::<Vec<_>>(), expected);
+
+        let mut it = It::<_, f32>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        let mut values = [0.0; 3];
+        assert_eq!(it.read_into(&mut values).unwrap(), 3);
+        assert_eq!(values, expected[..3]);
+        assert_eq!(it.read_into(&mut values).unwrap(), 1);
+        assert_eq!(values[0], expected[3]);
+        assert_eq!(it.read_into(&mut values).unwrap(), 0);
+
+        let buf: [u8; 12] = [
+            0x00, 0x01, 0xff, 0xfe, 0x80, 0x00, 0x7f, 0xff, 0x12, 0x34, 0xab, 0xcd,
+        ];
+        let mut it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        let mut values = [0; 6];
+        assert_eq!(it.read_into(&mut values).unwrap(), 6);
+        assert_eq!(
+            values,
+            [1, -2, i16::MIN, i16::MAX, 0x1234, 0xabcd_u16 as i16]
+        );
+    }
+
+    #[test]
+    fn truncated_values() {
+        // The reader holds fewer bytes than announced by the header
+        let buf: [u8; 7] = [0x00, 0x01, 0xff, 0xfe, 0x80, 0x00, 0x7f];
+
+        let mut it = It::<_, i16>::new(Cursor::new(&buf[..]), 8);
+        let mut values = [0; 4];
+        assert_eq!(it.read_into(&mut values), Err(Error::TruncatedData));
+        let mut it = It::<_, i32>::new(Cursor::new(&buf[..]), 8);
+        let mut values = [0; 2];
+        assert_eq!(it.read_into(&mut values), Err(Error::TruncatedData));
+        let mut it = It::<_, i64>::new(Cursor::new(&buf[..]), 8);
+        assert_eq!(it.read_into(&mut [0; 1]), Err(Error::TruncatedData));
+
+        let it = It::<_, u8>::new(Cursor::new(&buf[..]), 8);
+        assert_eq!(it.reinterpret_as::<i16>(), Err(Error::TruncatedData));
+    }
+
+    #[test]
+    fn iterate_from_both_ends() {
+        let buf: [u8; 8] = [0x00, 0x01, 0xff, 0xfe, 0x80, 0x00, 0x7f, 0xff];
+
+        let it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.rev().collect::<Vec<_>>(), [i16::MAX, i16::MIN, -2, 1]);
+
+        let mut it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert_eq!(it.next_back(), Some(i16::MAX));
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.next_back(), Some(i16::MIN));
+        assert_eq!(it.next(), Some(-2));
+        assert_eq!((it.next(), it.next_back()), (None, None));
+
+        it.rewind().unwrap();
+        assert_eq!(it.enumerate().next_back(), Some((3, i16::MAX)));
+    }
+
+    #[test]
+    fn iterate_rows() {
+        let buf: [u8; 12] = [
+            0x00, 0x01, 0xff, 0xfe, 0x80, 0x00, 0x7f, 0xff, 0x12, 0x34, 0xab, 0xcd,
+        ];
+
+        let it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        let rows = it.rows(3).unwrap().collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            rows,
+            Ok(vec![
+                vec![1, -2, i16::MIN],
+                vec![i16::MAX, 0x1234, 0xabcd_u16 as i16]
+            ])
+        );
+
+        // A truncated data gives an error ending the iteration
+        let it = It::<_, i16>::new(Cursor::new(&buf[..8]), buf.len() as u64);
+        let mut rows = it.rows(3).unwrap();
+        assert_eq!(rows.next(), Some(Ok(vec![1, -2, i16::MIN])));
+        assert!(matches!(rows.next(), Some(Err(_))));
+        assert_eq!(rows.next(), None);
+
+        let it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert!(it.rows(4).is_err());
+        let it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert!(it.rows(0).is_err());
+    }
+
+    #[test]
+    fn reinterpret_bytes() {
+        let buf: [u8; 6] = [0x00, 0x01, 0xff, 0xfe, 0x80, 0x00];
+
+        let it = It::<_, u8>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert_eq!(it.reinterpret_as::<i16>().unwrap(), [1, -2, i16::MIN]);
+
+        let mut it = It::<_, u8>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert_eq!(it.next(), Some(0x00));
+        assert_eq!(it.next(), Some(0x01));
+        assert_eq!(it.reinterpret_as::<i16>().unwrap(), [-2, i16::MIN]);
+
+        let it = It::<_, u8>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert!(it.reinterpret_as::<i32>().is_err());
+    }
+
+    #[test]
+    fn read_limited_values() {
+        let buf: [u8; 8] = [0x00, 0x01, 0xff, 0xfe, 0x80, 0x00, 0x7f, 0xff];
+
+        let mut it = It::<_, i16>::new(Cursor::new(&buf[..]), buf.len() as u64);
+        assert_eq!(it.read_limited(2).unwrap(), [1, -2]);
+        assert_eq!(it.read_limited(5).unwrap(), [i16::MIN, i16::MAX]);
+        assert!(it.read_limited(5).unwrap().is_empty());
+
+        // The header declares more values than available
+        let mut it = It::<_, i16>::new(Cursor::new(&buf[..]), 10);
+        assert!(it.read_limited(2).is_err());
+    }
+}
@@ -1,13 +1,18 @@
 pub mod asciitable;
 pub mod bintable;
+pub mod chunk;
 pub mod image;
 pub mod iter;
 pub mod stream;
+pub mod unknown;
+pub mod writer;
 
 pub use bintable::TableData;
-pub use image::{ImageData, Pixels};
+pub use chunk::DataUnitReader;
+pub use image::{ImageData, PixelData, Pixels};
 
-pub use iter::It;
+pub use iter::{It, Rows};
+pub use writer::DataUnitWriter;
 
 use std::fmt::Debug;
 
@@ -15,10 +20,75 @@ use std::marker::Unpin;
 
 use crate::error::Error;
 use crate::hdu::header::Xtension;
+use crate::FitsOptions;
 
-use std::io::Read;
+use std::io::{BufRead, Read, Seek};
 pub use stream::DataStream;
 
+/// Move a seekable reader, e.g. a file, from a data unit to the beginning of the next HDU
+///
+/// This is the fast path for random HDU access: the rest of the data unit and its padding
+/// are skipped with a single relative seek, without reading any byte.
+/// Non-seekable streams, e.g. a pipe or a socket, fall back to [discard_data_unit] which reads
+/// the bytes through the buffer of the reader and discards them.
+///
+/// # Params
+/// * `reader` - the reader positioned `num_bytes_read` bytes after the beginning of the data unit
+/// * `num_bytes_data_unit` - the size of the data unit, excluding its padding
+/// * `num_bytes_read` - the number of bytes of the data unit already read
+pub fn skip_data_unit<R>(
+    reader: &mut R,
+    num_bytes_data_unit: u64,
+    num_bytes_read: u64,
+) -> Result<(), Error>
+where
+    R: Seek,
+{
+    let num_bytes_to_skip = num_bytes_data_unit.next_multiple_of(2880) - num_bytes_read;
+    reader.seek_relative(num_bytes_to_skip as i64)?;
+
+    Ok(())
+}
+
+/// Move a non-seekable stream from a data unit to the beginning of the next HDU
+///
+/// This is the fallback of [skip_data_unit]: the rest of the data unit and its padding are read
+/// through the buffer of the reader and discarded. A missing padding at EOF is tolerated as
+/// some files do not pad their last HDU.
+///
+/// # Params
+/// * `reader` - the reader positioned `num_bytes_read` bytes after the beginning of the data unit
+/// * `num_bytes_data_unit` - the size of the data unit, excluding its padding
+/// * `num_bytes_read` - the number of bytes of the data unit already read
+pub fn discard_data_unit<R>(
+    reader: &mut R,
+    num_bytes_data_unit: u64,
+    num_bytes_read: u64,
+) -> Result<(), Error>
+where
+    R: BufRead,
+{
+    let mut num_bytes_to_skip = num_bytes_data_unit.next_multiple_of(2880) - num_bytes_read;
+    let mut num_data_bytes_to_skip = num_bytes_data_unit.saturating_sub(num_bytes_read);
+
+    while num_bytes_to_skip > 0 {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            if num_data_bytes_to_skip > 0 {
+                return Err(Error::Io(std::io::ErrorKind::UnexpectedEof));
+            }
+            break;
+        }
+
+        let amt = (buf.len() as u64).min(num_bytes_to_skip);
+        reader.consume(amt as usize);
+        num_bytes_to_skip -= amt;
+        num_data_bytes_to_skip = num_data_bytes_to_skip.saturating_sub(amt);
+    }
+
+    Ok(())
+}
+
 /// Special Read trait on top of the std Read trait
 ///
 /// This defines methods targeted on reading Fits data units
@@ -39,6 +109,13 @@ where
     fn read_data_unit(&'a mut self, header: &Header<X>, start_pos: u64) -> Self::Data
     where
         Self: Sized;
+
+    /// Apply the reading options of the [Fits](crate::Fits) to the data returned by
+    /// [read_data_unit](FitsRead::read_data_unit), e.g. the byte order of the pixels.
+    /// By default, the data is returned untouched
+    fn with_options(data: Self::Data, _options: &FitsOptions) -> Self::Data {
+        data
+    }
 }
 
 use async_trait::async_trait;
@@ -100,3 +177,38 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{discard_data_unit, skip_data_unit};
+    use crate::error::Error;
+    use std::io::{BufReader, Cursor, Read, Seek};
+
+    #[test]
+    fn skip_and_discard_data_units() {
+        let mut bytes = vec![0_u8; 2 * 2880];
+        bytes.extend(b"NEXT");
+
+        // The data unit of 3000 bytes is padded to 5760 bytes
+        let mut reader = Cursor::new(&bytes[..]);
+        reader.seek_relative(100).unwrap();
+        skip_data_unit(&mut reader, 3000, 100).unwrap();
+        assert_eq!(reader.position(), 5760);
+
+        // A small buffer forces several reads
+        let mut reader = BufReader::with_capacity(512, &bytes[..]);
+        discard_data_unit(&mut reader, 3000, 0).unwrap();
+        let mut next = String::new();
+        reader.read_to_string(&mut next).unwrap();
+        assert_eq!(next, "NEXT");
+
+        // Missing padding of the last HDU
+        let mut reader = &bytes[..4000];
+        assert!(discard_data_unit(&mut reader, 3000, 0).is_ok());
+        let mut reader = &bytes[..2000];
+        assert!(matches!(
+            discard_data_unit(&mut reader, 3000, 0),
+            Err(Error::Io(std::io::ErrorKind::UnexpectedEof))
+        ));
+    }
+}
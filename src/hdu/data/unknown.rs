@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use futures::AsyncReadExt;
+use std::io::Read;
+use std::{fmt::Debug, io::BufReader};
+
+use super::{stream::St, AsyncDataBufRead};
+
+use crate::hdu::data::FitsRead;
+use crate::hdu::header::extension::unknown::Unknown;
+use crate::hdu::header::extension::Xtension;
+use crate::hdu::header::Header;
+
+use std::io::Take;
+impl<'a, R> FitsRead<'a, Unknown> for R
+where
+    R: Read + Debug + 'a,
+{
+    /// The raw bytes of the data unit, which are not interpreted
+    type Data = BufReader<Take<&'a mut R>>;
+
+    fn read_data_unit(&'a mut self, header: &Header<Unknown>, _start_pos: u64) -> Self::Data {
+        let limit = header.get_xtension().get_num_bytes_data_block();
+
+        BufReader::new(self.take(limit))
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a, R> AsyncDataBufRead<'a, Unknown> for futures::io::BufReader<R>
+where
+    R: AsyncReadExt + 'a + std::marker::Unpin,
+{
+    /// The stream of the raw bytes of the data unit
+    type Data = St<'a, Self, u8>;
+
+    fn prepare_data_reading(
+        _ctx: &Unknown,
+        num_remaining_bytes_in_cur_hdu: &'a mut usize,
+        reader: &'a mut Self,
+    ) -> Self::Data {
+        St::new(reader, num_remaining_bytes_in_cur_hdu)
+    }
+}
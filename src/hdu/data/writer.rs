@@ -0,0 +1,140 @@
+use std::io::Write;
+
+use crate::error::Error;
+use crate::hdu::data::iter::Value;
+use crate::integrity::checksum;
+
+/// A writer encoding a data unit chunk by chunk
+///
+/// The values are converted to big endian as they are written so that an arbitrarily large data unit
+/// can be produced with a bounded amount of memory. The checksum of the data unit, i.e. the value of
+/// its `DATASUM` card, is computed along the way.
+///
+/// The writer given must be positioned just after the header of the HDU.
+#[derive(Debug)]
+pub struct DataUnitWriter<W> {
+    /// The writer
+    writer: W,
+    /// Number of bytes written
+    num_bytes: u64,
+    /// Checksum of the bytes written up to the last complete 32-bit word
+    sum: u32,
+    /// Bytes written after the last complete 32-bit word
+    pending: Vec<u8>,
+    /// Intern buffer where the big endian bytes of the values are encoded
+    buf: Vec<u8>,
+}
+
+impl<W> DataUnitWriter<W>
+where
+    W: Write,
+{
+    /// Create a new writer of a data unit
+    ///
+    /// # Params
+    /// * `writer` - a writer positioned at the beginning of the data unit
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            num_bytes: 0,
+            sum: 0,
+            pending: Vec::with_capacity(4),
+            buf: vec![],
+        }
+    }
+
+    /// Get the number of bytes of the values written so far
+    pub fn get_num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+
+    /// Encode and write values after the ones already written
+    ///
+    /// # Params
+    /// * `values` - the values in the native byte order
+    pub fn write_all<T>(&mut self, values: &[T]) -> Result<(), Error>
+    where
+        T: Value,
+    {
+        self.buf.resize(std::mem::size_of_val(values), 0);
+        T::write_be_into(values, &mut self.buf);
+        self.writer.write_all(&self.buf)?;
+        self.num_bytes += self.buf.len() as u64;
+
+        // Complete the pending 32-bit word first
+        let mut bytes = &self.buf[..];
+        if !self.pending.is_empty() {
+            let n = (4 - self.pending.len()).min(bytes.len());
+            self.pending.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+
+            if self.pending.len() == 4 {
+                self.sum = checksum(&self.pending, self.sum);
+                self.pending.clear();
+            }
+        }
+
+        let num_aligned_bytes = bytes.len() - bytes.len() % 4;
+        self.sum = checksum(&bytes[..num_aligned_bytes], self.sum);
+        self.pending.extend_from_slice(&bytes[num_aligned_bytes..]);
+
+        Ok(())
+    }
+
+    /// Write the zero bytes padding the data unit to a multiple of 2880 bytes and flush the writer
+    ///
+    /// Returns the checksum of the data unit to be written in its `DATASUM` card
+    pub fn finish(mut self) -> Result<u32, Error> {
+        // The last word is completed with zeros, as the padding bytes
+        let sum = checksum(&self.pending, self.sum);
+
+        let num_padding_bytes = self.num_bytes.next_multiple_of(2880) - self.num_bytes;
+        self.writer
+            .write_all(&vec![0; num_padding_bytes as usize])?;
+        self.writer.flush()?;
+
+        Ok(sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataUnitWriter;
+    use crate::integrity::checksum;
+
+    #[test]
+    fn write_values_chunk_by_chunk() {
+        let mut buf = vec![];
+        let mut writer = DataUnitWriter::new(&mut buf);
+        writer.write_all(&[1_i16, -2, 3]).unwrap();
+        writer.write_all(&[0x12345678_i32]).unwrap();
+        writer.write_all(&[0xab_u8]).unwrap();
+        writer.write_all(&[-1.5_f64]).unwrap();
+        assert_eq!(writer.get_num_bytes(), 19);
+        let datasum = writer.finish().unwrap();
+
+        let mut expected = vec![0x00, 0x01, 0xff, 0xfe, 0x00, 0x03];
+        expected.extend(0x12345678_i32.to_be_bytes());
+        expected.push(0xab);
+        expected.extend((-1.5_f64).to_be_bytes());
+        assert_eq!(buf.len(), 2880);
+        assert_eq!(&buf[..19], expected);
+        assert!(buf[19..].iter().all(|&b| b == 0));
+        assert_eq!(datasum, checksum(&buf, 0));
+    }
+
+    #[test]
+    fn datasum_of_bytes() {
+        let mut buf = vec![];
+        let mut writer = DataUnitWriter::new(&mut buf);
+        for v in 1..=10_u8 {
+            writer.write_all(&[v]).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), 252840460);
+
+        // An empty data unit has no padding
+        let mut buf = vec![];
+        assert_eq!(DataUnitWriter::new(&mut buf).finish().unwrap(), 0);
+        assert!(buf.is_empty());
+    }
+}
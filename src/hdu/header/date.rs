@@ -0,0 +1,163 @@
+//! Module parsing the dates of a header, e.g. the creation date of the file or the observation date
+
+use serde::Serialize;
+
+use crate::hdu::header::Header;
+
+/// A date and time as written in the `DATE` and `DATE-OBS` cards
+///
+/// The time is `00:00:00` when only the date is given.
+#[derive(Debug, PartialEq, Serialize, Clone, Copy)]
+pub struct DateTime {
+    /// The year, e.g. `2018`
+    pub year: i32,
+    /// The month from 1 to 12
+    pub month: u8,
+    /// The day of the month from 1 to 31
+    pub day: u8,
+    /// The hour from 0 to 23
+    pub hour: u8,
+    /// The minute from 0 to 59
+    pub minute: u8,
+    /// The second including its decimal part
+    pub second: f64,
+}
+
+impl<X> Header<X> {
+    /// Get the date the HDU (i.e. the FITS file) was written given by the `DATE` card
+    ///
+    /// Not to be confused with the date of the observation, see [Header::date_obs].
+    /// Returns `None` if the card is not found or is not a valid date.
+    pub fn date(&self) -> Option<DateTime> {
+        self.get_str("DATE").and_then(parse_date)
+    }
+
+    /// Get the date the observation started given by the `DATE-OBS` card
+    ///
+    /// Not to be confused with the date the file was written, see [Header::date].
+    /// Returns `None` if the card is not found or is not a valid date.
+    pub fn date_obs(&self) -> Option<DateTime> {
+        self.get_str("DATE-OBS").and_then(parse_date)
+    }
+}
+
+/// Parse a `YYYY-MM-DD[Thh:mm:ss[.s...]]` date or a `DD/MM/YY` date of the files written before 2000
+pub fn parse_date(s: &str) -> Option<DateTime> {
+    let s = s.trim();
+
+    // Deprecated format meaning 19YY
+    if let [day, month, year] = s.split('/').collect::<Vec<_>>()[..] {
+        if year.len() != 2 {
+            return None;
+        }
+        return new_date(
+            1900 + year.parse::<i32>().ok()?,
+            month.parse().ok()?,
+            day.parse().ok()?,
+            (0, 0, 0.0),
+        );
+    }
+
+    let (date, time) = s.split_once('T').unwrap_or((s, ""));
+    let [year, month, day] = date.split('-').collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    if year.len() != 4 {
+        return None;
+    }
+
+    let time = if time.is_empty() {
+        (0, 0, 0.0)
+    } else {
+        let [hour, minute, second] = time.split(':').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        let second = second.parse::<f64>().ok()?;
+        if !(0.0..61.0).contains(&second) {
+            return None;
+        }
+        (hour.parse().ok()?, minute.parse().ok()?, second)
+    };
+
+    new_date(
+        year.parse().ok()?,
+        month.parse().ok()?,
+        day.parse().ok()?,
+        time,
+    )
+}
+
+fn new_date(
+    year: i32,
+    month: u8,
+    day: u8,
+    (hour, minute, second): (u8, u8, f64),
+) -> Option<DateTime> {
+    let valid = (1..=12).contains(&month) && (1..=31).contains(&day) && hour < 24 && minute < 60;
+    valid.then_some(DateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_date, DateTime};
+    use crate::hdu::header::extension::image::Image;
+    use crate::test_utils::parse_header;
+
+    #[test]
+    fn date_formats() {
+        assert_eq!(
+            parse_date("2018-04-12T13:25:09.5"),
+            Some(DateTime {
+                year: 2018,
+                month: 4,
+                day: 12,
+                hour: 13,
+                minute: 25,
+                second: 9.5
+            })
+        );
+        assert_eq!(
+            parse_date("2018-04-12"),
+            Some(DateTime {
+                year: 2018,
+                month: 4,
+                day: 12,
+                hour: 0,
+                minute: 0,
+                second: 0.0
+            })
+        );
+        assert_eq!(parse_date("12/04/98").map(|d| d.year), Some(1998));
+        assert_eq!(parse_date("2018-13-12"), None);
+        assert_eq!(parse_date("18-04-12"), None);
+        assert_eq!(parse_date("2018-04-12T13:25"), None);
+    }
+
+    #[test]
+    fn creation_and_observation_dates() {
+        let header = parse_header::<Image>(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"ORIGIN  = 'ESO-PARANAL'        / European Southern Observatory                  ",
+            b"DATE    = '2021-03-01T10:00:00' / file creation date                            ",
+            b"DATE-OBS= '2021-02-28T23:59:59.999' / observation start                         ",
+            b"END                                                                             ",
+        ]);
+
+        assert_eq!(header.origin(), Some("ESO-PARANAL"));
+        assert_eq!(
+            header.date().map(|d| (d.month, d.day, d.hour)),
+            Some((3, 1, 10))
+        );
+        let date_obs = header.date_obs().unwrap();
+        assert_eq!((date_obs.day, date_obs.second), (28, 59.999));
+    }
+}
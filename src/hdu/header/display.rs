@@ -0,0 +1,319 @@
+//! Module parsing the display formats of the table fields given by the `TDISPn` cards
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::hdu::header::Header;
+
+/// The kind of conversion of a display format, following the Fortran edit descriptors
+#[derive(Debug, PartialEq, Eq, Serialize, Clone, Copy)]
+pub enum DisplayCode {
+    /// `Aw`: characters
+    Character,
+    /// `Lw`: logical values, i.e. `T` or `F`
+    Logical,
+    /// `Iw.m`: integers
+    Integer,
+    /// `Bw.m`: integers in binary
+    Binary,
+    /// `Ow.m`: integers in octal
+    Octal,
+    /// `Zw.m`: integers in hexadecimal
+    Hexadecimal,
+    /// `Fw.d`: fixed point numbers
+    Fixed,
+    /// `Ew.dEe`: numbers with an exponent
+    Exponential,
+    /// `ENw.d`: numbers in engineering notation, the exponent being a multiple of 3
+    Engineering,
+    /// `ESw.d`: numbers in scientific notation, the mantissa being between 1 and 10
+    Scientific,
+    /// `Gw.dEe`: numbers in fixed point or with an exponent depending on their magnitude
+    General,
+    /// `Dw.dEe`: double precision numbers with an exponent
+    Double,
+}
+
+/// A display format given by a `TDISPn` card, e.g. `F8.3` or `I6`
+///
+/// Numbers can be formatted with [DisplayFormat::format].
+#[derive(Debug, PartialEq, Eq, Serialize, Clone, Copy)]
+pub struct DisplayFormat {
+    /// The kind of conversion
+    pub code: DisplayCode,
+    /// The width `w` of the displayed value in characters
+    pub width: usize,
+    /// The minimum number of digits `m` of the integer codes or the number of digits `d`
+    /// after the decimal point of the real codes
+    pub precision: Option<usize>,
+    /// The number of digits `e` of the exponent
+    pub exponent: Option<usize>,
+}
+
+impl DisplayFormat {
+    /// Format a number, right-justified to the width of the format
+    ///
+    /// The integer codes truncate the value and pad it with zeros to `m` digits. The `E` and `D`
+    /// codes give a mantissa between 1 and 10 like `ES`, e.g. `1.500E+03` for `E10.3`, instead of the
+    /// Fortran one between 0.1 and 1. A value wider than the format is not replaced by asterisks.
+    /// The character and logical codes do not apply to numbers that are written as they are.
+    ///
+    /// # Params
+    /// * `value` - the number to format
+    pub fn format(&self, value: f64) -> String {
+        let digits = self.precision.unwrap_or(0);
+        let exp_digits = self.exponent.unwrap_or(2);
+        let s = match self.code {
+            DisplayCode::Integer => {
+                let int = value as i64;
+                let sign = if int < 0 { "-" } else { "" };
+                format!("{sign}{:0digits$}", int.unsigned_abs())
+            }
+            DisplayCode::Binary => format!("{:0digits$b}", value as i64),
+            DisplayCode::Octal => format!("{:0digits$o}", value as i64),
+            DisplayCode::Hexadecimal => format!("{:0digits$X}", value as i64),
+            DisplayCode::Fixed => format!("{value:.digits$}"),
+            DisplayCode::Exponential | DisplayCode::Scientific | DisplayCode::Double => {
+                format_exponential(value, digits, exp_digits, 1)
+            }
+            DisplayCode::Engineering => format_exponential(value, digits, exp_digits, 3),
+            DisplayCode::General => {
+                // Fixed point with `d` significant digits if the exponent fits
+                let magnitude = value.abs();
+                if magnitude >= 0.1 && magnitude < 10_f64.powi(digits as i32) {
+                    let int_digits = (magnitude.log10().floor() as i64 + 1).max(0) as usize;
+                    format!("{value:.*}", digits.saturating_sub(int_digits))
+                } else {
+                    format_exponential(value, digits.saturating_sub(1), exp_digits, 1)
+                }
+            }
+            DisplayCode::Character | DisplayCode::Logical => value.to_string(),
+        };
+
+        format!("{s:>width$}", width = self.width)
+    }
+
+    /// Format an integer, right-justified to the width of the format
+    ///
+    /// Contrary to [DisplayFormat::format], the integer codes format the value exactly, even beyond
+    /// the integers that can be represented by a `f64`, e.g. 64-bit identifiers. The other codes format
+    /// the value converted to `f64`.
+    ///
+    /// # Params
+    /// * `value` - the integer to format
+    pub fn format_integer(&self, value: i64) -> String {
+        let digits = self.precision.unwrap_or(0);
+        let s = match self.code {
+            DisplayCode::Integer => {
+                let sign = if value < 0 { "-" } else { "" };
+                format!("{sign}{:0digits$}", value.unsigned_abs())
+            }
+            DisplayCode::Binary => format!("{value:0digits$b}"),
+            DisplayCode::Octal => format!("{value:0digits$o}"),
+            DisplayCode::Hexadecimal => format!("{value:0digits$X}"),
+            _ => return self.format(value as f64),
+        };
+
+        format!("{s:>width$}", width = self.width)
+    }
+}
+
+/// Format a number with an exponent multiple of `step`, a signed exponent of `exp_digits` digits
+/// and `digits` digits after the decimal point of the mantissa
+fn format_exponential(value: f64, digits: usize, exp_digits: usize, step: i32) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+
+    let mut exp = if value == 0.0 {
+        0
+    } else {
+        value.abs().log10().floor() as i32
+    };
+    exp -= exp.rem_euclid(step);
+    let mut mantissa = format!("{:.digits$}", value / 10_f64.powi(exp));
+    // Rounding may give a mantissa out of range, e.g. 9.9996 giving 10.000 with 3 digits
+    if mantissa
+        .parse::<f64>()
+        .is_ok_and(|m| m.abs() >= 10_f64.powi(step))
+    {
+        exp += step;
+        mantissa = format!("{:.digits$}", value / 10_f64.powi(exp));
+    }
+
+    let sign = if exp < 0 { '-' } else { '+' };
+    format!("{mantissa}E{sign}{:0exp_digits$}", exp.unsigned_abs())
+}
+
+impl<X> Header<X> {
+    /// Get the display format of a table field given by its `TDISPn` card, e.g. `F8.3`
+    ///
+    /// Returns `None` if the card is not found.
+    ///
+    /// # Params
+    /// * `col` - the index of the field starting from 0
+    pub fn get_tdisp(&self, col: usize) -> Result<Option<DisplayFormat>, Error> {
+        let key = format!("TDISP{}", col + 1);
+        let Some(tdisp) = self.get_str(&key) else {
+            return Ok(None);
+        };
+
+        parse_display_format(tdisp)
+            .map(Some)
+            .ok_or_else(|| Error::DynamicError(format!("{key} = '{tdisp}' is not valid")))
+    }
+}
+
+/// Parse a `TDISPn` display format, e.g. `F8.3`, `I6` or `E15.7E3`
+pub fn parse_display_format(s: &str) -> Option<DisplayFormat> {
+    let s = s.trim();
+    let num_letters = s.bytes().take_while(|b| b.is_ascii_alphabetic()).count();
+    let (code, rest) = s.split_at(num_letters);
+    let code = match code {
+        "A" => DisplayCode::Character,
+        "L" => DisplayCode::Logical,
+        "I" => DisplayCode::Integer,
+        "B" => DisplayCode::Binary,
+        "O" => DisplayCode::Octal,
+        "Z" => DisplayCode::Hexadecimal,
+        "F" => DisplayCode::Fixed,
+        "E" => DisplayCode::Exponential,
+        "EN" => DisplayCode::Engineering,
+        "ES" => DisplayCode::Scientific,
+        "G" => DisplayCode::General,
+        "D" => DisplayCode::Double,
+        _ => return None,
+    };
+
+    let (rest, exponent) = match rest.split_once('E') {
+        Some((rest, e)) => (rest, Some(parse_digits(e)?)),
+        None => (rest, None),
+    };
+    let (width, precision) = match rest.split_once('.') {
+        Some((w, p)) => (parse_digits(w)?, Some(parse_digits(p)?)),
+        None => (parse_digits(rest)?, None),
+    };
+
+    let valid = match code {
+        DisplayCode::Character | DisplayCode::Logical => precision.is_none() && exponent.is_none(),
+        DisplayCode::Integer
+        | DisplayCode::Binary
+        | DisplayCode::Octal
+        | DisplayCode::Hexadecimal => exponent.is_none(),
+        DisplayCode::Fixed | DisplayCode::Engineering | DisplayCode::Scientific => {
+            precision.is_some() && exponent.is_none()
+        }
+        DisplayCode::Exponential | DisplayCode::General | DisplayCode::Double => {
+            precision.is_some()
+        }
+    };
+
+    (valid && width > 0).then_some(DisplayFormat {
+        code,
+        width,
+        precision,
+        exponent,
+    })
+}
+
+fn parse_digits(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        None
+    } else {
+        s.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_display_format, DisplayCode, DisplayFormat};
+    use crate::hdu::header::extension::bintable::BinTable;
+    use crate::test_utils::{bintable_cards, parse_header};
+
+    #[test]
+    fn display_formats() {
+        assert_eq!(
+            parse_display_format("E15.7E3"),
+            Some(DisplayFormat {
+                code: DisplayCode::Exponential,
+                width: 15,
+                precision: Some(7),
+                exponent: Some(3),
+            })
+        );
+        assert_eq!(
+            parse_display_format(" ES12.4 ").map(|f| (f.code, f.width)),
+            Some((DisplayCode::Scientific, 12))
+        );
+        assert_eq!(
+            parse_display_format("Z8.8").map(|f| f.code),
+            Some(DisplayCode::Hexadecimal)
+        );
+        assert_eq!(parse_display_format("F8"), None);
+        assert_eq!(parse_display_format("A10.2"), None);
+        assert_eq!(parse_display_format("I0"), None);
+        assert_eq!(parse_display_format("X4"), None);
+        assert_eq!(parse_display_format("F8.-3"), None);
+    }
+
+    #[test]
+    fn format_numbers() {
+        let format = |tdisp: &str, value: f64| parse_display_format(tdisp).unwrap().format(value);
+
+        assert_eq!(format("I6", 42.7), "    42");
+        assert_eq!(format("I6.4", -42.0), " -0042");
+        assert_eq!(format("Z4", 255.0), "  FF");
+        assert_eq!(format("B8.8", 5.0), "00000101");
+        assert_eq!(format("F8.3", 1.23456), "   1.235");
+        assert_eq!(format("E10.3", 1500.0), " 1.500E+03");
+        assert_eq!(format("E10.3E3", -0.000125), "-1.250E-004");
+        assert_eq!(format("E9.3", 9.9996), "1.000E+01");
+        assert_eq!(format("EN12.3", 12345.0), "  12.345E+03");
+        assert_eq!(format("G10.4", 1.23456), "     1.235");
+        assert_eq!(format("G10.4", 123456.0), " 1.235E+05");
+        // Too wide values are kept
+        assert_eq!(format("F4.2", 123.0), "123.00");
+
+        // Integers beyond 2^53 are formatted exactly by the integer codes
+        let format =
+            |tdisp: &str, value: i64| parse_display_format(tdisp).unwrap().format_integer(value);
+        assert_eq!(format("I20", 9007199254740993), "    9007199254740993");
+        assert_eq!(format("I6.4", -42), " -0042");
+        assert_eq!(format("Z16", i64::MAX), "7FFFFFFFFFFFFFFF");
+        assert_eq!(format("F8.1", 42), "    42.0");
+    }
+
+    #[test]
+    fn display_formats_of_fields() {
+        let header = parse_header::<BinTable>(&bintable_cards(
+            26,
+            0,
+            0,
+            &[("", "D"), ("", "J"), ("", "10A"), ("", "J")],
+            &[
+                "TDISP1  = 'F8.3    '",
+                "TDISP2  = 'I6      '",
+                "TDISP3  = 'A10     '",
+            ],
+        ));
+
+        assert_eq!(
+            header.get_tdisp(0).unwrap(),
+            Some(DisplayFormat {
+                code: DisplayCode::Fixed,
+                width: 8,
+                precision: Some(3),
+                exponent: None,
+            })
+        );
+        assert_eq!(
+            header.get_tdisp(1).unwrap().map(|f| (f.code, f.width)),
+            Some((DisplayCode::Integer, 6))
+        );
+        assert_eq!(
+            header.get_tdisp(2).unwrap().map(|f| (f.code, f.width)),
+            Some((DisplayCode::Character, 10))
+        );
+        assert_eq!(header.get_tdisp(3).unwrap(), None);
+    }
+}
@@ -184,6 +184,82 @@ pub enum TFormAsciiTable {
     DFloatingPointExp { w: usize, d: usize },
 }
 
+impl TFormAsciiTable {
+    /// Get the width in characters of the field
+    pub fn get_width(&self) -> usize {
+        match *self {
+            TFormAsciiTable::Character { w }
+            | TFormAsciiTable::DecimalInteger { w }
+            | TFormAsciiTable::FloatingPointFixed { w, .. }
+            | TFormAsciiTable::EFloatingPointExp { w, .. }
+            | TFormAsciiTable::DFloatingPointExp { w, .. } => w,
+        }
+    }
+
+    /// Decode a `Fw.d`, `Ew.d` or `Dw.d` field of an ASCII table row
+    ///
+    /// The field is read as a Fortran formatted input, cf. FITSv4, section 7.2.5:
+    /// * the exponent can be introduced by `E`, `D`, `e` or `d`, or only by its sign
+    /// * if no decimal point is given, it is implied by the format, i.e. the `d` last
+    ///   digits of the mantissa are the fractional part
+    ///
+    /// Only the first `w` bytes of `field` are read.
+    /// Returns `None` if the field is blank.
+    ///
+    /// # Params
+    /// * `field` - the bytes of the field in the row
+    pub fn parse_float(&self, field: &[u8]) -> Result<Option<f64>, Error> {
+        let d = match *self {
+            TFormAsciiTable::FloatingPointFixed { d, .. }
+            | TFormAsciiTable::EFloatingPointExp { d, .. }
+            | TFormAsciiTable::DFloatingPointExp { d, .. } => d,
+            _ => {
+                return Err(Error::StaticError(
+                    "Only F, E and D fields can be decoded as floating point values",
+                ))
+            }
+        };
+
+        let field = &field[..field.len().min(self.get_width())];
+        let field = std::str::from_utf8(field)?.trim();
+        if field.is_empty() {
+            return Ok(None);
+        }
+
+        // Split the mantissa and the exponent. A sign not at the start also begins the exponent
+        let exp_start = field
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| matches!(c, 'E' | 'D' | 'e' | 'd' | '+' | '-'))
+            .map(|(i, _)| i);
+        let (mantissa, exponent) = match exp_start {
+            Some(i) => {
+                let exp = field[i..].trim_start_matches(['E', 'D', 'e', 'd']);
+                (&field[..i], exp.trim_start())
+            }
+            None => (field, ""),
+        };
+
+        let invalid = || Error::DynamicError(format!("Invalid floating point field `{field}`"));
+
+        let mut exponent = if exponent.is_empty() {
+            0
+        } else {
+            exponent.parse::<i32>().map_err(|_| invalid())?
+        };
+        if !mantissa.contains('.') {
+            exponent -= d as i32;
+        }
+
+        // Parse the whole number at once not to lose precision
+        let value = format!("{}e{exponent}", mantissa.trim_end())
+            .parse::<f64>()
+            .map_err(|_| invalid())?;
+
+        Ok(Some(value))
+    }
+}
+
 impl<'de> Deserialize<'de> for TFormAsciiTable {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -472,4 +548,30 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn parse_float_fields() {
+        let e = TFormAsciiTable::EFloatingPointExp { w: 10, d: 3 };
+        assert_eq!(e.parse_float(b"   1.5D+02"), Ok(Some(150.0)));
+        assert_eq!(e.parse_float(b"  1.5e-2  "), Ok(Some(0.015)));
+        assert_eq!(e.parse_float(b" -2.25E+01"), Ok(Some(-22.5)));
+        assert_eq!(e.parse_float(b"    2.5+01"), Ok(Some(25.0)));
+        assert_eq!(e.parse_float(b"          "), Ok(None));
+        assert!(e.parse_float(b"  1.5X+02 ").is_err());
+
+        let d = TFormAsciiTable::DFloatingPointExp { w: 8, d: 2 };
+        assert_eq!(d.parse_float(b"-1.25d0 "), Ok(Some(-1.25)));
+
+        // Implied decimal point
+        let f = TFormAsciiTable::FloatingPointFixed { w: 6, d: 2 };
+        assert_eq!(f.parse_float(b"  1234"), Ok(Some(12.34)));
+        assert_eq!(f.parse_float(b" -1234"), Ok(Some(-12.34)));
+        assert_eq!(f.parse_float(b" 12.5 "), Ok(Some(12.5)));
+        // Only the width of the field is read
+        assert_eq!(f.parse_float(b" 12.5 999"), Ok(Some(12.5)));
+        assert_eq!(f.parse_float(b"      "), Ok(None));
+
+        let i = TFormAsciiTable::DecimalInteger { w: 4 };
+        assert!(i.parse_float(b"  12").is_err());
+    }
 }
@@ -50,6 +50,8 @@ pub struct BinTable {
     /// values should not be case sensitive (e.g., ’TIME’ and ’Time’
     /// should be interpreted as the same name).
     pub(crate) ttypes: Vec<Option<String>>,
+    /// The byte offset of each field inside a row, computed once from the TFORMn keywords
+    col_offsets: Vec<usize>,
 
     /// The value field shall contain the number of
     /// bytes that follow the table in the supplemental data area called
@@ -84,10 +86,149 @@ impl BinTable {
         self.naxis2 as usize
     }
 
+    /// Get the byte offset of the heap from the start of the data unit given by the "THEAP" card
+    ///
+    /// Defaults to the end of the main data table, i.e. `NAXIS1 * NAXIS2`, if the card is not found.
+    pub fn get_theap(&self) -> usize {
+        self.theap
+    }
+
+    /// Get the tile compressed image properties if the table follows the tiled image compression convention
+    ///
+    /// The underlying table can still be read without decompressing the image, e.g. to
+    /// inspect the `COMPRESSED_DATA`, `ZSCALE` or `ZZERO` columns, with [TableData](crate::TableData).
+    pub fn get_tile_compressed_image(&self) -> Option<&TileCompressedImage> {
+        self.z_image.as_ref()
+    }
+
     /// Returns the index of the field by name
     pub fn find_field_by_ttype(&self, ttype: &str) -> Option<usize> {
         find_field_by_ttype(&self.ttypes, ttype)
     }
+
+    /// Returns the index of the field whose `TTYPEn` name matches, e.g. `FLUX` matching `'Flux    '`
+    ///
+    /// The names are compared case-insensitively once trimmed, as recommended by the standard.
+    /// See [BinTable::column_exact] for a case-sensitive lookup.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.ttypes.iter().position(|ttype| {
+            ttype
+                .as_deref()
+                .is_some_and(|ttype| ttype.trim().eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Returns the index of the field whose `TTYPEn` name is exactly `name`
+    pub fn column_exact(&self, name: &str) -> Option<usize> {
+        self.find_field_by_ttype(name)
+    }
+
+    /// Get the byte offset of each field inside a row of `NAXIS1` bytes
+    pub fn column_offsets(&self) -> &[usize] {
+        &self.col_offsets
+    }
+
+    /// Get the repeat count `r` of a field given by its `TFORMn` card, e.g. `3` for `3E`
+    ///
+    /// Each row of the field stores `r` values, e.g. a light curve per row. A variable length array
+    /// field (`P` or `Q`) stores one array descriptor per row and gives `1`.
+    /// Returns `None` if the field does not exist.
+    ///
+    /// # Params
+    /// * `col` - the index of the field starting from 0
+    pub fn get_repeat_count(&self, col: usize) -> Option<usize> {
+        self.tforms.get(col).map(TFormType::repeat_count)
+    }
+
+    /// Get the context of the table restricted to some fields given by their `TTYPEn` names
+    ///
+    /// The names are compared as in [BinTable::column]. The fields are kept in the order of the table
+    /// with their byte offsets inside the rows of `NAXIS1` bytes. The fields are then indexed from 0
+    /// in the restricted context, whereas [TableData::select](crate::TableData::select) reads the
+    /// selected fields of a table while keeping their indices in the whole table.
+    ///
+    /// The tile compressed image is only kept if its `COMPRESSED_DATA` field is selected, with the
+    /// index of that field in the restricted context.
+    ///
+    /// An error is returned if a name is not found or if no name is given.
+    ///
+    /// # Params
+    /// * `names` - the names of the fields to keep
+    pub fn select(&self, names: &[&str]) -> Result<BinTable, Error> {
+        let cols = self.columns(names)?;
+
+        let z_image = self.z_image.as_ref().and_then(|z_image| {
+            let data_compressed_idx = cols
+                .iter()
+                .position(|&col| col == z_image.data_compressed_idx)?;
+
+            Some(TileCompressedImage {
+                data_compressed_idx,
+                ..z_image.clone()
+            })
+        });
+
+        Ok(BinTable {
+            tfields: cols.len(),
+            tforms: cols.iter().map(|&col| self.tforms[col]).collect(),
+            ttypes: cols.iter().map(|&col| self.ttypes[col].clone()).collect(),
+            col_offsets: cols.iter().map(|&col| self.col_offsets[col]).collect(),
+            z_image,
+            ..self.clone()
+        })
+    }
+
+    /// Get the sorted indices of the fields given by their `TTYPEn` names, compared as in [BinTable::column]
+    ///
+    /// An error is returned if a name is not found or if no name is given.
+    pub(crate) fn columns(&self, names: &[&str]) -> Result<Vec<usize>, Error> {
+        if names.is_empty() {
+            return Err(Error::StaticError("At least one field must be selected"));
+        }
+
+        let mut cols = names
+            .iter()
+            .map(|name| {
+                self.column(name).ok_or_else(|| {
+                    Error::DynamicError(format!("No field named {name} has been found"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        cols.sort_unstable();
+        cols.dedup();
+
+        Ok(cols)
+    }
+
+    /// Get the context of a sub-table made of `count` rows starting from the row `start`
+    ///
+    /// The sub-table is a view over the same bytes: its main data table starts `start * NAXIS1`
+    /// bytes later and `THEAP` and `PCOUNT` are shifted accordingly so that the descriptors of the
+    /// variable length array fields still point into the heap of the whole table.
+    /// See [TableData::row_slice](crate::TableData::row_slice) to read the rows of the sub-table.
+    ///
+    /// # Params
+    /// * `start` - the index of the first row of the sub-table
+    /// * `count` - the number of rows of the sub-table
+    pub fn row_slice(&self, start: usize, count: usize) -> Result<BinTable, Error> {
+        let num_rows = self.naxis2 as usize;
+        if start.checked_add(count).is_none_or(|end| end > num_rows) {
+            return Err(Error::DynamicError(format!(
+                "Rows {start}..{} are out of the table of {num_rows} rows (NAXIS2)",
+                start.saturating_add(count)
+            )));
+        }
+
+        let num_skipped_bytes = start as u64 * self.naxis1;
+        let num_trailing_bytes = (num_rows - start - count) as u64 * self.naxis1;
+        Ok(BinTable {
+            naxis2: count as u64,
+            theap: self.theap - num_skipped_bytes as usize,
+            pcount: self.pcount + num_trailing_bytes,
+            ..self.clone()
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize, Clone)]
@@ -135,6 +276,28 @@ pub struct TileCompressedImage {
     pub(crate) data_compressed_idx: usize,
 }
 
+impl TileCompressedImage {
+    /// Get the bitpix of the uncompressed image given by the "ZBITPIX" card
+    pub fn get_z_bitpix(&self) -> Bitpix {
+        self.z_bitpix
+    }
+
+    /// Get the sizes of axis of the uncompressed image given by the "ZNAXISn" cards
+    pub fn get_z_naxis(&self) -> &[usize] {
+        &self.z_naxisn
+    }
+
+    /// Get the sizes of axis of the tiles given by the "ZTILEn" cards
+    pub fn get_z_tile(&self) -> &[usize] {
+        &self.z_tilen
+    }
+
+    /// Get the index of the column storing the compressed tiles
+    pub fn get_data_compressed_idx(&self) -> usize {
+        self.data_compressed_idx
+    }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub(crate) enum ZQuantiz {
     #[serde(rename = "NO_DITHER")]
@@ -212,7 +375,11 @@ impl Xtension for BinTable {
         }
 
         // FIELDS
-        let tfields = values.check_for_tfields()?;
+        let declared_tfields = values.check_for_tfields()?;
+        let num_tforms = values.num_tforms();
+        if num_tforms != declared_tfields {
+            warn!("TFIELDS = {declared_tfields} but {num_tforms} TFORMn cards are found. The TFORMn cards found are parsed");
+        }
 
         // Tile compressed image parameters
         let z_cmp_type = if let Some(Value::String {
@@ -339,7 +506,7 @@ impl Xtension for BinTable {
         let z_dither_0 = values.get_parsed("ZDITHER0").ok();
 
         // TFORMS & TTYPES
-        let (tforms, ttypes): (Vec<_>, Vec<_>) = (1..=tfields)
+        let (tforms, ttypes): (Vec<_>, Vec<_>) = (1..=declared_tfields.max(num_tforms))
             .filter_map(|idx_field| {
                 // discard the tform if it was not found and raise a warning
                 let tform_kw = format!("TFORM{idx_field}");
@@ -452,6 +619,12 @@ impl Xtension for BinTable {
             })
             .unzip();
 
+        let tfields = if num_tforms == declared_tfields {
+            declared_tfields
+        } else {
+            tforms.len()
+        };
+
         let data_compressed_idx = find_field_by_ttype(&ttypes, "COMPRESSED_DATA")
             // Find for a GZIP_DATA_COMPRESSED named field
             .or(find_field_by_ttype(&ttypes, "GZIP_COMPRESSED_DATA"));
@@ -498,11 +671,26 @@ impl Xtension for BinTable {
             None
         };
 
-        // update the value of theap if found
-        let theap = if let Ok(value) = values.get_parsed::<usize>("THEAP") {
-            value
-        } else {
-            (naxis1 as usize) * (naxis2 as usize)
+        // The heap follows the main data table by default. If given, THEAP must locate the heap
+        // after the main table and inside the supplemental data area, i.e. between NAXIS1 * NAXIS2
+        // and NAXIS1 * NAXIS2 + PCOUNT bytes from the start of the data unit.
+        let main_table_num_bytes = (naxis1 as usize) * (naxis2 as usize);
+        let theap = match values.get_parsed::<Option<usize>>("THEAP") {
+            Ok(Some(theap))
+                if (main_table_num_bytes..=main_table_num_bytes + pcount as usize)
+                    .contains(&theap) =>
+            {
+                theap
+            }
+            Ok(None) => main_table_num_bytes,
+            Ok(Some(theap)) => {
+                warn!("THEAP = {theap} does not locate the heap after the main table and inside the data unit. The heap is considered to immediately follow the main table.");
+                main_table_num_bytes
+            }
+            Err(err) => {
+                warn!("THEAP is not valid. The heap is considered to immediately follow the main table: {err}");
+                main_table_num_bytes
+            }
         };
 
         let num_bits_per_row = tforms
@@ -515,6 +703,15 @@ impl Xtension for BinTable {
             return Err(Error::StaticError("BinTable NAXIS1 and TFORMS does not give the same amount of bytes the table should have per row."));
         }
 
+        let col_offsets = tforms
+            .iter()
+            .scan(0, |offset, tform| {
+                let col_offset = *offset;
+                *offset += tform.num_bytes_field();
+                Some(col_offset)
+            })
+            .collect();
+
         Ok(BinTable {
             bitpix,
             naxis1,
@@ -522,6 +719,7 @@ impl Xtension for BinTable {
             tfields,
             tforms,
             ttypes,
+            col_offsets,
             pcount,
             gcount,
             theap,
@@ -762,6 +960,23 @@ pub(crate) enum ArrayDescriptorTy {
 }
 */
 impl TFormType {
+    pub(crate) fn repeat_count(&self) -> usize {
+        match self {
+            TFormType::L { repeat_count }
+            | TFormType::X { repeat_count }
+            | TFormType::B { repeat_count }
+            | TFormType::I { repeat_count }
+            | TFormType::J { repeat_count }
+            | TFormType::K { repeat_count }
+            | TFormType::A { repeat_count }
+            | TFormType::E { repeat_count }
+            | TFormType::D { repeat_count }
+            | TFormType::C { repeat_count }
+            | TFormType::M { repeat_count } => *repeat_count,
+            TFormType::P { .. } | TFormType::Q { .. } => 1,
+        }
+    }
+
     pub(crate) fn num_bits_field(&self) -> usize {
         match self {
             TFormType::L { repeat_count } => repeat_count * L::BITS_SIZE, // Logical
@@ -787,10 +1002,11 @@ impl TFormType {
 
 #[cfg(test)]
 mod tests {
-    use super::{BinTable, TFormType};
+    use super::{BinTable, TFormType, Xtension};
+    use crate::test_utils::{bintable_cards, first_bintable, mock_extension};
     use crate::{
         hdu::{header::Bitpix, HDU},
-        FITSFile,
+        DataValue, FITSFile, Fits, NullPolicy,
     };
 
     fn compare_bintable_ext(filename: &str, bin_table: BinTable) {
@@ -849,6 +1065,7 @@ mod tests {
                     Some("QUALITY".to_owned()),
                     Some("FLUX".to_owned()),
                 ],
+                col_offsets: vec![0, 5, 7, 11, 15, 2575, 5135, 7695, 8975],
                 theap: 11535,
                 // Should be 0
                 pcount: 0,
@@ -858,4 +1075,277 @@ mod tests {
             },
         );
     }
+
+    fn mock_bintable_with_heap(theap: Option<&str>) -> Vec<u8> {
+        let cards = bintable_cards(8, 1, 16, &[("", "PJ(2)")], theap.as_slice());
+
+        // The array descriptor: 2 elements starting at the beginning of the heap
+        let mut du = vec![0_u8; 24];
+        du[..4].copy_from_slice(&2_i32.to_be_bytes());
+        du[4..8].copy_from_slice(&0_i32.to_be_bytes());
+        // A gap of 8 bytes followed by the heap
+        du[8..16].copy_from_slice(&[0xff; 8]);
+        du[16..20].copy_from_slice(&7_i32.to_be_bytes());
+        du[20..24].copy_from_slice(&(-3_i32).to_be_bytes());
+
+        mock_extension(&cards, &du)
+    }
+
+    fn read_heap_array(buf: &[u8]) -> (usize, Vec<i32>) {
+        let (mut hdu_list, hdu) = first_bintable(buf);
+        let theap = hdu.get_header().get_xtension().get_theap();
+
+        let values = hdu_list
+            .get_data(&hdu)
+            .table_data()
+            .map(|value| match value {
+                DataValue::Integer { value, .. } => value,
+                _ => panic!("expected integers"),
+            })
+            .collect();
+        (theap, values)
+    }
+
+    #[test]
+    fn heap_located_by_theap() {
+        let buf = mock_bintable_with_heap(Some("THEAP   =                   16"));
+        assert_eq!(read_heap_array(&buf), (16, vec![7, -3]));
+    }
+
+    #[test]
+    fn heap_default_location() {
+        let buf = mock_bintable_with_heap(None);
+        let (theap, values) = read_heap_array(&buf);
+        assert_eq!(theap, 8);
+        assert_eq!(values, [-1, -1]);
+
+        // A heap located outside of the data unit is ignored
+        let buf = mock_bintable_with_heap(Some("THEAP   =                  100"));
+        assert_eq!(read_heap_array(&buf).0, 8);
+    }
+
+    #[test]
+    fn column_lookup_by_name() {
+        let cards = bintable_cards(8, 0, 0, &[("Flux", "1E"), (" ra_deg ", "1E")], &[]);
+        let data = mock_extension(&cards, &[]);
+
+        let (_, hdu) = first_bintable(&data);
+        let xtension = hdu.get_header().get_xtension();
+
+        assert_eq!(xtension.column("FLUX"), Some(0));
+        assert_eq!(xtension.column(" flux "), Some(0));
+        assert_eq!(xtension.column("RA_DEG"), Some(1));
+        assert_eq!(xtension.column("DEC"), None);
+
+        assert_eq!(xtension.column_exact("Flux"), Some(0));
+        assert_eq!(xtension.column_exact("FLUX"), None);
+    }
+
+    #[test]
+    fn vector_field_with_repeat_count() {
+        let cards = bintable_cards(16, 2, 0, &[("FLUX", "3E"), ("ID", "1J")], &[]);
+        let mut du = vec![0_u8; 2880];
+        for (row, (fluxes, id)) in [([1.5_f32, 2.5, 3.5], 7_i32), ([-1.0, 0.0, 4.25], 8)]
+            .iter()
+            .enumerate()
+        {
+            for (i, flux) in fluxes.iter().enumerate() {
+                du[row * 16 + i * 4..row * 16 + (i + 1) * 4].copy_from_slice(&flux.to_be_bytes());
+            }
+            du[row * 16 + 12..row * 16 + 16].copy_from_slice(&id.to_be_bytes());
+        }
+        let data = mock_extension(&cards, &du);
+
+        let (mut hdu_list, hdu) = first_bintable(&data);
+        let xtension = hdu.get_header().get_xtension();
+        assert_eq!(xtension.get_repeat_count(0), Some(3));
+        assert_eq!(xtension.get_repeat_count(1), Some(1));
+        assert_eq!(xtension.get_repeat_count(2), None);
+        assert_eq!(xtension.column_offsets(), [0, 12]);
+
+        let values = hdu_list
+            .get_data(&hdu)
+            .table_data()
+            .map(|value| match value {
+                DataValue::Float { value, idx, .. } => (value as f64, idx),
+                DataValue::Integer { value, idx, .. } => (value as f64, idx),
+                _ => panic!("expected floats and integers"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [
+                (1.5, 0),
+                (2.5, 1),
+                (3.5, 2),
+                (7.0, 0),
+                (-1.0, 0),
+                (0.0, 1),
+                (4.25, 2),
+                (8.0, 0)
+            ]
+        );
+
+        // The values of the field split into rows
+        let (mut hdu_list, hdu) = first_bintable(&data);
+        let fluxes = hdu_list
+            .column_as_vec::<f32>(&hdu, 0, NullPolicy::Error)
+            .unwrap();
+        let rows = fluxes.chunks(3).collect::<Vec<_>>();
+        assert_eq!(rows, [[1.5, 2.5, 3.5], [-1.0, 0.0, 4.25]]);
+    }
+
+    #[test]
+    fn select_columns() {
+        let cards = bintable_cards(
+            18,
+            2,
+            0,
+            &[("ID", "1J"), ("NAME", "6A"), ("RA", "1E"), ("DEC", "1E")],
+            &[],
+        );
+        let mut du = vec![0_u8; 2880];
+        for (row, (id, name, ra, dec)) in [
+            (1_i32, b"Vega  ", 279.23_f32, 38.78_f32),
+            (2, b"Deneb ", 310.36, 45.28),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let row = &mut du[row * 18..(row + 1) * 18];
+            row[..4].copy_from_slice(&id.to_be_bytes());
+            row[4..10].copy_from_slice(*name);
+            row[10..14].copy_from_slice(&ra.to_be_bytes());
+            row[14..].copy_from_slice(&dec.to_be_bytes());
+        }
+        let data = mock_extension(&cards, &du);
+
+        let (mut hdu_list, hdu) = first_bintable(&data);
+        let xtension = hdu.get_header().get_xtension();
+
+        // The fields are kept in the order of the table with their offsets
+        let selected = xtension.select(&["dec", "ID"]).unwrap();
+        assert_eq!(selected.get_num_cols(), 2);
+        assert_eq!(selected.column_offsets(), [0, 14]);
+        assert_eq!(selected.column("DEC"), Some(1));
+        assert_eq!(selected.column("RA"), None);
+        assert!(xtension.select(&["ID", "PARALLAX"]).is_err());
+        assert!(xtension.select(&[]).is_err());
+
+        assert!(hdu_list
+            .get_data(&hdu)
+            .table_data()
+            .select(&["ID", "PARALLAX"])
+            .is_err());
+
+        let data = hdu_list
+            .get_data(&hdu)
+            .table_data()
+            .select(&["DEC", "id", "ID"])
+            .unwrap();
+        // The fields keep their index in the whole table
+        assert_eq!(data.ctx.column("DEC"), xtension.column("DEC"));
+        let rows = data
+            .row_iter()
+            .map(|row| match &row[..] {
+                [DataValue::Integer { value: id, .. }, DataValue::Float { value: dec, .. }] => {
+                    (*id, *dec)
+                }
+                _ => panic!("expected an integer and a float"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [(1, 38.78), (2, 45.28)]);
+    }
+
+    #[test]
+    fn row_slice() {
+        let cards = bintable_cards(12, 120, 480, &[("ID", "1J"), ("COUNTS", "1PJ(1)")], &[]);
+        // Each row stores its index and a variable length array of one element in the heap
+        let mut du = vec![0_u8; 2880];
+        for row in 0..120 {
+            du[row * 12..row * 12 + 4].copy_from_slice(&(row as i32).to_be_bytes());
+            du[row * 12 + 4..row * 12 + 8].copy_from_slice(&1_i32.to_be_bytes());
+            du[row * 12 + 8..row * 12 + 12].copy_from_slice(&(row as i32 * 4).to_be_bytes());
+            du[1440 + row * 4..1440 + (row + 1) * 4]
+                .copy_from_slice(&(row as i32 * 10).to_be_bytes());
+        }
+        let data = mock_extension(&cards, &du);
+
+        let (mut hdu_list, hdu) = first_bintable(&data);
+        let xtension = hdu.get_header().get_xtension();
+
+        let sub_table = xtension.row_slice(100, 10).unwrap();
+        assert_eq!(sub_table.get_num_rows(), 10);
+        assert_eq!(sub_table.get_theap(), 1440 - 1200);
+        assert_eq!(sub_table.get_num_bytes_data_block(), 1920 - 1200);
+        assert!(xtension.row_slice(100, 21).is_err());
+        assert!(xtension.row_slice(121, 0).is_err());
+        assert!(xtension.row_slice(usize::MAX, 2).is_err());
+
+        let table_data = hdu_list
+            .get_data(&hdu)
+            .table_data()
+            .row_slice(100, 10)
+            .unwrap();
+        assert_eq!(table_data.raw_bytes().len(), 1920 - 1200);
+        let values = table_data
+            .map(|value| match value {
+                DataValue::Integer { value, .. } => value,
+                _ => panic!("expected integers"),
+            })
+            .collect::<Vec<_>>();
+        let expected = (100..110)
+            .flat_map(|row| [row, row * 10])
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn compressed_table_of_tile_compressed_image() {
+        let cards = bintable_cards(
+            16,
+            0,
+            0,
+            &[("COMPRESSED_DATA", "1PB(0)"), ("ZSCALE", "1D")],
+            &[
+                "ZIMAGE  =                    T",
+                "ZCMPTYPE= 'GZIP_1  '",
+                "ZBITPIX =                   16",
+                "ZNAXIS  =                    2",
+                "ZNAXIS1 =                  100",
+                "ZNAXIS2 =                   50",
+            ],
+        );
+        let data = mock_extension(&cards, &[]);
+
+        let mut hdu_list = Fits::from_bytes(&data);
+        let primary = hdu_list.next().unwrap().unwrap();
+        assert!(primary.as_compressed_table().is_none());
+
+        let hdu = hdu_list.next().unwrap().unwrap();
+        let table = hdu
+            .as_compressed_table()
+            .expect("expected a tile compressed image");
+        let xtension = table.get_header().get_xtension();
+        assert_eq!(xtension.find_field_by_ttype("ZSCALE"), Some(1));
+        assert_eq!(xtension.column("zscale"), Some(1));
+        assert_eq!(xtension.column_offsets(), [0, 8]);
+
+        let z_image = xtension.get_tile_compressed_image().unwrap();
+        assert_eq!(z_image.get_z_bitpix(), Bitpix::I16);
+        assert_eq!(z_image.get_z_naxis(), [100, 50]);
+        // Row by row tiling by default
+        assert_eq!(z_image.get_z_tile(), [100, 1]);
+        assert_eq!(z_image.get_data_compressed_idx(), 0);
+
+        let selected = xtension.select(&["ZSCALE", "COMPRESSED_DATA"]).unwrap();
+        assert_eq!(
+            selected
+                .get_tile_compressed_image()
+                .map(|z_image| z_image.get_data_compressed_idx()),
+            Some(0)
+        );
+        let selected = xtension.select(&["ZSCALE"]).unwrap();
+        assert!(selected.get_tile_compressed_image().is_none());
+    }
 }
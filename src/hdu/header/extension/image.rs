@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use log::warn;
 use serde::Serialize;
 
 use crate::error::Error;
@@ -13,19 +14,67 @@ pub struct Image {
     bitpix: Bitpix,
     // The size of each axis
     naxisn: Box<[u64]>,
+    // The value of undefined pixels for integer images
+    blank: Option<i64>,
+    // The number of parameters preceding each group, 0 for a conforming image
+    pcount: u64,
+    // The number of groups, 1 for a conforming image
+    gcount: u64,
 }
 
 impl Image {
     /// Get the sizes of axis given by the "NAXIS" cards
+    ///
+    /// The slice is 0-based, i.e. its first element is the value of the `NAXIS1` card.
+    /// See [Image::axis_size] to follow the 1-based indexing of the FITS cards.
     pub fn get_naxis(&self) -> &[u64] {
         &self.naxisn
     }
 
+    /// Get the size of an axis given by the "NAXISn" card
+    ///
+    /// Returns `None` if `n` is not in `1..=NAXIS`
+    ///
+    /// # Params
+    /// * `n` - the 1-based index of the axis, i.e. `1` for `NAXIS1`
+    pub fn axis_size(&self, n: usize) -> Option<usize> {
+        n.checked_sub(1)
+            .and_then(|idx| self.naxisn.get(idx))
+            .map(|&size| size as usize)
+    }
+
+    /// Returns `true` if the data unit is a vector, e.g. a spectrum, i.e. `NAXIS = 1`
+    pub fn is_vector(&self) -> bool {
+        self.naxisn.len() == 1
+    }
+
+    /// Returns `true` if the data unit has at least two axes, i.e. `NAXIS >= 2`
+    pub fn is_image(&self) -> bool {
+        self.naxisn.len() >= 2
+    }
+
     /// Get the bitpix value given by the "BITPIX" card
     pub fn get_bitpix(&self) -> Bitpix {
         self.bitpix
     }
 
+    /// Get the value of undefined pixels given by the "BLANK" card
+    ///
+    /// Only relevant for integer images, undefined pixels of floating point images being NaN
+    pub fn get_blank(&self) -> Option<i64> {
+        self.blank
+    }
+
+    /// Get the pcount value given by the "PCOUNT" card, `0` if not found
+    pub fn get_pcount(&self) -> u64 {
+        self.pcount
+    }
+
+    /// Get the gcount value given by the "GCOUNT" card, `1` if not found
+    pub fn get_gcount(&self) -> u64 {
+        self.gcount
+    }
+
     /// Get total number of pixels in the image
     pub fn get_num_pixels(&self) -> u64 {
         if self.naxisn.is_empty() {
@@ -37,8 +86,11 @@ impl Image {
 
 #[async_trait(?Send)]
 impl Xtension for Image {
+    /// The size of the data unit is `|BITPIX| / 8 * GCOUNT * (PCOUNT + NAXIS1 * ... * NAXISn)`.
+    /// It only differs from the size of the pixels for non-conforming images declaring a
+    /// `PCOUNT` or `GCOUNT` card, the following HDU being still correctly located.
     fn get_num_bytes_data_block(&self) -> u64 {
-        self.bitpix.byte_size() as u64 * self.get_num_pixels()
+        self.bitpix.byte_size() as u64 * self.gcount * (self.pcount + self.get_num_pixels())
     }
 
     fn parse(values: &ValueMap) -> Result<Self, Error> {
@@ -49,8 +101,46 @@ impl Xtension for Image {
         // The size of each NAXIS
         let naxisn = (1..=naxis)
             .map(|naxis_i| values.check_for_naxisi(naxis_i))
-            .collect::<Result<_, _>>()?;
+            .collect::<Result<Box<[u64]>, _>>()?;
+
+        let pcount = values.get_parsed::<Option<u64>>("PCOUNT")?.unwrap_or(0);
+        let gcount = values.get_parsed::<Option<u64>>("GCOUNT")?.unwrap_or(1);
+
+        // The size of the data unit must not overflow
+        naxisn
+            .iter()
+            .try_fold(1_u64, |num_pixels, &naxisi| num_pixels.checked_mul(naxisi))
+            .and_then(|num_pixels| num_pixels.checked_add(pcount))
+            .and_then(|num_values| num_values.checked_mul(gcount))
+            .and_then(|num_values| num_values.checked_mul(bitpix.byte_size() as u64))
+            .ok_or(Error::StaticError(
+                "The size of the image data unit overflows",
+            ))?;
+
+        // Random groups are flagged by `GROUPS = T` and `NAXIS1 = 0`, cf. FITSv4, section 6.
+        // Their data unit is not an image and would be wrongly interpreted as an empty one.
+        let groups = values
+            .get_parsed::<Option<bool>>("GROUPS")?
+            .unwrap_or(false);
+        if groups && naxisn.first() == Some(&0) {
+            return Err(Error::StaticError(
+                "Random groups (GROUPS = T and NAXIS1 = 0) are not supported",
+            ));
+        }
+
+        let blank = values
+            .get_parsed::<Option<i64>>("BLANK")
+            .unwrap_or_else(|err| {
+                warn!("BLANK is not valid and will be discarded: {err}");
+                None
+            });
 
-        Ok(Image { bitpix, naxisn })
+        Ok(Image {
+            bitpix,
+            naxisn,
+            blank,
+            pcount,
+            gcount,
+        })
     }
 }
@@ -1,6 +1,7 @@
 pub mod asciitable;
 pub mod bintable;
 pub mod image;
+pub mod unknown;
 
 use std::str::FromStr;
 
@@ -10,19 +11,22 @@ use serde::Serialize;
 use super::ValueMap;
 use crate::error::Error;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize)]
 pub enum XtensionType {
     Image,
     BinTable,
     AsciiTable,
+    /// An unsupported or obsolete type, e.g. `A3DTABLE`, whose data unit is only skipped
+    Unknown(String),
 }
 
 impl XtensionType {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             XtensionType::Image => "IMAGE",
             XtensionType::BinTable => "BINTABLE",
             XtensionType::AsciiTable => "TABLE",
+            XtensionType::Unknown(name) => name,
         }
     }
 }
@@ -41,7 +45,8 @@ impl FromStr for XtensionType {
             "IMAGE" | "IUEIMAGE" => Ok(XtensionType::Image),
             "TABLE" => Ok(XtensionType::AsciiTable),
             "BINTABLE" => Ok(XtensionType::BinTable),
-            _ => Err(Error::NotSupportedXtensionType(value.to_owned())),
+            "" => Err(Error::NotSupportedXtensionType(value.to_owned())),
+            _ => Ok(XtensionType::Unknown(value.to_owned())),
         }
     }
 }
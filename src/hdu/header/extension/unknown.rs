@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::hdu::header::Bitpix;
+
+use crate::hdu::header::ValueMap;
+use crate::hdu::header::Xtension;
+
+/// An extension whose `XTENSION` type is not supported, e.g. the obsolete `A3DTABLE`
+///
+/// Only the mandatory keywords common to all the conforming extensions are parsed so that the size of
+/// the data unit is known. The HDU can then be skipped and its data unit read as raw bytes.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+pub struct Unknown {
+    // The type given by the "XTENSION" card
+    name: String,
+    // A number of bit that each value has
+    bitpix: Bitpix,
+    // The size of each axis
+    naxisn: Box<[u64]>,
+    // The number of values following the main ones
+    pcount: u64,
+    // The number of groups
+    gcount: u64,
+}
+
+impl Unknown {
+    /// Get the type of the extension given by the "XTENSION" card, e.g. `A3DTABLE`
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the bitpix value given by the "BITPIX" card
+    pub fn get_bitpix(&self) -> Bitpix {
+        self.bitpix
+    }
+
+    /// Get the sizes of axis given by the "NAXIS" cards
+    pub fn get_naxis(&self) -> &[u64] {
+        &self.naxisn
+    }
+
+    /// Get the pcount value given by the "PCOUNT" card
+    pub fn get_pcount(&self) -> u64 {
+        self.pcount
+    }
+
+    /// Get the gcount value given by the "GCOUNT" card
+    pub fn get_gcount(&self) -> u64 {
+        self.gcount
+    }
+}
+
+#[async_trait(?Send)]
+impl Xtension for Unknown {
+    /// The size of the data unit is `|BITPIX| / 8 * GCOUNT * (PCOUNT + NAXIS1 * ... * NAXISn)`,
+    /// as for all the conforming extensions, the product of the axes being `0` if `NAXIS = 0`.
+    fn get_num_bytes_data_block(&self) -> u64 {
+        let num_values = if self.naxisn.is_empty() {
+            0
+        } else {
+            self.naxisn.iter().product()
+        };
+        self.bitpix.byte_size() as u64 * self.gcount * (self.pcount + num_values)
+    }
+
+    fn parse(values: &ValueMap) -> Result<Self, Error> {
+        let name = values.get_str("XTENSION").unwrap_or_default().to_owned();
+        // BITPIX
+        let bitpix = values.check_for_bitpix()?;
+        // NAXIS
+        let naxis = values.check_for_naxis()? as usize;
+        // The size of each NAXIS
+        let naxisn = (1..=naxis)
+            .map(|naxis_i| values.check_for_naxisi(naxis_i))
+            .collect::<Result<Box<[u64]>, _>>()?;
+        // PCOUNT
+        let pcount = values.check_for_pcount()?;
+        // GCOUNT
+        let gcount = values.check_for_gcount()?;
+
+        // The size of the data unit must not overflow
+        naxisn
+            .iter()
+            .try_fold(1_u64, |num_values, &naxisi| num_values.checked_mul(naxisi))
+            .and_then(|num_values| num_values.checked_add(pcount))
+            .and_then(|num_values| num_values.checked_mul(gcount))
+            .and_then(|num_values| num_values.checked_mul(bitpix.byte_size() as u64))
+            .ok_or(Error::StaticError(
+                "The size of the extension data unit overflows",
+            ))?;
+
+        Ok(Unknown {
+            name,
+            bitpix,
+            naxisn,
+            pcount,
+            gcount,
+        })
+    }
+}
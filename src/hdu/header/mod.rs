@@ -8,7 +8,11 @@ use log::warn;
 use serde::de::{value::MapDeserializer, IntoDeserializer};
 use serde::{Deserialize, Serialize};
 
+pub mod date;
+pub mod display;
 pub mod extension;
+pub mod parser;
+pub mod pointing;
 
 pub use extension::Xtension;
 
@@ -19,6 +23,8 @@ use std::ops::Deref;
 use crate::{
     card::{self, *},
     error::Error,
+    hdu::header::extension::bintable::BinTable,
+    hdu::header::extension::image::Image,
 };
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -77,12 +83,36 @@ impl Bitpix {
     pub fn byte_size(&self) -> usize {
         ((*self as i8).unsigned_abs() as usize) >> 3
     }
+
+    /// Get the FITS integer code of the bitpix as written in the `BITPIX` card
+    pub fn to_i64(&self) -> i64 {
+        *self as i8 as i64
+    }
+
+    /// Get the bitpix from its FITS integer code
+    ///
+    /// # Params
+    /// * `code` - One of the valid `BITPIX` values, i.e. 8, 16, 32, 64, -32 or -64
+    pub fn from_i64(code: i64) -> Result<Self, Error> {
+        match code {
+            8 => Ok(Bitpix::U8),
+            16 => Ok(Bitpix::I16),
+            32 => Ok(Bitpix::I32),
+            64 => Ok(Bitpix::I64),
+            -32 => Ok(Bitpix::F32),
+            -64 => Ok(Bitpix::F64),
+            _ => Err(Error::DynamicError(format!("Invalid BITPIX value: {code}"))),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize, Clone)]
 #[serde(transparent)]
 pub struct ValueMap {
     values: IndexMap<String, Value>,
+    /// The index of the card giving the value of each keyword
+    #[serde(skip)]
+    card_indices: IndexMap<String, usize>,
 }
 
 impl ValueMap {
@@ -106,6 +136,22 @@ impl ValueMap {
         T::deserialize(self.get(key).unwrap_or(&Value::Undefined))
     }
 
+    /// Get the string value of a card, returns `None` if the card is not
+    /// found or its value is not a [string](Value::String).
+    ///
+    /// A card present with an empty value is distinguished from an absent one: the null string `''`
+    /// gives `Some("")` and an all-blank string, e.g. `'    '`, gives `Some(" ")`.
+    ///
+    /// # Params
+    /// * `key` - The key of a card
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        if let Some(Value::String { value, .. }) = self.get(key) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
     /// Return an iterator over all key-[value](Card::Value) pairs in the FITS
     /// header.
     pub fn iter(&self) -> ValueMapIter<'_> {
@@ -133,28 +179,61 @@ impl ValueMap {
 
     /* Mandatory keywords parsing */
 
+    /// Parse the value of a mandatory keyword
+    ///
+    /// A missing keyword gives a [FailFindingKeyword](Error::FailFindingKeyword) error and an
+    /// invalid value gives a [CardParse](Error::CardParse) error locating the card in the header.
+    fn check_for<'de, T>(&'de self, key: &str) -> Result<T, Error>
+    where
+        T: Deserialize<'de>,
+    {
+        let card_index = *self
+            .card_indices
+            .get(key)
+            .ok_or_else(|| Error::FailFindingKeyword(key.to_owned()))?;
+
+        self.get_parsed(key).map_err(|cause| Error::CardParse {
+            card_index,
+            keyword: key.to_owned(),
+            offset: card_index as u64 * 80,
+            cause: Box::new(cause),
+        })
+    }
+
     fn check_for_bitpix(&self) -> Result<Bitpix, Error> {
-        self.get_parsed("BITPIX")
+        self.check_for("BITPIX")
     }
 
     fn check_for_naxis(&self) -> Result<u64, Error> {
-        self.get_parsed("NAXIS")
+        self.check_for("NAXIS")
     }
 
     fn check_for_naxisi(&self, i: usize) -> Result<u64, Error> {
-        self.get_parsed(&format!("NAXIS{i}"))
+        self.check_for(&format!("NAXIS{i}"))
     }
 
     fn check_for_gcount(&self) -> Result<u64, Error> {
-        self.get_parsed("GCOUNT")
+        self.check_for("GCOUNT")
     }
 
     fn check_for_pcount(&self) -> Result<u64, Error> {
-        self.get_parsed("PCOUNT")
+        self.check_for("PCOUNT")
     }
 
     fn check_for_tfields(&self) -> Result<usize, Error> {
-        self.get_parsed("TFIELDS")
+        self.check_for("TFIELDS")
+    }
+
+    /// Count the `TFORMn` cards of a table, which should be as many as its `TFIELDS` value
+    pub(crate) fn num_tforms(&self) -> usize {
+        self.keywords()
+            .filter(|keyword| {
+                keyword
+                    .strip_prefix("TFORM")
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .is_some_and(|n| n >= 1)
+            })
+            .count()
     }
 }
 
@@ -182,8 +261,19 @@ impl<'de> IntoDeserializer<'de, Error> for &'de ValueMap {
     }
 }
 
+/// A numeric card found among several possible keywords
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericCard<'a> {
+    /// The keyword of the card found
+    pub keyword: &'a str,
+    /// The value of the card
+    pub value: f64,
+    /// The unit given in the comment of the card between square brackets, e.g. `[e-/ADU]`
+    pub unit: Option<&'a str>,
+}
+
 /// The header part of an [crate::hdu::HDU].
-#[derive(Debug, PartialEq, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct Header<X> {
     /// All cards in the order they appear in the header.
     cards: Vec<Card>,
@@ -197,6 +287,21 @@ pub struct Header<X> {
     values: ValueMap,
     /// Mandatory keywords for fits ext parsing.
     xtension: X,
+    /// The 80 bytes of each card as read from the source, empty if the header has not been read
+    /// from bytes.
+    #[serde(skip)]
+    raw_cards: Vec<CardBuf>,
+}
+
+/// Headers are compared by their cards, the raw bytes of the cards being ignored so that a header
+/// read from bytes equals the same header built from its cards
+impl<X> PartialEq for Header<X>
+where
+    X: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cards == other.cards && self.values == other.values && self.xtension == other.xtension
+    }
 }
 
 impl<X> Deref for Header<X> {
@@ -220,9 +325,45 @@ where
             cards,
             values,
             xtension,
+            raw_cards: Vec::new(),
         })
     }
 
+    /// Keep the bytes the cards have been parsed from, one [CardBuf] per card
+    pub(crate) fn with_raw_cards(mut self, raw_cards: Vec<CardBuf>) -> Self {
+        debug_assert_eq!(raw_cards.len(), self.cards.len());
+        self.raw_cards = raw_cards;
+        self
+    }
+
+    /// Get the exact 80 bytes of a card as read from the source, e.g. to inspect a malformed card
+    ///
+    /// Returns `None` if the index is out of range or if the header has not been read from bytes.
+    ///
+    /// # Params
+    /// * `index` - the index of the card in the header starting from 0
+    pub fn raw_card(&self, index: usize) -> Option<&CardBuf> {
+        self.raw_cards.get(index)
+    }
+
+    /// Get the exact 80 bytes of the card of a keyword as read from the source
+    ///
+    /// For a value card, this is the card giving the value returned by [ValueMap::get], i.e. the last
+    /// one if the keyword appears more than once. For other cards, e.g. `COMMENT`, this is the first one.
+    ///
+    /// # Params
+    /// * `keyword` - the keyword of the card, e.g. `NAXIS1`
+    pub fn raw_card_by_keyword(&self, keyword: &str) -> Option<&CardBuf> {
+        let index = self
+            .values
+            .card_indices
+            .get(keyword)
+            .copied()
+            .or_else(|| self.cards.iter().position(|card| card.keyword() == keyword))?;
+
+        self.raw_card(index)
+    }
+
     /// Get the gcount value given by the `PCOUNT` card
     pub fn get_xtension(&self) -> &X {
         &self.xtension
@@ -233,6 +374,31 @@ where
         self.cards.iter()
     }
 
+    /// Get the keywords of all the cards in the order they appear in the header
+    ///
+    /// Contrary to [ValueMap::keywords] that only gives the keywords of the cards having a value,
+    /// the keywords of the `COMMENT`, `HISTORY` and `CONTINUE` cards are included as many times
+    /// as they are found. See [Card::keyword] for the keyword of each kind of card.
+    pub fn card_keywords(&self) -> Vec<&str> {
+        self.cards.iter().map(Card::keyword).collect()
+    }
+
+    /// Check whether a card with the given keyword is found in the header, e.g. `HISTORY` or `EXPTIME`
+    pub fn contains(&self, keyword: &str) -> bool {
+        self.cards.iter().any(|card| card.keyword() == keyword)
+    }
+
+    /// Get the number of 2880-byte blocks needed to write the cards of the header, i.e. 36 cards per
+    /// block including `END`
+    ///
+    /// It gives the size of the header as read from a file only if no card has been [pushed](Header::push)
+    /// and no line ending bytes have been skipped between its cards. See
+    /// [HDU::get_data_unit_byte_offset](crate::fits::HDU::get_data_unit_byte_offset) for the position
+    /// of the data unit.
+    pub fn num_blocks(&self) -> usize {
+        self.cards.len().div_ceil(36)
+    }
+
     /// Return an iterator over the processing history of the header, i.e. all
     /// [cards](Card) with the `HISTORY` keyword.
     ///
@@ -247,8 +413,329 @@ where
     }
 }
 
+/// Keywords describing the structure of a HDU, which cannot appear twice in a header
+const MANDATORY_KEYWORDS: &[&str] = &[
+    "SIMPLE", "XTENSION", "BITPIX", "NAXIS", "PCOUNT", "GCOUNT", "TFIELDS", "END",
+];
+
+fn is_mandatory_keyword(keyword: &str) -> bool {
+    MANDATORY_KEYWORDS.contains(&keyword)
+        || keyword
+            .strip_prefix("NAXIS")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+impl<X> Header<X> {
+    /// Append a value card just before the `END` card
+    ///
+    /// The card can be retrieved from its keyword afterwards. As when parsing a header, the value of
+    /// a keyword appearing more than once is the last one, but a mandatory keyword describing the
+    /// structure of the HDU (e.g. `BITPIX` or `NAXISn`) cannot be pushed twice.
+    ///
+    /// # Params
+    /// * `name` - the keyword of the card, i.e. at most 8 uppercase letters, digits, `-` or `_`
+    /// * `value` - the value of the card and its comment
+    pub fn push(&mut self, name: &str, value: Value) -> Result<(), Error> {
+        let is_valid_keyword = !name.is_empty()
+            && name.len() <= 8
+            && name
+                .bytes()
+                .all(|b| matches!(b, b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_'));
+        if !is_valid_keyword {
+            return Err(Error::DynamicError(format!(
+                "'{name}' is not a valid keyword"
+            )));
+        }
+        if is_mandatory_keyword(name) && self.values.get(name).is_some() {
+            return Err(Error::DynamicError(format!(
+                "The mandatory keyword {name} is already in the header"
+            )));
+        }
+
+        let card = Card::Value {
+            name: name.to_owned(),
+            value: value.clone(),
+        };
+        // Check that the value fits in the card
+        let raw_card = card.to_bytes()?;
+
+        let idx = self
+            .cards
+            .iter()
+            .rposition(|card| card == &Card::End)
+            .unwrap_or(self.cards.len());
+        if !self.raw_cards.is_empty() {
+            self.raw_cards.insert(idx, raw_card);
+        }
+        self.cards.insert(idx, card);
+        self.values.values.insert(name.to_owned(), value);
+        self.values.card_indices.insert(name.to_owned(), idx);
+
+        Ok(())
+    }
+
+    /// Write the cards of the header followed by spaces padding it to a multiple of 2880 bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity((self.cards.len() * 80).next_multiple_of(2880));
+        for card in &self.cards {
+            bytes.extend(card.to_bytes()?);
+        }
+        bytes.resize(bytes.len().next_multiple_of(2880), b' ');
+
+        Ok(bytes)
+    }
+}
+
+impl<X> Header<X> {
+    /// Keywords whose cards usually differ between two runs of the same processing,
+    /// e.g. timestamps, checksums or commentary cards.
+    pub const VOLATILE_KEYWORDS: &'static [&'static str] =
+        &["COMMENT", "HISTORY", "CHECKSUM", "DATASUM", "DATE"];
+
+    /// Compare the cards of two headers, skipping the ones whose keyword is given.
+    ///
+    /// The [CONTINUE](Card::Continuation) cards of a skipped long-string keyword are skipped as well.
+    /// This is useful for comparing headers of files produced at different times, see
+    /// [Header::VOLATILE_KEYWORDS] for a default set of keywords to ignore.
+    ///
+    /// # Params
+    /// * `other` - The header to compare with
+    /// * `keywords` - The keywords of the cards to ignore
+    pub fn equals_ignoring<Y>(&self, other: &Header<Y>, keywords: &[&str]) -> bool {
+        fn filter_cards<'a>(cards: &'a [Card], keywords: &[&str]) -> Vec<&'a Card> {
+            let mut skip_continuation = false;
+            cards
+                .iter()
+                .filter(|card| {
+                    if let Card::Continuation { .. } = card {
+                        !skip_continuation
+                    } else {
+                        skip_continuation = keywords.contains(&card.keyword());
+                        !skip_continuation
+                    }
+                })
+                .collect()
+        }
+
+        filter_cards(&self.cards, keywords) == filter_cards(&other.cards, keywords)
+    }
+}
+
+impl<X> Header<X> {
+    /// Get the telescope used to acquire the data given by the `TELESCOP` card
+    pub fn telescope(&self) -> Option<&str> {
+        self.get_str("TELESCOP")
+    }
+
+    /// Get the instrument used to acquire the data given by the `INSTRUME` card
+    pub fn instrument(&self) -> Option<&str> {
+        self.get_str("INSTRUME")
+    }
+
+    /// Get who acquired the data given by the `OBSERVER` card
+    pub fn observer(&self) -> Option<&str> {
+        self.get_str("OBSERVER")
+    }
+
+    /// Get the organization or institution responsible for creating the FITS file
+    /// given by the `ORIGIN` card
+    pub fn origin(&self) -> Option<&str> {
+        self.get_str("ORIGIN")
+    }
+
+    /// Get the version of the long string convention declared by the `LONGSTRN` card, e.g. `OGIP 1.0`
+    ///
+    /// The card states that long string values are continued over `CONTINUE` cards, a convention
+    /// that has only become part of the standard with FITS 4.0.
+    pub fn long_string_convention(&self) -> Option<&str> {
+        self.get_str("LONGSTRN").map(str::trim_end)
+    }
+
+    /// Get the software that created the FITS file
+    ///
+    /// As pipelines use different keywords, the `CREATOR`, `PROGRAM` and `SOFTWARE` cards are
+    /// looked for in that order and the first one found is returned.
+    pub fn creator(&self) -> Option<&str> {
+        ["CREATOR", "PROGRAM", "SOFTWARE"]
+            .iter()
+            .find_map(|key| self.get_str(key))
+    }
+
+    /// Get the gain of the detector, usually in e-/ADU
+    ///
+    /// The `GAIN`, `EGAIN` and `CCDGAIN` cards are looked for in that order.
+    pub fn gain(&self) -> Option<NumericCard<'_>> {
+        self.find_numeric_card(&["GAIN", "EGAIN", "CCDGAIN"])
+    }
+
+    /// Get the read noise of the detector, usually in e-
+    ///
+    /// The `RDNOISE`, `READNOIS` and `RON` cards are looked for in that order.
+    pub fn read_noise(&self) -> Option<NumericCard<'_>> {
+        self.find_numeric_card(&["RDNOISE", "READNOIS", "RON"])
+    }
+
+    /// Get the saturation level of the detector, usually in ADU
+    ///
+    /// The `SATURATE` and `SATLEVEL` cards are looked for in that order.
+    pub fn saturation(&self) -> Option<NumericCard<'_>> {
+        self.find_numeric_card(&["SATURATE", "SATLEVEL"])
+    }
+
+    /// Get the geocentric position of the observatory in meters given by the `OBSGEO-X`, `OBSGEO-Y`
+    /// and `OBSGEO-Z` cards
+    ///
+    /// The position is given in the ITRS Cartesian frame, e.g. for barycentric time corrections.
+    /// Returns `None` unless the three cards are present with a numeric value.
+    pub fn obs_geo(&self) -> Option<[f64; 3]> {
+        let coo = |keyword| Some(self.find_numeric_card(&[keyword])?.value);
+        Some([coo("OBSGEO-X")?, coo("OBSGEO-Y")?, coo("OBSGEO-Z")?])
+    }
+
+    /// Find the first card among `keywords` having a numeric value
+    fn find_numeric_card<'a>(&'a self, keywords: &[&'a str]) -> Option<NumericCard<'a>> {
+        keywords.iter().find_map(|&keyword| {
+            let value = self.get(keyword)?;
+            let num = match value {
+                Value::Integer { value, .. } => *value as f64,
+                Value::Float { value, .. } => *value,
+                _ => return None,
+            };
+
+            Some(NumericCard {
+                keyword,
+                value: num,
+                unit: value.unit(),
+            })
+        })
+    }
+}
+
+impl Header<Image> {
+    /// Get the value of the `EXTEND` card of a primary header
+    ///
+    /// `EXTEND = T` only states that the FITS file may contain extensions. It is therefore possible
+    /// that no extension actually follows the primary HDU, see [Fits::has_extensions](crate::Fits::has_extensions).
+    /// Returns `false` if the card is not found or its value is not a logical.
+    pub fn get_extend(&self) -> bool {
+        self.get_parsed::<bool>("EXTEND").unwrap_or(false)
+    }
+
+    /// Get the scaling factor of the pixel values given by the `BSCALE` card, `1.0` if not found
+    pub fn get_bscale(&self) -> Result<f64, Error> {
+        Ok(self.get_parsed::<Option<f64>>("BSCALE")?.unwrap_or(1.0))
+    }
+
+    /// Get the offset of the pixel values given by the `BZERO` card, `0.0` if not found
+    pub fn get_bzero(&self) -> Result<f64, Error> {
+        Ok(self.get_parsed::<Option<f64>>("BZERO")?.unwrap_or(0.0))
+    }
+
+    /// Returns `true` if the integer pixels store unsigned values following the FITS convention
+    ///
+    /// That is `BSCALE = 1` and `BZERO = 2^(BITPIX - 1)`, e.g. `BZERO = 32768` for `BITPIX = 16`.
+    /// The cards can be written as integers or floats, e.g. `32768` or `32768.0`.
+    pub fn is_unsigned(&self) -> bool {
+        let offset = match self.get_xtension().get_bitpix() {
+            Bitpix::I16 => 32768.0,
+            Bitpix::I32 => 2147483648.0,
+            Bitpix::I64 => 9223372036854775808.0,
+            _ => return false,
+        };
+
+        matches!(
+            (self.get_bscale(), self.get_bzero()),
+            (Ok(bscale), Ok(bzero)) if bscale == 1.0 && bzero == offset
+        )
+    }
+
+    /// Get the type of the physical values of the image, i.e. once scaled by `BSCALE` and `BZERO`
+    ///
+    /// Returns [Bitpix::F64] if the scaling is not the identity, i.e. `BSCALE != 1` or `BZERO != 0`,
+    /// as the physical values are then computed as floats, and the `BITPIX` of the stored values otherwise.
+    /// An image following the unsigned integer convention is therefore given as [Bitpix::F64], see
+    /// [Header::is_unsigned] to detect it.
+    pub fn effective_bitpix(&self) -> Result<Bitpix, Error> {
+        if self.get_bscale()? != 1.0 || self.get_bzero()? != 0.0 {
+            Ok(Bitpix::F64)
+        } else {
+            Ok(self.get_xtension().get_bitpix())
+        }
+    }
+
+    /// Get the minimum physical value of the image given by the `DATAMIN` card
+    ///
+    /// Returns `None` if the card is not found or its value is not a number
+    pub fn data_min(&self) -> Option<f64> {
+        self.get_parsed::<Option<f64>>("DATAMIN").ok().flatten()
+    }
+
+    /// Get the maximum physical value of the image given by the `DATAMAX` card
+    ///
+    /// Returns `None` if the card is not found or its value is not a number
+    pub fn data_max(&self) -> Option<f64> {
+        self.get_parsed::<Option<f64>>("DATAMAX").ok().flatten()
+    }
+}
+
+impl Header<BinTable> {
+    /// Get the dimensions of the values of a field given by its `TDIMn` card, e.g. `[16, 4]` for `TDIM1 = '(16,4)'`
+    ///
+    /// For a character field, the first dimension is the number of characters of each string.
+    /// Returns `None` if the card is not found.
+    ///
+    /// # Params
+    /// * `col` - the index of the field starting from 0
+    pub fn get_tdim(&self, col: usize) -> Result<Option<Vec<usize>>, Error> {
+        let key = format!("TDIM{}", col + 1);
+        let Some(tdim) = self.get_str(&key) else {
+            return Ok(None);
+        };
+
+        tdim.trim()
+            .strip_prefix('(')
+            .and_then(|dims| dims.strip_suffix(')'))
+            .and_then(|dims| {
+                dims.split(',')
+                    .map(|dim| dim.trim().parse::<usize>().ok())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .map(Some)
+            .ok_or_else(|| Error::DynamicError(format!("{key} = '{tdim}' is not valid")))
+    }
+
+    /// Get the scaling factor of the values of a field given by its `TSCALn` card, `1.0` if not found
+    ///
+    /// # Params
+    /// * `col` - the index of the field starting from 0
+    pub fn get_tscal(&self, col: usize) -> Result<f64, Error> {
+        Ok(self
+            .get_parsed::<Option<f64>>(&format!("TSCAL{}", col + 1))?
+            .unwrap_or(1.0))
+    }
+
+    /// Get the offset of the values of a field given by its `TZEROn` card, `0.0` if not found
+    ///
+    /// # Params
+    /// * `col` - the index of the field starting from 0
+    pub fn get_tzero(&self, col: usize) -> Result<f64, Error> {
+        Ok(self
+            .get_parsed::<Option<f64>>(&format!("TZERO{}", col + 1))?
+            .unwrap_or(0.0))
+    }
+
+    /// Get the stored value of an integer field representing an undefined value, given by its `TNULLn` card
+    ///
+    /// # Params
+    /// * `col` - the index of the field starting from 0
+    pub fn get_tnull(&self, col: usize) -> Result<Option<i64>, Error> {
+        self.get_parsed::<Option<i64>>(&format!("TNULL{}", col + 1))
+    }
+}
+
 fn process_cards(cards: &[Card]) -> Result<ValueMap, Error> {
     let mut values = IndexMap::new();
+    let mut card_indices = IndexMap::new();
     let mut kw: Option<String> = None;
 
     for (i, card) in cards.iter().enumerate() {
@@ -260,6 +747,7 @@ fn process_cards(cards: &[Card]) -> Result<ValueMap, Error> {
                     kw = None;
                 }
                 values.insert(name.to_owned(), value.to_owned());
+                card_indices.insert(name.to_owned(), i);
                 if value.continued() {
                     kw = Some(name.to_owned());
                 }
@@ -293,6 +781,7 @@ fn process_cards(cards: &[Card]) -> Result<ValueMap, Error> {
                     kw = None;
                 }
 
+                card_indices.insert("XTENSION".to_owned(), i);
                 values.insert(
                     "XTENSION".to_owned(),
                     Value::String {
@@ -303,7 +792,10 @@ fn process_cards(cards: &[Card]) -> Result<ValueMap, Error> {
             }
             Card::End => {
                 if i + 1 == cards.len() {
-                    return Ok(ValueMap { values });
+                    return Ok(ValueMap {
+                        values,
+                        card_indices,
+                    });
                 } else {
                     unreachable!("cards trailing after the END card")
                 }
@@ -321,6 +813,7 @@ mod tests {
     use crate::error::Error;
     use crate::fits::Fits;
     use crate::hdu::HDU;
+    use crate::test_utils::{first_primary, mock_header};
 
     use core::panic;
     use std::collections::VecDeque;
@@ -329,6 +822,10 @@ mod tests {
     use std::io::Cursor;
 
     use super::check_card_keyword;
+    use super::Bitpix;
+    use super::Header;
+    use crate::hdu::header::extension::image::Image;
+    use std::convert::TryFrom;
     // use Iterator;
 
     use super::CardBuf;
@@ -349,7 +846,7 @@ mod tests {
 
     #[test]
     fn primary_hdu_without_simple_keyword() -> Result<(), Error> {
-        let data = mock_fits_data([
+        let data = mock_header(&[
             b"WRONGKW =                    T / this is a fake FITS file                       ",
             b"BITPIX  =                    8 / byte sized numbers                             ",
             b"NAXIS   =                    0 / no data arrays                                 ",
@@ -371,7 +868,7 @@ mod tests {
 
     #[test]
     fn primary_hdu_with_no_data() -> Result<(), Error> {
-        let data = mock_fits_data([
+        let data = mock_header(&[
             b"SIMPLE  =                    T / this is a fake FITS file                       ",
             b"BITPIX  =                    8 / byte sized numbers                             ",
             b"NAXIS   =                    0 / no data arrays                                 ",
@@ -462,9 +959,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn blank_cards_between_mandatory_keywords() -> Result<(), Error> {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"                                                                                ",
+            b"BITPIX  =                    8                                                  ",
+            b"COMMENT                                                                         ",
+            b"NAXIS   =                    0                                                  ",
+            b"        text-only card                                                          ",
+            b"HISTORY                                                                         ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+
+        let cards = hdu.get_header().cards().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            cards,
+            [
+                &Card::Space,
+                &Card::Value {
+                    name: "BITPIX".to_owned(),
+                    value: Value::Integer {
+                        value: 8,
+                        comment: None
+                    }
+                },
+                &Card::Comment(String::new()),
+                &Card::Value {
+                    name: "NAXIS".to_owned(),
+                    value: Value::Integer {
+                        value: 0,
+                        comment: None
+                    }
+                },
+                &Card::Comment("text-only card".to_owned()),
+                &Card::History(String::new()),
+                &Card::End,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn commented_simple_card() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T / conforms to FITS standard                      ",
+            b"BITPIX  =                    8 / array data type                                ",
+            b"NAXIS   =                    0 / number of array dimensions                     ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        assert_eq!(
+            hdu.get_header().get("SIMPLE"),
+            Some(&Value::Logical {
+                value: true,
+                comment: Some(" conforms to FITS standard".to_owned())
+            })
+        );
+
+        let data = mock_header(&[
+            b"SIMPLE  =                    F / does not conform to FITS standard              ",
+            b"BITPIX  =                    8 / array data type                                ",
+            b"NAXIS   =                    0 / number of array dimensions                     ",
+            b"END                                                                             ",
+        ]);
+        let mut fits = Fits::from_reader(Cursor::new(&data[..]));
+        assert!(fits.next().unwrap().is_err());
+    }
+
     #[test]
     fn end_card_not_found() {
-        let data = mock_fits_data([
+        let data = mock_header(&[
             b"SIMPLE  =                    T / Standard FITS Format                           ",
             b"BITPIX  =                    8 / Character data                                 ",
             b"NAXIS   =                    0 / No Image --- just extension(s)                 ",
@@ -481,7 +1048,7 @@ mod tests {
 
     #[test]
     fn blank_interpreted_as_comments() -> Result<(), Error> {
-        let data = mock_fits_data([
+        let data = mock_header(&[
             b"SIMPLE  =                    T / Standard FITS Format                           ",
             b"BITPIX  =                    8 / Character data                                 ",
             b"NAXIS   =                    0 / No Image --- just extension(s)                 ",
@@ -656,15 +1223,209 @@ mod tests {
         Ok(())
     }
 
-    /// panics if N > 36
-    fn mock_fits_data<const N: usize>(cards: [&CardBuf; N]) -> [u8; 2880] {
-        let mut data = [b' '; 2880];
-        let mut cursor = 0;
-        for card in cards {
-            data[cursor..cursor + 80].copy_from_slice(card);
-            cursor += 80;
-        }
-        data
+    #[test]
+    fn header_equals_ignoring_volatile_keywords() {
+        let parse_primary = |cards: &[&CardBuf]| {
+            let cards = cards
+                .iter()
+                .map(|c| Card::try_from(*c).unwrap())
+                .collect::<Vec<_>>();
+            Header::<Image>::parse(cards).unwrap()
+        };
+
+        let h1 = parse_primary(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"DATE    = '2024-01-01T10:00:00'                                                 ",
+            b"HISTORY run 1                                                                   ",
+            b"CHECKSUM= 'this is a checksum&'                                                 ",
+            b"CONTINUE 'continued'                                                            ",
+            b"END                                                                             ",
+        ]);
+        let h2 = parse_primary(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"DATE    = '2025-06-12T18:30:00'                                                 ",
+            b"CHECKSUM= 'another one'                                                         ",
+            b"END                                                                             ",
+        ]);
+        let h3 = parse_primary(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"OBJECT  = 'M31'                                                                 ",
+            b"END                                                                             ",
+        ]);
+
+        assert_ne!(h1, h2);
+        assert!(h1.equals_ignoring(&h2, Header::<Image>::VOLATILE_KEYWORDS));
+        assert!(!h1.equals_ignoring(&h2, &["DATE", "CHECKSUM"]));
+        assert!(!h1.equals_ignoring(&h3, Header::<Image>::VOLATILE_KEYWORDS));
+    }
+
+    #[test]
+    fn empty_and_absent_strings() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"OBJECT  = ''                   / no target                                      ",
+            b"OBSERVER= '        '                                                            ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let header = hdu.get_header();
+
+        assert_eq!(header.get_str("OBJECT"), Some(""));
+        assert_eq!(header.observer(), Some(" "));
+        assert_eq!(header.telescope(), None);
+        assert_eq!(
+            header.get_parsed::<Option<String>>("OBJECT"),
+            Ok(Some(String::new()))
+        );
+        assert_eq!(header.get_parsed::<Option<String>>("TELESCOP"), Ok(None));
+    }
+
+    #[test]
+    fn keywords_of_cards() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"COMMENT first comment                                                           ",
+            b"HISTORY first step                                                              ",
+            b"HISTORY second step                                                             ",
+            b"OBJECT  = 'M31'                                                                 ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let header = hdu.get_header();
+
+        assert_eq!(
+            header.card_keywords(),
+            ["SIMPLE", "BITPIX", "NAXIS", "COMMENT", "HISTORY", "HISTORY", "OBJECT", "END"]
+        );
+        assert!(header.contains("HISTORY") && header.contains("OBJECT"));
+        assert!(!header.contains("EXPTIME") && !header.contains("object"));
+    }
+
+    #[test]
+    fn header_spanning_two_blocks() {
+        let parse = |num_history_cards: usize| {
+            let mut data = Vec::new();
+            data.extend(
+                b"SIMPLE  =                    T                                                  ",
+            );
+            data.extend(
+                b"BITPIX  =                    8                                                  ",
+            );
+            data.extend(
+                b"NAXIS   =                    0                                                  ",
+            );
+            for _ in 0..num_history_cards {
+                data.extend(b"HISTORY step                                                                    ");
+            }
+            data.extend(
+                b"END                                                                             ",
+            );
+            data.resize(data.len().next_multiple_of(2880), b' ');
+
+            let (_, hdu) = first_primary(&data);
+            (
+                hdu.get_header().num_blocks(),
+                hdu.get_data_unit_byte_offset(),
+            )
+        };
+
+        // 36 cards including END fill exactly one block
+        assert_eq!(parse(32), (1, 2880));
+        assert_eq!(parse(33), (2, 2 * 2880));
+        assert_eq!(parse(40), (2, 2 * 2880));
+    }
+
+    #[test]
+    fn push_cards_and_serialize() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"HISTORY created for a test                                                      ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let mut header = hdu.get_header().clone();
+
+        header
+            .push(
+                "OBJECT",
+                Value::String {
+                    value: "Barnard's star".to_owned(),
+                    comment: None,
+                },
+            )
+            .unwrap();
+        header
+            .push(
+                "EXPTIME",
+                Value::Float {
+                    value: 30.0,
+                    comment: Some("[s] exposure time".to_owned()),
+                },
+            )
+            .unwrap();
+        header
+            .push(
+                "NCOMBINE",
+                Value::Integer {
+                    value: -12,
+                    comment: None,
+                },
+            )
+            .unwrap();
+        header
+            .push(
+                "FLIPPED",
+                Value::Logical {
+                    value: false,
+                    comment: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(header.get_parsed::<f64>("EXPTIME"), Ok(30.0));
+        assert_eq!(header.cards().last(), Some(&Card::End));
+
+        // Mandatory, invalid or too long cards
+        let value = Value::Integer {
+            value: 16,
+            comment: None,
+        };
+        assert!(header.push("BITPIX", value.clone()).is_err());
+        assert!(header.push("exptime", value.clone()).is_err());
+        assert!(header.push("TOOLONGKEY", value).is_err());
+        let value = Value::String {
+            value: "a".repeat(70),
+            comment: None,
+        };
+        assert!(header.push("LONGSTR", value).is_err());
+        assert_eq!(header.cards().count(), 9);
+
+        let bytes = header.to_bytes().unwrap();
+        assert_eq!(bytes.len(), 2880);
+        assert_eq!(
+            &bytes[5 * 80..6 * 80],
+            b"EXPTIME =                 30.0 / [s] exposure time                              "
+        );
+
+        let (_, hdu) = first_primary(&bytes);
+        let parsed = hdu.get_header();
+        assert_eq!(parsed.get_str("OBJECT"), Some("Barnard's star"));
+        assert_eq!(parsed.get_parsed::<f64>("EXPTIME"), Ok(30.0));
+        assert_eq!(parsed.get_parsed::<i64>("NCOMBINE"), Ok(-12));
+        assert_eq!(parsed.get_parsed::<bool>("FLIPPED"), Ok(false));
+        assert_eq!(parsed.history().collect::<Vec<_>>(), ["created for a test"]);
+        assert_eq!(parsed.get_xtension(), header.get_xtension());
     }
 
     #[test]
@@ -727,4 +1488,371 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn bitpix_code_round_trip() {
+        for code in [8, 16, 32, 64, -32, -64] {
+            assert_eq!(Bitpix::from_i64(code).unwrap().to_i64(), code);
+        }
+        assert!(Bitpix::from_i64(0).is_err());
+        assert!(Bitpix::from_i64(-8).is_err());
+    }
+
+    #[test]
+    fn negative_float_bitpix() {
+        for (card, bitpix) in [
+            (
+                b"BITPIX  =                  -32                                                  ",
+                Bitpix::F32,
+            ),
+            (
+                b"BITPIX  =                  -64 / IEEE double precision floating point           ",
+                Bitpix::F64,
+            ),
+        ] {
+            let data = mock_header(&[
+                b"SIMPLE  =                    T                                                  ",
+                card,
+                b"NAXIS   =                    0                                                  ",
+                b"END                                                                             ",
+            ]);
+            let (_, hdu) = first_primary(&data);
+            assert_eq!(hdu.get_header().get_xtension().get_bitpix(), bitpix);
+        }
+
+        // -16 is not a valid code
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -16                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"END                                                                             ",
+        ]);
+        let mut fits = Fits::from_reader(Cursor::new(data));
+        assert!(matches!(fits.next(), Some(Err(_))));
+    }
+
+    #[test]
+    fn extend_flag() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"EXTEND  =                    T                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        assert!(hdu.get_header().get_extend());
+
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        assert!(!hdu.get_header().get_extend());
+    }
+
+    #[test]
+    fn provenance_keywords() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"TELESCOP= 'HST     '           / telescope used to acquire data                 ",
+            b"INSTRUME= 'WFPC2   '                                                            ",
+            b"ORIGIN  =                   42                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let header = hdu.get_header();
+
+        assert_eq!(header.telescope(), Some("HST"));
+        assert_eq!(header.instrument(), Some("WFPC2"));
+        assert_eq!(header.observer(), None);
+        // Not a string value
+        assert_eq!(header.origin(), None);
+    }
+
+    #[test]
+    fn creator_lookup_order() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"SOFTWARE= 'astropy 6.0'                                                         ",
+            b"PROGRAM = 'SWarp   '                                                            ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        assert_eq!(hdu.get_header().creator(), Some("SWarp"));
+
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        assert_eq!(hdu.get_header().creator(), None);
+    }
+
+    #[test]
+    fn unsigned_integer_spellings() {
+        let is_unsigned = |bitpix: &CardBuf, bzero: &CardBuf| {
+            let data = mock_header(&[
+                b"SIMPLE  =                    T                                                  ",
+                bitpix,
+                b"NAXIS   =                    0                                                  ",
+                bzero,
+                b"END                                                                             ",
+            ]);
+            let (_, hdu) = first_primary(&data);
+            let header = hdu.get_header();
+            (header.get_bzero().unwrap(), header.is_unsigned())
+        };
+
+        let i16 =
+            b"BITPIX  =                   16                                                  ";
+        assert_eq!(
+            is_unsigned(
+                i16,
+                b"BZERO   =                32768                                                  "
+            ),
+            (32768.0, true)
+        );
+        assert_eq!(
+            is_unsigned(
+                i16,
+                b"BZERO   =              32768.0                                                  "
+            ),
+            (32768.0, true)
+        );
+        assert_eq!(
+            is_unsigned(
+                i16,
+                b"BZERO   =              3.2768D4                                                 "
+            ),
+            (32768.0, true)
+        );
+        assert_eq!(
+            is_unsigned(
+                i16,
+                b"BZERO   =                    0                                                  "
+            ),
+            (0.0, false)
+        );
+        assert_eq!(
+            is_unsigned(
+                b"BITPIX  =                   32                                                  ",
+                b"BZERO   =           2147483648                                                  "
+            ),
+            (2147483648.0, true)
+        );
+        assert_eq!(
+            is_unsigned(
+                b"BITPIX  =                   64                                                  ",
+                b"BZERO   =  9223372036854775808                                                  "
+            ),
+            (9223372036854775808.0, true)
+        );
+    }
+
+    #[test]
+    fn effective_bitpix_of_scaled_images() {
+        let effective_bitpix = |bitpix: &CardBuf, bscale: &CardBuf, bzero: &CardBuf| {
+            let data = mock_header(&[
+                b"SIMPLE  =                    T                                                  ",
+                bitpix,
+                b"NAXIS   =                    0                                                  ",
+                bscale,
+                bzero,
+                b"END                                                                             ",
+            ]);
+            let (_, hdu) = first_primary(&data);
+            hdu.get_header().effective_bitpix().unwrap()
+        };
+
+        let i16 =
+            b"BITPIX  =                   16                                                  ";
+        let blank =
+            b"                                                                                ";
+        // No scaling
+        assert_eq!(effective_bitpix(i16, blank, blank), Bitpix::I16);
+        // Identity scaling
+        assert_eq!(
+            effective_bitpix(
+                i16,
+                b"BSCALE  =                  1.0                                                  ",
+                b"BZERO   =                    0                                                  "
+            ),
+            Bitpix::I16
+        );
+        // Non-identity scaling
+        assert_eq!(
+            effective_bitpix(
+                i16,
+                b"BSCALE  =                 0.01                                                  ",
+                blank
+            ),
+            Bitpix::F64
+        );
+        assert_eq!(
+            effective_bitpix(
+                b"BITPIX  =                    8                                                  ",
+                blank,
+                b"BZERO   =                 -128                                                  "
+            ),
+            Bitpix::F64
+        );
+        assert_eq!(
+            effective_bitpix(
+                b"BITPIX  =                  -32                                                  ",
+                blank,
+                blank
+            ),
+            Bitpix::F32
+        );
+    }
+
+    #[test]
+    fn detector_cards() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"EGAIN   =                  2.5 / [e-/ADU] electronic gain                       ",
+            b"RDNOISE =                    4 / read noise                                     ",
+            b"SATURATE= 'unknown '                                                            ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let header = hdu.get_header();
+
+        let gain = header.gain().unwrap();
+        assert_eq!(
+            (gain.keyword, gain.value, gain.unit),
+            ("EGAIN", 2.5, Some("e-/ADU"))
+        );
+        let read_noise = header.read_noise().unwrap();
+        assert_eq!(
+            (read_noise.keyword, read_noise.value, read_noise.unit),
+            ("RDNOISE", 4.0, None)
+        );
+        // Not a numeric value
+        assert_eq!(header.saturation(), None);
+    }
+
+    #[test]
+    fn observatory_location() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"OBSGEO-X=     -1601185.3650000 / [m] observatory X coordinate                   ",
+            b"OBSGEO-Y=     -5041977.5470000 / [m] observatory Y coordinate                   ",
+            b"OBSGEO-Z=              3554875 / [m] observatory Z coordinate                   ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let header = hdu.get_header();
+
+        assert_eq!(
+            header.card_keywords()[3..6],
+            ["OBSGEO-X", "OBSGEO-Y", "OBSGEO-Z"]
+        );
+        assert_eq!(
+            header.obs_geo(),
+            Some([-1601185.365, -5041977.547, 3554875.0])
+        );
+
+        // The three coordinates are needed
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"OBSGEO-X=     -1601185.3650000                                                  ",
+            b"OBSGEO-Y=     -5041977.5470000                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        assert_eq!(hdu.get_header().obs_geo(), None);
+    }
+
+    #[test]
+    fn malformed_card_kept_as_undefined() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"STRKEY  = '                                                                     ",
+            b"BADKEY  =                 12\t3                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+
+        let cards = hdu.get_header().cards().collect::<Vec<_>>();
+        assert!(matches!(cards[3], Card::Undefined(card) if card.starts_with("STRKEY")));
+        assert!(matches!(cards[4], Card::Undefined(card) if card.starts_with("BADKEY")));
+        assert_eq!(cards[5], &Card::End);
+    }
+
+    #[test]
+    fn raw_cards() {
+        let data = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"BADKEY  =                 12\t3                                                  ",
+            b"OBJECT  = 'M31     '                                                            ",
+            b"COMMENT first comment                                                           ",
+            b"OBJECT  = 'M 31    '           / duplicated                                     ",
+            b"END                                                                             ",
+        ]);
+        let (_, hdu) = first_primary(&data);
+        let mut header = hdu.get_header().clone();
+
+        // The raw bytes are not compared
+        let parsed = Header::<Image>::parse(header.cards().cloned().collect()).unwrap();
+        assert_eq!(parsed.raw_card(0), None);
+        assert_eq!(&parsed, hdu.get_header());
+
+        assert_eq!(
+            header.raw_card(3).map(|card| &card[..]),
+            Some(&data[240..320])
+        );
+        assert_eq!(header.raw_card(8), None);
+        // The card giving the value of a duplicated keyword
+        assert_eq!(
+            header.raw_card_by_keyword("OBJECT"),
+            Some(
+                b"OBJECT  = 'M 31    '           / duplicated                                     "
+            )
+        );
+        assert_eq!(
+            header
+                .raw_card_by_keyword("COMMENT")
+                .map(|card| &card[..21]),
+            Some(&b"COMMENT first comment"[..])
+        );
+        assert_eq!(header.raw_card_by_keyword("NAXIS1"), None);
+
+        // A pushed card is serialized
+        header
+            .push(
+                "EXPTIME",
+                Value::Float {
+                    value: 30.0,
+                    comment: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            header
+                .raw_card_by_keyword("EXPTIME")
+                .map(|card| &card[..30]),
+            Some(&b"EXPTIME =                 30.0"[..])
+        );
+        assert_eq!(header.raw_card(8).map(|card| &card[..3]), Some(&b"END"[..]));
+    }
 }
@@ -0,0 +1,204 @@
+//! Module parsing a primary header from bytes received in chunks, e.g. from a network stream
+
+use std::convert::TryFrom;
+
+use log::warn;
+
+use crate::card::{Card, CardBuf, Value};
+use crate::error::Error;
+use crate::hdu::header::extension::image::Image;
+use crate::hdu::header::Header;
+
+/// The state of a [HeaderParser] after being fed with bytes
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    /// The `END` card has not been found yet, more bytes must be fed
+    NeedMore,
+    /// The `END` card has been found
+    ///
+    /// The number of bytes is the size of the header padded to a multiple of 2880 bytes, i.e. the
+    /// byte index of the data unit from the beginning of the first chunk. The padding bytes may
+    /// not have been fed yet.
+    Done(Box<Header<Image>>, usize),
+}
+
+/// A parser of a primary header accepting its bytes incrementally
+///
+/// This is the resumable counterpart of [Fits::primary_header](crate::Fits::primary_header) for
+/// sources delivering the header in chunks of any size. The cards are parsed as they are completed
+/// so that only the last incomplete card is buffered. As for the one-shot parsing, a malformed card
+/// is kept as an undefined one.
+#[derive(Debug)]
+pub struct HeaderParser {
+    // The bytes of the card being received
+    card: CardBuf,
+    // The number of bytes of `card` already received
+    num_card_bytes: usize,
+    // The cards parsed so far
+    cards: Vec<Card>,
+    // The bytes of the cards parsed so far
+    raw_cards: Vec<CardBuf>,
+    // Whether the `END` card has already been found
+    done: bool,
+}
+
+impl Default for HeaderParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeaderParser {
+    pub fn new() -> Self {
+        Self {
+            card: [b' '; 80],
+            num_card_bytes: 0,
+            cards: Vec::new(),
+            raw_cards: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Feed the parser with the next bytes of the header
+    ///
+    /// Returns [Status::Done] once the `END` card has been found, the bytes following it in `bytes`
+    /// being left unread. An error is returned if the first card is not `SIMPLE = T`, if the header
+    /// cannot be parsed or if the parser is fed again after being done.
+    ///
+    /// # Params
+    /// * `bytes` - the next bytes of the header, of any length
+    pub fn feed(&mut self, mut bytes: &[u8]) -> Result<Status, Error> {
+        if self.done {
+            return Err(Error::StaticError(
+                "The header has already been parsed, no more bytes can be fed",
+            ));
+        }
+
+        while !bytes.is_empty() {
+            let num_bytes = bytes.len().min(80 - self.num_card_bytes);
+            self.card[self.num_card_bytes..self.num_card_bytes + num_bytes]
+                .copy_from_slice(&bytes[..num_bytes]);
+            self.num_card_bytes += num_bytes;
+            bytes = &bytes[num_bytes..];
+
+            if self.num_card_bytes == 80 {
+                self.num_card_bytes = 0;
+                if self.push_card()? {
+                    return self.finish();
+                }
+            }
+        }
+
+        Ok(Status::NeedMore)
+    }
+
+    /// Parse the card that has just been received and tell whether it is the `END` card
+    fn push_card(&mut self) -> Result<bool, Error> {
+        let card = Card::try_from(&self.card).unwrap_or_else(|e| {
+            warn!(
+                "Card {} could not be parsed and is kept as undefined: {e}",
+                self.cards.len()
+            );
+            Card::Undefined(String::from_utf8_lossy(&self.card).into_owned())
+        });
+
+        // Check for the SIMPLE keyword as soon as possible to reject non FITS streams
+        if self.cards.is_empty() {
+            match &card {
+                Card::Value {
+                    name,
+                    value: Value::Logical { value: true, .. },
+                    ..
+                } if name == "SIMPLE" => {}
+                Card::Value { name, .. } => {
+                    return Err(Error::DynamicError(format!(
+                        "Invalid FITS file: expected `SIMPLE` keyword in first card, found `{name}`"
+                    )));
+                }
+                _ => return Err(Error::StaticError("not a FITSv4 file")),
+            }
+        }
+
+        let is_end = card == Card::End;
+        self.cards.push(card);
+        self.raw_cards.push(self.card);
+
+        Ok(is_end)
+    }
+
+    fn finish(&mut self) -> Result<Status, Error> {
+        self.done = true;
+
+        let num_bytes = (self.cards.len() * 80).next_multiple_of(2880);
+        let header = Header::parse(std::mem::take(&mut self.cards))?
+            .with_raw_cards(std::mem::take(&mut self.raw_cards));
+
+        Ok(Status::Done(Box::new(header), num_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderParser, Status};
+    use crate::error::Error;
+    use crate::test_utils::mock_header;
+
+    #[test]
+    fn header_fed_one_byte_at_a_time() {
+        let mut bytes = mock_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                   16                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                    3                                                  ",
+            b"NAXIS2  =                    2                                                  ",
+            b"OBJECT  = 'M31     '                                                            ",
+            b"END                                                                             ",
+        ]);
+        // The beginning of the data unit
+        bytes.extend([1, 2, 3]);
+
+        let mut parser = HeaderParser::new();
+        let mut status = Status::NeedMore;
+        let mut num_bytes_fed = 0;
+        for byte in &bytes {
+            status = parser.feed(std::slice::from_ref(byte)).unwrap();
+            num_bytes_fed += 1;
+            if status != Status::NeedMore {
+                break;
+            }
+        }
+
+        // The parsing ends with the END card, before its padding
+        assert_eq!(num_bytes_fed, 7 * 80);
+        let Status::Done(header, num_bytes) = status else {
+            panic!("the END card has not been found");
+        };
+        assert_eq!(num_bytes, 2880);
+        assert_eq!(header.get_xtension().get_naxis(), [3, 2]);
+        assert_eq!(header.get_str("OBJECT"), Some("M31"));
+        assert!(parser.feed(&[0]).is_err());
+
+        // Chunks of any size give the same header
+        let mut parser = HeaderParser::new();
+        assert_eq!(parser.feed(&bytes[..100]).unwrap(), Status::NeedMore);
+        assert_eq!(
+            parser.feed(&bytes[100..]).unwrap(),
+            Status::Done(header, 2880)
+        );
+    }
+
+    #[test]
+    fn not_a_primary_header() {
+        let bytes = mock_header(&[
+            b"XTENSION= 'IMAGE   '                                                            ",
+            b"END                                                                             ",
+        ]);
+
+        let mut parser = HeaderParser::new();
+        assert_eq!(parser.feed(&bytes[..79]).unwrap(), Status::NeedMore);
+        assert_eq!(
+            parser.feed(&bytes[79..80]),
+            Err(Error::StaticError("not a FITSv4 file"))
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Module giving the target and pointing of an observation from its header
+
+use serde::Serialize;
+
+use crate::card::Value;
+use crate::hdu::header::Header;
+
+/// The target and pointing coordinates of an observation
+#[derive(Debug, PartialEq, Serialize, Clone)]
+pub struct Pointing {
+    /// The name of the observed object given by the `OBJECT` card
+    pub object: Option<String>,
+    /// The right ascension in degrees
+    pub ra: f64,
+    /// The declination in degrees
+    pub dec: f64,
+}
+
+impl<X> Header<X> {
+    /// Get the observed object and the pointing coordinates
+    ///
+    /// The coordinates are looked for in the `RA`/`DEC` cards first and then in
+    /// the `CRVAL1`/`CRVAL2` cards. `RA`/`DEC` can either be given in degrees or as sexagesimal
+    /// strings, e.g. `RA = '12:34:56.7'` in hours and `DEC = '-12 34 56.7'` in degrees.
+    /// Returns `None` if the coordinates are not found.
+    pub fn pointing(&self) -> Option<Pointing> {
+        let (ra, dec) = parse_coo(self.get("RA"), self.get("DEC"))
+            .or_else(|| parse_coo(self.get("CRVAL1"), self.get("CRVAL2")))?;
+
+        let object = self.get_str("OBJECT").map(|object| object.to_owned());
+
+        Some(Pointing { object, ra, dec })
+    }
+}
+
+fn parse_coo(ra: Option<&Value>, dec: Option<&Value>) -> Option<(f64, f64)> {
+    let ra = parse_angle(ra?, true)?;
+    let dec = parse_angle(dec?, false)?;
+
+    Some((ra, dec))
+}
+
+/// Parse a numeric value in degrees or a sexagesimal string
+///
+/// # Params
+/// * `value` - the value of a card
+/// * `hours` - whether the sexagesimal string is given in hours, e.g. for a right ascension
+fn parse_angle(value: &Value, hours: bool) -> Option<f64> {
+    match value {
+        Value::Float { value, .. } => Some(*value),
+        Value::Integer { value, .. } => Some(*value as f64),
+        Value::String { value, .. } => sexagesimal_to_degrees(value, hours),
+        _ => None,
+    }
+}
+
+/// Convert a sexagesimal string, e.g. `'12:34:56.78'` or `'+41 16 09'`, into decimal degrees
+///
+/// # Params
+/// * `s` - the sexagesimal string
+/// * `hours` - whether the string is given in hours (e.g. a right ascension) or in degrees
+///   (e.g. a declination)
+pub fn sexagesimal_to_degrees(s: &str, hours: bool) -> Option<f64> {
+    let unit = if hours { 15.0 } else { 1.0 };
+    parse_sexagesimal(s).map(|v| v * unit)
+}
+
+/// Parse a `[+-]DD:MM:SS.S` or `[+-]DD MM SS.S` string into decimal units
+///
+/// The sign applies to the whole value so that `-00:30:00` gives `-0.5`
+pub fn parse_sexagesimal(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let (sign, s) = match s.strip_prefix('-') {
+        Some(s) => (-1.0, s),
+        None => (1.0, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let mut value = 0.0;
+    let mut unit = 1.0;
+    let mut num_fields = 0;
+    for field in s.split(|c: char| c == ':' || c.is_ascii_whitespace()) {
+        if field.is_empty() {
+            continue;
+        }
+
+        num_fields += 1;
+        if num_fields > 3 {
+            return None;
+        }
+
+        let field = field.parse::<f64>().ok()?;
+        if field < 0.0 {
+            return None;
+        }
+
+        value += field / unit;
+        unit *= 60.0;
+    }
+
+    if num_fields == 0 {
+        None
+    } else {
+        Some(sign * value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_sexagesimal, sexagesimal_to_degrees, Pointing};
+    use crate::hdu::header::extension::image::Image;
+    use crate::test_utils::parse_header;
+
+    #[test]
+    fn sexagesimal_strings() {
+        assert_eq!(parse_sexagesimal("12:30:00"), Some(12.5));
+        assert_eq!(parse_sexagesimal("-00 30 36"), Some(-0.51));
+        assert_eq!(parse_sexagesimal("+10"), Some(10.0));
+        assert_eq!(parse_sexagesimal("1:2:3:4"), None);
+        assert_eq!(parse_sexagesimal("12:-30"), None);
+        assert_eq!(parse_sexagesimal("  "), None);
+    }
+
+    #[test]
+    fn negative_zero_sexagesimal() {
+        assert_eq!(parse_sexagesimal("-00:30:00"), Some(-0.5));
+        assert_eq!(parse_sexagesimal("-0 0 36"), Some(-0.01));
+        assert_eq!(sexagesimal_to_degrees("-00:00:36", false), Some(-0.01));
+        assert_eq!(sexagesimal_to_degrees("-00:04:00", true), Some(-1.0));
+
+        let zero = parse_sexagesimal("-00:00:00").unwrap();
+        assert!(zero == 0.0 && zero.is_sign_negative());
+    }
+
+    #[test]
+    fn sexagesimal_angles() {
+        assert_eq!(sexagesimal_to_degrees("12:30:00", true), Some(187.5));
+        assert_eq!(sexagesimal_to_degrees("+41 15 00", false), Some(41.25));
+        assert_eq!(sexagesimal_to_degrees("12h30m", true), None);
+    }
+
+    #[test]
+    fn pointing_from_sexagesimal_cards() {
+        let header = parse_header::<Image>(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"OBJECT  = 'M31     '                                                            ",
+            b"RA      = '00:42:44.4'                                                          ",
+            b"DEC     = '+41:16:12'                                                           ",
+            b"CRVAL1  =                  0.0                                                  ",
+            b"CRVAL2  =                  0.0                                                  ",
+            b"END                                                                             ",
+        ]);
+        let Some(Pointing { object, ra, dec }) = header.pointing() else {
+            panic!("expected a pointing");
+        };
+        assert_eq!(object.as_deref(), Some("M31"));
+        assert!((ra - 10.685).abs() < 1e-9);
+        assert!((dec - 41.27).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pointing_from_crval_cards() {
+        let header = parse_header::<Image>(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"CRVAL1  =                 83.6                                                  ",
+            b"CRVAL2  =                   22                                                  ",
+            b"END                                                                             ",
+        ]);
+        assert_eq!(
+            header.pointing(),
+            Some(Pointing {
+                object: None,
+                ra: 83.6,
+                dec: 22.0
+            })
+        );
+
+        let header = parse_header::<Image>(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                    8                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"OBJECT  = 'M31     '                                                            ",
+            b"END                                                                             ",
+        ]);
+        assert_eq!(header.pointing(), None);
+    }
+}
@@ -6,7 +6,7 @@ pub mod primary;
 use std::convert::TryFrom;
 use std::io::Seek;
 
-use futures::AsyncRead;
+use futures::{AsyncRead, AsyncReadExt};
 
 use crate::card::Card;
 use crate::card::CardBuf;
@@ -22,7 +22,9 @@ use self::header::consume_next_card_async;
 use self::header::extension::asciitable::AsciiTable;
 use self::header::extension::bintable::BinTable;
 use self::header::extension::image::Image;
+use self::header::extension::unknown::Unknown;
 use self::header::extension::XtensionType;
+use self::header::ValueMap;
 use crate::hdu::Value::Logical;
 
 //use super::data::DataAsyncBufRead;
@@ -30,7 +32,7 @@ use crate::hdu::Value::Logical;
 use crate::async_fits;
 use crate::fits;
 use crate::hdu::primary::consume_next_card;
-use log::error;
+use log::{error, warn};
 /// An enumeration of the supported FITS Header Data Unit types.
 #[derive(Debug, PartialEq)]
 pub enum HDU {
@@ -42,15 +44,28 @@ pub enum HDU {
     XBinaryTable(fits::HDU<BinTable>),
     /// HDU ASCII table extension
     XASCIITable(fits::HDU<AsciiTable>),
+    /// HDU extension of an unsupported or obsolete type, e.g. `A3DTABLE`, whose data unit is kept as raw bytes
+    XUnknown(fits::HDU<Unknown>),
 }
 
 use std::io::Read;
-fn consume_cards<R>(reader: &mut R, num_bytes_read: &mut usize) -> Result<Vec<Card>, Error>
+/// Read the cards of a header until the `END` card, together with the bytes of each card
+///
+/// # Params
+/// * `reader` - the reader positioned at the beginning of the header
+/// * `num_bytes_read` - incremented by the number of bytes read
+/// * `strict` - whether a card that cannot be parsed gives an error instead of being kept as undefined
+fn consume_cards<R>(
+    reader: &mut R,
+    num_bytes_read: &mut usize,
+    strict: bool,
+) -> Result<(Vec<Card>, Vec<CardBuf>), Error>
 where
     R: Read,
 {
     let mut card_80_bytes_buf: CardBuf = [0; 80];
     let mut cards = Vec::new();
+    let mut raw_cards = Vec::new();
 
     /* Consume cards until `END` is reached */
     loop {
@@ -60,31 +75,126 @@ where
                 error!("Fail reading the header without encountering the END card");
             })?;
 
-        if let Ok(card) = Card::try_from(&card_80_bytes_buf) {
-            cards.push(card);
-            if Some(&Card::End) == cards.last() {
-                break;
+        let num_line_ending_bytes = count_line_ending_bytes(&card_80_bytes_buf);
+        if num_line_ending_bytes > 0 {
+            if strict {
+                return Err(Error::CardParse {
+                    card_index: cards.len(),
+                    keyword: String::new(),
+                    offset: (*num_bytes_read - 80) as u64,
+                    cause: Box::new(Error::StaticError(
+                        "Line ending bytes found between cards, the header is not aligned to 80 bytes",
+                    )),
+                });
+            }
+
+            realign_card(reader, &mut card_80_bytes_buf, num_line_ending_bytes)?;
+            warn!(
+                "Line ending bytes found before card {} have been skipped",
+                cards.len()
+            );
+        }
+
+        raw_cards.push(card_80_bytes_buf);
+        match Card::try_from(&card_80_bytes_buf) {
+            Ok(card) => {
+                cards.push(card);
+                if Some(&Card::End) == cards.last() {
+                    break;
+                }
+            }
+            Err(e) if strict => {
+                return Err(Error::CardParse {
+                    card_index: cards.len(),
+                    keyword: String::from_utf8_lossy(&card_80_bytes_buf[..8])
+                        .trim_end()
+                        .to_owned(),
+                    offset: cards.len() as u64 * 80,
+                    cause: Box::new(e),
+                });
+            }
+            Err(e) => {
+                // A malformed card does not abort the parsing of the header,
+                // the unparsable card is preserved as an undefined one
+                warn!(
+                    "Card {} could not be parsed and is kept as undefined: {e}",
+                    cards.len()
+                );
+                let card =
+                    Card::Undefined(String::from_utf8_lossy(&card_80_bytes_buf).into_owned());
+                cards.push(card);
             }
-        } else {
-            // FIXME log warning
-            // preserve the unparsable header
-            let card = Card::Undefined(String::from_utf8_lossy(&card_80_bytes_buf).into_owned());
-            cards.push(card);
         }
     }
 
-    Ok(cards)
+    Ok((cards, raw_cards))
+}
+
+/// Skip the CR/LF bytes found at the beginning of a card, e.g. introduced by an ASCII mode transfer
+///
+/// The card is shifted and completed with the next bytes of the reader until it does not start
+/// with a line ending byte anymore.
+///
+/// # Params
+/// * `reader` - the reader positioned just after the card
+/// * `card` - the card to realign
+/// * `num_line_ending_bytes` - the number of line ending bytes the card starts with
+fn realign_card<R>(
+    reader: &mut R,
+    card: &mut CardBuf,
+    mut num_line_ending_bytes: usize,
+) -> Result<(), Error>
+where
+    R: Read,
+{
+    while num_line_ending_bytes > 0 {
+        card.copy_within(num_line_ending_bytes.., 0);
+        reader.read_exact(&mut card[80 - num_line_ending_bytes..])?;
+
+        num_line_ending_bytes = count_line_ending_bytes(card);
+    }
+
+    Ok(())
+}
+
+/// Async version of [realign_card]
+async fn realign_card_async<R>(
+    reader: &mut R,
+    card: &mut CardBuf,
+    mut num_line_ending_bytes: usize,
+) -> Result<(), Error>
+where
+    R: AsyncRead + std::marker::Unpin,
+{
+    while num_line_ending_bytes > 0 {
+        card.copy_within(num_line_ending_bytes.., 0);
+        reader
+            .read_exact(&mut card[80 - num_line_ending_bytes..])
+            .await?;
+
+        num_line_ending_bytes = count_line_ending_bytes(card);
+    }
+
+    Ok(())
+}
+
+/// Count the CR and LF bytes a card starts with
+fn count_line_ending_bytes(card: &CardBuf) -> usize {
+    card.iter()
+        .take_while(|&&b| b == b'\r' || b == b'\n')
+        .count()
 }
 
 async fn consume_cards_async<R>(
     reader: &mut R,
     num_bytes_read: &mut usize,
-) -> Result<Vec<Card>, Error>
+) -> Result<(Vec<Card>, Vec<CardBuf>), Error>
 where
     R: AsyncRead + std::marker::Unpin,
 {
     let mut card_80_bytes_buf: CardBuf = [0; 80];
     let mut cards = Vec::new();
+    let mut raw_cards = Vec::new();
 
     /* Consume cards until `END` is reached */
     loop {
@@ -94,71 +204,107 @@ where
             .map_err(|_| {
                 Error::StaticError("Fail reading the header without encountering the END card")
             })?;
-        if let Ok(card) = Card::try_from(&card_80_bytes_buf) {
-            cards.push(card);
-            if Some(&Card::End) == cards.last() {
-                break;
+
+        let num_line_ending_bytes = count_line_ending_bytes(&card_80_bytes_buf);
+        if num_line_ending_bytes > 0 {
+            realign_card_async(reader, &mut card_80_bytes_buf, num_line_ending_bytes).await?;
+            warn!(
+                "Line ending bytes found before card {} have been skipped",
+                cards.len()
+            );
+        }
+
+        raw_cards.push(card_80_bytes_buf);
+        match Card::try_from(&card_80_bytes_buf) {
+            Ok(card) => {
+                cards.push(card);
+                if Some(&Card::End) == cards.last() {
+                    break;
+                }
+            }
+            Err(e) => {
+                // A malformed card does not abort the parsing of the header,
+                // the unparsable card is preserved as an undefined one
+                warn!(
+                    "Card {} could not be parsed and is kept as undefined: {e}",
+                    cards.len()
+                );
+                let card =
+                    Card::Undefined(String::from_utf8_lossy(&card_80_bytes_buf).into_owned());
+                cards.push(card);
             }
-        } else {
-            // FIXME log warning
-            // preserve the unparsable header
-            let card = Card::Undefined(String::from_utf8_lossy(&card_80_bytes_buf).into_owned());
-            cards.push(card);
         }
     }
 
-    Ok(cards)
+    Ok((cards, raw_cards))
 }
 
 impl HDU {
     pub(crate) fn new_xtension<'a, R>(
         reader: &mut R,
         num_bytes_read: &mut usize,
+        strict: bool,
     ) -> Result<Self, Error>
     where
-        R: FitsRead<'a, Image> + FitsRead<'a, BinTable> + FitsRead<'a, AsciiTable> + Seek + 'a,
+        R: FitsRead<'a, Image>
+            + FitsRead<'a, BinTable>
+            + FitsRead<'a, AsciiTable>
+            + FitsRead<'a, Unknown>
+            + Seek
+            + 'a,
     {
-        let cards = consume_cards(reader, num_bytes_read)?;
+        let header_byte_offset = reader.stream_position()?;
+        let (cards, raw_cards) = consume_cards(reader, num_bytes_read, strict)
+            .map_err(|e| e.offset_by(header_byte_offset))?;
         // Check only the the first card. Even if not FITS valid we could accept
         // it if its xtension card is down in the header.
         match &cards[0] {
             Card::Xtension {
                 x: XtensionType::Image,
                 ..
-            } => Ok(HDU::XImage(fits::HDU::<Image>::new(
-                reader,
-                num_bytes_read,
-                cards,
-            )?)),
+            } => Ok(HDU::XImage(
+                fits::HDU::<Image>::new(reader, header_byte_offset, num_bytes_read, cards)?
+                    .with_raw_cards(raw_cards),
+            )),
             Card::Xtension {
                 x: XtensionType::BinTable,
                 ..
-            } => Ok(HDU::XBinaryTable(fits::HDU::<BinTable>::new(
-                reader,
-                num_bytes_read,
-                cards,
-            )?)),
+            } => Ok(HDU::XBinaryTable(
+                fits::HDU::<BinTable>::new(reader, header_byte_offset, num_bytes_read, cards)?
+                    .with_raw_cards(raw_cards),
+            )),
             Card::Xtension {
                 x: XtensionType::AsciiTable,
                 ..
-            } => Ok(HDU::XASCIITable(fits::HDU::<AsciiTable>::new(
-                reader,
-                num_bytes_read,
-                cards,
-            )?)),
+            } => Ok(HDU::XASCIITable(
+                fits::HDU::<AsciiTable>::new(reader, header_byte_offset, num_bytes_read, cards)?
+                    .with_raw_cards(raw_cards),
+            )),
+            Card::Xtension {
+                x: XtensionType::Unknown(name),
+                ..
+            } => {
+                warn!("{name} extension is not supported, its data unit is kept as raw bytes");
+                Ok(HDU::XUnknown(
+                    fits::HDU::<Unknown>::new(reader, header_byte_offset, num_bytes_read, cards)?
+                        .with_raw_cards(raw_cards),
+                ))
+            }
             _ => Err(Error::StaticError(
                 "XTENSION card has not been found in the header",
             )),
         }
     }
 
-    pub(crate) fn new_primary<'a, R>(reader: &mut R) -> Result<Self, Error>
+    pub(crate) fn new_primary<'a, R>(reader: &mut R, strict: bool) -> Result<Self, Error>
     where
         R: FitsRead<'a, Image> + Seek + 'a,
     {
         let mut num_bytes_read = 0;
 
-        let cards = consume_cards(reader, &mut num_bytes_read)?;
+        let header_byte_offset = reader.stream_position()?;
+        let (cards, raw_cards) = consume_cards(reader, &mut num_bytes_read, strict)
+            .map_err(|e| e.offset_by(header_byte_offset))?;
 
         // Check for SIMPLE keyword
         if let Card::Value {
@@ -168,11 +314,15 @@ impl HDU {
         } = &cards[0]
         {
             if name == "SIMPLE" {
-                Ok(HDU::Primary(fits::HDU::<Image>::new(
-                    reader,
-                    &mut num_bytes_read,
-                    cards,
-                )?))
+                Ok(HDU::Primary(
+                    fits::HDU::<Image>::new(
+                        reader,
+                        header_byte_offset,
+                        &mut num_bytes_read,
+                        cards,
+                    )?
+                    .with_raw_cards(raw_cards),
+                ))
             } else {
                 // TODO log the card to stderr
                 Err(Error::DynamicError(format!(
@@ -185,11 +335,50 @@ impl HDU {
         }
     }
 
+    /// Get the binary table of a HDU storing a tile compressed image
+    ///
+    /// Returns `None` if the HDU is not a binary table following the tiled image compression convention.
+    /// The table can be read without decompressing the image to inspect the tiles metadata.
+    pub fn as_compressed_table(&self) -> Option<&fits::HDU<BinTable>> {
+        match self {
+            HDU::XBinaryTable(hdu)
+                if hdu
+                    .get_header()
+                    .get_xtension()
+                    .get_tile_compressed_image()
+                    .is_some() =>
+            {
+                Some(hdu)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the values of the cards of the header, whatever the type of the HDU
+    pub(crate) fn get_values(&self) -> &ValueMap {
+        match self {
+            HDU::Primary(hdu) | HDU::XImage(hdu) => hdu.get_header(),
+            HDU::XBinaryTable(hdu) => hdu.get_header(),
+            HDU::XASCIITable(hdu) => hdu.get_header(),
+            HDU::XUnknown(hdu) => hdu.get_header(),
+        }
+    }
+
+    pub fn get_header_byte_offset(&self) -> u64 {
+        match self {
+            HDU::Primary(hdu) | HDU::XImage(hdu) => hdu.get_header_byte_offset(),
+            HDU::XBinaryTable(hdu) => hdu.get_header_byte_offset(),
+            HDU::XASCIITable(hdu) => hdu.get_header_byte_offset(),
+            HDU::XUnknown(hdu) => hdu.get_header_byte_offset(),
+        }
+    }
+
     pub fn get_data_unit_byte_offset(&self) -> u64 {
         match self {
             HDU::Primary(hdu) | HDU::XImage(hdu) => hdu.get_data_unit_byte_offset(),
             HDU::XBinaryTable(hdu) => hdu.get_data_unit_byte_offset(),
             HDU::XASCIITable(hdu) => hdu.get_data_unit_byte_offset(),
+            HDU::XUnknown(hdu) => hdu.get_data_unit_byte_offset(),
         }
     }
     pub fn get_data_unit_byte_size(&self) -> u64 {
@@ -197,6 +386,7 @@ impl HDU {
             HDU::Primary(hdu) | HDU::XImage(hdu) => hdu.get_data_unit_byte_size(),
             HDU::XBinaryTable(hdu) => hdu.get_data_unit_byte_size(),
             HDU::XASCIITable(hdu) => hdu.get_data_unit_byte_size(),
+            HDU::XUnknown(hdu) => hdu.get_data_unit_byte_size(),
         }
     }
 }
@@ -207,6 +397,8 @@ pub enum AsyncHDU {
     XImage(async_fits::AsyncHDU<Image>),
     XBinaryTable(crate::async_fits::AsyncHDU<BinTable>),
     XASCIITable(crate::async_fits::AsyncHDU<AsciiTable>),
+    /// HDU extension of an unsupported or obsolete type, e.g. `A3DTABLE`, whose data unit is streamed as raw bytes
+    XUnknown(crate::async_fits::AsyncHDU<Unknown>),
 }
 
 impl AsyncHDU {
@@ -215,11 +407,12 @@ impl AsyncHDU {
         R: AsyncDataBufRead<'a, Image>
             + AsyncDataBufRead<'a, BinTable>
             + AsyncDataBufRead<'a, AsciiTable>
+            + AsyncDataBufRead<'a, Unknown>
             + 'a,
     {
         let mut num_bytes_read = 0;
 
-        let cards = consume_cards_async(reader, &mut num_bytes_read).await?;
+        let (cards, raw_cards) = consume_cards_async(reader, &mut num_bytes_read).await?;
         // Check only the if the first card. Even if not FITS valid we could accept
         // it if its xtension card is down in the header.
         let hdu = match &cards[0] {
@@ -227,20 +420,37 @@ impl AsyncHDU {
                 x: XtensionType::Image,
                 ..
             } => AsyncHDU::XImage(
-                async_fits::AsyncHDU::<Image>::new(reader, &mut num_bytes_read, cards).await?,
+                async_fits::AsyncHDU::<Image>::new(reader, &mut num_bytes_read, cards)
+                    .await?
+                    .with_raw_cards(raw_cards),
             ),
             Card::Xtension {
                 x: XtensionType::BinTable,
                 ..
             } => AsyncHDU::XBinaryTable(
-                async_fits::AsyncHDU::<BinTable>::new(reader, &mut num_bytes_read, cards).await?,
+                async_fits::AsyncHDU::<BinTable>::new(reader, &mut num_bytes_read, cards)
+                    .await?
+                    .with_raw_cards(raw_cards),
             ),
             Card::Xtension {
                 x: XtensionType::AsciiTable,
                 ..
             } => AsyncHDU::XASCIITable(
-                async_fits::AsyncHDU::<AsciiTable>::new(reader, &mut num_bytes_read, cards).await?,
+                async_fits::AsyncHDU::<AsciiTable>::new(reader, &mut num_bytes_read, cards)
+                    .await?
+                    .with_raw_cards(raw_cards),
             ),
+            Card::Xtension {
+                x: XtensionType::Unknown(name),
+                ..
+            } => {
+                warn!("{name} extension is not supported, its data unit is kept as raw bytes");
+                AsyncHDU::XUnknown(
+                    async_fits::AsyncHDU::<Unknown>::new(reader, &mut num_bytes_read, cards)
+                        .await?
+                        .with_raw_cards(raw_cards),
+                )
+            }
             _ => {
                 return Err(Error::StaticError(
                     "XTENSION card has not been found in the header",
@@ -257,7 +467,7 @@ impl AsyncHDU {
     {
         let mut num_bytes_read = 0;
 
-        let cards = consume_cards_async(reader, &mut num_bytes_read).await?;
+        let (cards, raw_cards) = consume_cards_async(reader, &mut num_bytes_read).await?;
 
         // Check for SIMPLE keyword
         let _name: String = "SIMPLE".to_owned();
@@ -268,7 +478,9 @@ impl AsyncHDU {
         } = &cards[0]
         {
             Ok(AsyncHDU::Primary(
-                async_fits::AsyncHDU::<Image>::new(reader, &mut num_bytes_read, cards).await?,
+                async_fits::AsyncHDU::<Image>::new(reader, &mut num_bytes_read, cards)
+                    .await?
+                    .with_raw_cards(raw_cards),
             ))
         } else {
             Err(Error::StaticError("not a FITSv4 file"))
@@ -0,0 +1,209 @@
+//! Integrity checks of an in-memory FITS file, similar to what `fitsverify` reports
+use std::io::Cursor;
+
+use crate::error::Error;
+use crate::fits::Fits;
+
+/// Integrity status of a HDU
+#[derive(Debug, Clone, PartialEq)]
+pub struct HduIntegrity {
+    /// The index of the HDU, `0` being the primary HDU
+    pub index: usize,
+    /// The byte index where the header starts
+    pub header_byte_offset: u64,
+    /// The number of data bytes declared by the header
+    pub declared_data_bytes: u64,
+    /// The number of data bytes found in the file
+    pub available_data_bytes: u64,
+    /// Whether the data unit is followed by the padding bytes completing its last 2880 bytes block
+    pub data_padded: bool,
+    /// Whether the `DATASUM` card matches the checksum of the data unit, `None` if the card is not found
+    pub datasum: Option<bool>,
+    /// Whether the `CHECKSUM` card verifies the whole HDU, `None` if the card is not found
+    pub checksum: Option<bool>,
+}
+
+impl HduIntegrity {
+    /// Check that the data unit is complete and that the checksums, when given, verify
+    pub fn is_valid(&self) -> bool {
+        self.declared_data_bytes == self.available_data_bytes
+            && self.data_padded
+            && self.datasum != Some(false)
+            && self.checksum != Some(false)
+    }
+}
+
+/// Integrity report of a FITS file given by [Fits::integrity_report]
+#[derive(Debug, PartialEq)]
+pub struct IntegrityReport {
+    /// The status of the HDUs successfully parsed
+    pub hdus: Vec<HduIntegrity>,
+    /// The error that stopped the parsing of the HDUs, e.g. a header not ended by an `END` card
+    /// or not padded to a multiple of 2880 bytes
+    pub error: Option<Error>,
+}
+
+impl IntegrityReport {
+    /// Check that the whole file has been parsed and that all its HDUs are valid
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && self.hdus.iter().all(HduIntegrity::is_valid)
+    }
+}
+
+impl Fits<Cursor<&[u8]>> {
+    /// Check the integrity of each HDU of the file
+    ///
+    /// The headers are parsed one after the other, which ensures they end with an `END` card and are
+    /// padded to a multiple of 2880 bytes. For each HDU, the size of the data unit declared in the header
+    /// is compared to the number of bytes available and the `DATASUM` and `CHECKSUM` cards are verified
+    /// when present. Contrary to the iteration over the HDUs, a truncated data unit does not stop the
+    /// report.
+    pub fn integrity_report(self) -> IntegrityReport {
+        let buf = self.get_buf();
+        let mut hdus = vec![];
+
+        let mut hdu_list = self;
+        for (index, hdu) in hdu_list.by_ref().enumerate() {
+            let hdu = match hdu {
+                Ok(hdu) => hdu,
+                Err(error) => {
+                    return IntegrityReport {
+                        hdus,
+                        error: Some(error),
+                    }
+                }
+            };
+
+            let header_byte_offset = hdu.get_header_byte_offset();
+            let data_start = (hdu.get_data_unit_byte_offset() as usize).min(buf.len());
+            let declared_data_bytes = hdu.get_data_unit_byte_size();
+            let data_end = (data_start + declared_data_bytes as usize).min(buf.len());
+            let padded_data_end = data_start + (declared_data_bytes as usize).div_ceil(2880) * 2880;
+
+            let values = hdu.get_values();
+
+            let data_unit = &buf[data_start..padded_data_end.min(buf.len())];
+            let datasum = values
+                .get_str("DATASUM")
+                .map(|s| s.trim().parse::<u32>().ok() == Some(checksum(data_unit, 0)));
+            let checksum = values.get_str("CHECKSUM").map(|_| {
+                let header = &buf[header_byte_offset as usize..data_start];
+                is_negative_zero(checksum(data_unit, checksum(header, 0)))
+            });
+
+            hdus.push(HduIntegrity {
+                index,
+                header_byte_offset,
+                declared_data_bytes,
+                available_data_bytes: (data_end - data_start) as u64,
+                data_padded: padded_data_end <= buf.len(),
+                datasum,
+                checksum,
+            });
+        }
+
+        IntegrityReport { hdus, error: None }
+    }
+}
+
+/// Check that the checksum of a whole HDU verifies, i.e. is negative zero in ones' complement
+///
+/// A positive zero is only the sum of bytes all equal to zero, which is not a valid HDU.
+fn is_negative_zero(sum: u32) -> bool {
+    sum == 0xFFFFFFFF
+}
+
+/// Compute the 32-bit ones' complement checksum of bytes as defined in the FITS standard
+///
+/// # Params
+/// * `bytes` - the bytes interpreted as big endian 32-bit words, the last one being padded with zeros
+/// * `init` - the checksum to accumulate with
+pub fn checksum(bytes: &[u8], init: u32) -> u32 {
+    let mut sum = init as u64;
+    for word in bytes.chunks(4) {
+        let mut w = [0_u8; 4];
+        w[..word.len()].copy_from_slice(word);
+        sum += u32::from_be_bytes(w) as u64;
+    }
+
+    // Wrap the carry bits around
+    while sum >> 32 != 0 {
+        sum = (sum & 0xFFFFFFFF) + (sum >> 32);
+    }
+    sum as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_negative_zero;
+    use crate::card::CardBuf;
+    use crate::fits::Fits;
+    use crate::test_utils::mock_hdu;
+
+    fn mock_fits_file(cards: &[&CardBuf]) -> Vec<u8> {
+        mock_hdu(cards, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10])
+    }
+
+    const CARDS: [&CardBuf; 7] = [
+        b"SIMPLE  =                    T                                                  ",
+        b"BITPIX  =                    8                                                  ",
+        b"NAXIS   =                    1                                                  ",
+        b"NAXIS1  =                   10                                                  ",
+        b"CHECKSUM= 'mAhLo9gKmAgKm7gK'                                                    ",
+        b"DATASUM = '252840460'                                                           ",
+        b"END                                                                             ",
+    ];
+
+    #[test]
+    fn valid_checksums() {
+        let buf = mock_fits_file(&CARDS);
+
+        let report = Fits::from_bytes(&buf).integrity_report();
+        assert!(report.is_valid());
+        assert_eq!(report.hdus.len(), 1);
+        assert_eq!(report.hdus[0].checksum, Some(true));
+        assert_eq!(report.hdus[0].datasum, Some(true));
+        assert_eq!(report.hdus[0].available_data_bytes, 10);
+    }
+
+    #[test]
+    fn corrupted_data() {
+        let mut buf = mock_fits_file(&CARDS);
+        buf[2880] = 0;
+
+        let report = Fits::from_bytes(&buf).integrity_report();
+        assert!(!report.is_valid());
+        assert_eq!(report.hdus[0].checksum, Some(false));
+        assert_eq!(report.hdus[0].datasum, Some(false));
+    }
+
+    #[test]
+    fn positive_zero_checksum() {
+        assert!(is_negative_zero(0xFFFFFFFF));
+        assert!(!is_negative_zero(0));
+    }
+
+    #[test]
+    fn truncated_data_unit() {
+        let buf = mock_fits_file(&[CARDS[0], CARDS[1], CARDS[2], CARDS[3], CARDS[6]]);
+        let buf = &buf[..2880 + 5];
+
+        let report = Fits::from_bytes(buf).integrity_report();
+        assert!(!report.is_valid());
+        assert_eq!(report.error, None);
+        let hdu = &report.hdus[0];
+        assert_eq!((hdu.declared_data_bytes, hdu.available_data_bytes), (10, 5));
+        assert!(!hdu.data_padded);
+        assert_eq!((hdu.checksum, hdu.datasum), (None, None));
+    }
+
+    #[test]
+    fn missing_end_card() {
+        let buf = mock_fits_file(&CARDS[..6]);
+
+        let report = Fits::from_bytes(&buf).integrity_report();
+        assert!(!report.is_valid());
+        assert!(report.hdus.is_empty());
+        assert!(report.error.is_some());
+    }
+}
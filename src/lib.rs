@@ -44,21 +44,31 @@ extern crate quick_error;
 
 pub mod async_fits;
 pub mod card;
+pub mod compression;
 pub mod error;
 pub mod file;
 pub mod fits;
+pub mod integrity;
+pub mod options;
 pub mod wcs;
 
 pub mod gz;
 pub mod hdu;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 pub use async_fits::AsyncFits;
 pub use file::FITSFile;
 pub use fits::Fits;
-pub use hdu::data::bintable::{DataValue, TableData, TableRowData};
-pub use hdu::data::image::{ImageData, Pixels};
+pub use hdu::data::bintable::{
+    BinTableBuilder, CellValue, ColumnValue, DataValue, EventList, NullPolicy, TableData,
+    TableRowData,
+};
+pub use hdu::data::image::{DataRange, ImageData, Pixels};
 pub use hdu::data::iter::It;
 pub use hdu::{AsyncHDU, HDU};
+pub use options::FitsOptions;
 pub use wcs::{ImgXY, LonLat, WCSParams, WCS};
 
 #[cfg(test)]
@@ -141,6 +151,7 @@ mod tests {
                 HDU::XASCIITable(_) => {
                     n_asciitable_ext += 1;
                 }
+                HDU::XUnknown(_) => (),
             };
             seen_byte_lengths.push(hdu.get_data_unit_byte_size());
             seen_byte_offsets.push(hdu.get_data_unit_byte_offset())
@@ -422,6 +433,12 @@ mod tests {
                     let num_bytes = hdu.get_header().get_xtension().get_num_bytes_data_block();
                     let bytes = hdu_list.get_data(&hdu);
 
+                    assert_eq!(num_bytes as usize, bytes.bytes().count());
+                }
+                HDU::XUnknown(hdu) => {
+                    let num_bytes = hdu.get_header().get_xtension().get_num_bytes_data_block();
+                    let bytes = hdu_list.get_data(&hdu);
+
                     assert_eq!(num_bytes as usize, bytes.bytes().count());
                 }
             }
@@ -537,6 +554,11 @@ mod tests {
                 AsyncHDU::XASCIITable(hdu) => {
                     let num_bytes = hdu.get_header().get_xtension().get_num_bytes_data_block();
 
+                    assert_eq!(num_bytes as usize, hdu_list.get_data(&hdu).count().await);
+                }
+                AsyncHDU::XUnknown(hdu) => {
+                    let num_bytes = hdu.get_header().get_xtension().get_num_bytes_data_block();
+
                     assert_eq!(num_bytes as usize, hdu_list.get_data(&hdu).count().await);
                 }
             }
@@ -581,6 +603,11 @@ mod tests {
                 HDU::XASCIITable(hdu) => {
                     let num_bytes = hdu.get_header().get_xtension().get_num_bytes_data_block();
 
+                    assert_eq!(num_bytes as usize, hdu_list.get_data(&hdu).bytes().count());
+                }
+                HDU::XUnknown(hdu) => {
+                    let num_bytes = hdu.get_header().get_xtension().get_num_bytes_data_block();
+
                     assert_eq!(num_bytes as usize, hdu_list.get_data(&hdu).bytes().count());
                 }
             }
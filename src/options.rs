@@ -0,0 +1,128 @@
+//! Options driving the parsing of a FITS file
+
+/// Options of the parsing of a FITS file given to [Fits::from_reader_with](crate::Fits::from_reader_with)
+///
+/// The default options are used by [Fits::from_reader](crate::Fits::from_reader).
+///
+/// ```
+/// use fitsrs::FitsOptions;
+///
+/// use fitsrs::options::FitsVersion;
+///
+/// let options = FitsOptions::new()
+///     .strict(true)
+///     .standard(FitsVersion::V3_0)
+///     .max_data_bytes(1 << 30)
+///     .verify_checksum(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FitsOptions {
+    strict: bool,
+    max_data_bytes: Option<u64>,
+    verify_checksum: bool,
+    standard: FitsVersion,
+    assume_little_endian: bool,
+}
+
+/// Version of the FITS standard whose rules are enforced in strict mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FitsVersion {
+    /// FITS 3.0 (2008), introducing the 64-bit integer `BITPIX = 64`
+    V3_0,
+    /// FITS 4.0 (2016), adding the long string `CONTINUE` cards, the 64-bit `Q` array descriptors
+    /// and the tiled image compression convention
+    #[default]
+    V4_0,
+}
+
+impl std::fmt::Display for FitsVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FitsVersion::V3_0 => write!(f, "FITS 3.0"),
+            FitsVersion::V4_0 => write!(f, "FITS 4.0"),
+        }
+    }
+}
+
+impl FitsOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail on cards that cannot be parsed instead of keeping them as [undefined](crate::card::Card::Undefined)
+    /// and on HDUs using features not allowed by the [standard](FitsOptions::standard) version
+    ///
+    /// Default: `false`
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Fail on HDUs whose data unit is bigger than a number of bytes, e.g. to protect against
+    /// corrupted headers declaring huge data units
+    ///
+    /// Default: no limit
+    pub fn max_data_bytes(mut self, max_data_bytes: u64) -> Self {
+        self.max_data_bytes = Some(max_data_bytes);
+        self
+    }
+
+    /// Verify the `CHECKSUM` and `DATASUM` cards of each HDU when present, failing if they do not match
+    ///
+    /// The whole HDU is read to compute its checksum, the reader being then moved back to the
+    /// beginning of the data unit.
+    ///
+    /// Default: `false`
+    pub fn verify_checksum(mut self, verify_checksum: bool) -> Self {
+        self.verify_checksum = verify_checksum;
+        self
+    }
+
+    /// Set the version of the FITS standard whose rules are enforced in [strict](FitsOptions::strict) mode
+    ///
+    /// Default: the latest supported version, i.e. [FitsVersion::V4_0]
+    pub fn standard(mut self, standard: FitsVersion) -> Self {
+        self.standard = standard;
+        self
+    }
+
+    /// Decode the pixels of the images in the little endian byte order
+    ///
+    /// **This violates the FITS standard**, which requires big endian data. It is a deliberate escape hatch
+    /// for the legacy files of known nonconforming producers and must not be used otherwise. The byte order
+    /// is applied to the images given by [Fits::get_data](crate::Fits::get_data) and
+    /// [Fits::get_data_chunks](crate::Fits::get_data_chunks). It is ignored in [strict](FitsOptions::strict) mode.
+    ///
+    /// Default: `false`
+    pub fn assume_little_endian(mut self, assume_little_endian: bool) -> Self {
+        self.assume_little_endian = assume_little_endian;
+        self
+    }
+
+    /// Whether cards that cannot be parsed give an error
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Get the maximum number of bytes of a data unit, `None` meaning no limit
+    pub fn get_max_data_bytes(&self) -> Option<u64> {
+        self.max_data_bytes
+    }
+
+    /// Whether the checksums of the HDUs are verified
+    pub fn is_verifying_checksum(&self) -> bool {
+        self.verify_checksum
+    }
+
+    /// Get the version of the FITS standard enforced in strict mode
+    pub fn get_standard(&self) -> FitsVersion {
+        self.standard
+    }
+
+    /// Whether the pixels of the images are decoded in the little endian byte order, i.e.
+    /// [assume_little_endian](FitsOptions::assume_little_endian) is set outside of strict mode
+    pub fn is_assuming_little_endian(&self) -> bool {
+        self.assume_little_endian && !self.strict
+    }
+}
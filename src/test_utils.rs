@@ -0,0 +1,123 @@
+//! In-memory FITS files shared by the unit tests
+
+use crate::card::{Card, CardBuf};
+use crate::fits::{self, Fits};
+use crate::hdu::header::extension::bintable::BinTable;
+use crate::hdu::header::extension::image::Image;
+use crate::hdu::header::extension::Xtension;
+use crate::hdu::header::Header;
+use crate::hdu::HDU;
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+/// The header of a primary HDU without data
+const EMPTY_PRIMARY: [&CardBuf; 4] = [
+    b"SIMPLE  =                    T                                                  ",
+    b"BITPIX  =                    8                                                  ",
+    b"NAXIS   =                    0                                                  ",
+    b"END                                                                             ",
+];
+
+/// Write the cards of a header padded with spaces to a whole number of blocks
+pub(crate) fn mock_header(cards: &[impl AsRef<[u8]>]) -> Vec<u8> {
+    let mut bytes = cards
+        .iter()
+        .flat_map(|card| card.as_ref().iter())
+        .copied()
+        .collect::<Vec<_>>();
+    bytes.resize(bytes.len().div_ceil(2880).max(1) * 2880, b' ');
+    bytes
+}
+
+/// Write an HDU made of the cards of its header followed by its data unit padded with zeros
+pub(crate) fn mock_hdu(cards: &[impl AsRef<[u8]>], data: &[u8]) -> Vec<u8> {
+    let mut bytes = mock_header(cards);
+    bytes.extend(data);
+    bytes.resize(bytes.len().div_ceil(2880) * 2880, 0);
+    bytes
+}
+
+/// Write an empty primary HDU followed by an extension
+pub(crate) fn mock_extension(cards: &[impl AsRef<[u8]>], data: &[u8]) -> Vec<u8> {
+    let mut bytes = mock_header(&EMPTY_PRIMARY);
+    bytes.extend(mock_hdu(cards, data));
+    bytes
+}
+
+/// Parse the cards of a header
+pub(crate) fn parse_header<X>(cards: &[impl AsRef<[u8]>]) -> Header<X>
+where
+    X: Xtension + std::fmt::Debug,
+{
+    let cards = cards
+        .iter()
+        .map(|c| Card::try_from(<&CardBuf>::try_from(c.as_ref()).unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    Header::parse(cards).unwrap()
+}
+
+/// Write a card from its text padded with spaces to 80 bytes
+fn card(text: &str) -> CardBuf {
+    let mut card = [b' '; 80];
+    card[..text.len()].copy_from_slice(text.as_bytes());
+    card
+}
+
+/// Build the header of a binary table from the `(TTYPEn, TFORMn)` pairs of its fields
+///
+/// No `TTYPEn` card is written for an empty name. The `others` cards, e.g. `TSCALn` or `THEAP`,
+/// are written before the `END` card.
+pub(crate) fn bintable_cards(
+    naxis1: usize,
+    naxis2: usize,
+    pcount: usize,
+    fields: &[(&str, &str)],
+    others: &[&str],
+) -> Vec<CardBuf> {
+    let mut cards = vec![
+        card("XTENSION= 'BINTABLE'"),
+        card("BITPIX  =                    8"),
+        card("NAXIS   =                    2"),
+        card(&format!("NAXIS1  = {naxis1:>20}")),
+        card(&format!("NAXIS2  = {naxis2:>20}")),
+        card(&format!("PCOUNT  = {pcount:>20}")),
+        card("GCOUNT  =                    1"),
+        card(&format!("TFIELDS = {:>20}", fields.len())),
+    ];
+    for (i, (ttype, tform)) in fields.iter().enumerate() {
+        if !ttype.is_empty() {
+            cards.push(card(&format!(
+                "{:<8}= '{ttype:<8}'",
+                format!("TTYPE{}", i + 1)
+            )));
+        }
+        cards.push(card(&format!(
+            "{:<8}= '{tform:<8}'",
+            format!("TFORM{}", i + 1)
+        )));
+    }
+    cards.extend(others.iter().map(|text| card(text)));
+    cards.push(card("END"));
+    cards
+}
+
+/// Open an in-memory FITS file and read its primary HDU
+pub(crate) fn first_primary(buf: &[u8]) -> (Fits<Cursor<&[u8]>>, fits::HDU<Image>) {
+    let mut hdu_list = Fits::from_bytes(buf);
+    let Some(Ok(HDU::Primary(hdu))) = hdu_list.next() else {
+        panic!("expected a primary HDU");
+    };
+    (hdu_list, hdu)
+}
+
+/// Open an in-memory FITS file and read its first binary table, skipping the HDUs before it
+pub(crate) fn first_bintable(buf: &[u8]) -> (Fits<Cursor<&[u8]>>, fits::HDU<BinTable>) {
+    let mut hdu_list = Fits::from_bytes(buf);
+    let hdu = hdu_list
+        .find_map(|hdu| match hdu.unwrap() {
+            HDU::XBinaryTable(hdu) => Some(hdu),
+            _ => None,
+        })
+        .expect("expected a binary table");
+    (hdu_list, hdu)
+}
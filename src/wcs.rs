@@ -1,12 +1,15 @@
+use crate::card::Value;
 use crate::error::Error;
 use crate::hdu::header::extension::image::Image;
 use crate::hdu::header::Header;
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
 pub type ImgXY = wcs::ImgXY;
 pub type LonLat = wcs::LonLat;
 
 use crate::fits::HDU;
+use serde::de::value::MapDeserializer;
 use serde::de::IntoDeserializer;
 use serde::Deserialize;
 use std::convert::TryInto;
@@ -17,6 +20,288 @@ impl HDU<Image> {
     pub fn wcs(&self) -> Result<WCS, Error> {
         self.get_header().try_into()
     }
+
+    /// Try to look for an alternate WCS in the image header
+    ///
+    /// Alternate descriptions are made of the WCS cards suffixed by a letter, e.g. `CTYPE1A`, `CRVAL1A`.
+    /// The cards of the default description (i.e. without suffix) are ignored.
+    ///
+    /// # Params
+    /// * `alt` - the letter identifying the description, from `A` to `Z`
+    pub fn wcs_alt(&self, alt: char) -> Result<WCS, Error> {
+        if !alt.is_ascii_uppercase() {
+            return Err(Error::DynamicError(format!(
+                "Alternate WCS must be identified by a letter from A to Z, found '{alt}'"
+            )));
+        }
+
+        let header = self.get_header();
+        let cards = header.iter().filter_map(|(key, value)| {
+            match key.strip_suffix(alt) {
+                Some(stem) if is_alt_wcs_keyword(stem) => Some((stem, value)),
+                // Ignore the default description
+                _ if is_alt_wcs_keyword(key) => None,
+                _ => Some((key, value)),
+            }
+        });
+
+        let params = WCSParams::deserialize(MapDeserializer::<_, Error>::new(cards))?;
+        WCS::new(&params).map_err(|e| e.into())
+    }
+
+    /// Get the letters of the alternate WCS found in the image header
+    ///
+    /// An alternate description is detected by its `CTYPE1<alt>` card
+    pub fn wcs_alts(&self) -> Vec<char> {
+        let header = self.get_header();
+        ('A'..='Z')
+            .filter(|alt| header.get(&format!("CTYPE1{alt}")).is_some())
+            .collect()
+    }
+
+    /// Get the number of axes of the WCS given by the `WCSAXES` card, defaulting to `NAXIS`
+    ///
+    /// It can differ from `NAXIS`, e.g. for a 2D image extracted from a spectral cube that keeps
+    /// a degenerate third WCS axis.
+    pub fn wcs_axes(&self) -> Result<usize, Error> {
+        wcs_axes(self.get_header())
+    }
+
+    /// Get human-friendly labels of the WCS axes for display, e.g. `Right Ascension (deg)` or `Wavelength (nm)`
+    ///
+    /// The label is derived from the coordinate type of the `CTYPEi` card and the unit of the `CUNITi` card,
+    /// celestial coordinates being expressed in degrees by default. Unknown coordinate types are kept
+    /// as they are written.
+    pub fn axis_labels(&self) -> Result<Vec<String>, Error> {
+        axis_labels(self.get_header())
+    }
+
+    /// Get the random and systematic errors of the world coordinates given by the `CRDERi` and `CSYERi` cards
+    ///
+    /// Only the values are extracted, no error is propagated through the projection.
+    pub fn coordinate_errors(&self) -> Result<CoordinateErrors, Error> {
+        coordinate_errors(self.get_header())
+    }
+
+    /// Get the projection parameters given by the `PVi_m` cards, keyed by `(i, m)`
+    ///
+    /// `i` is the index of the axis starting from 1 and `m` the index of the parameter starting
+    /// from 0. The parameters are already taken into account by the projections of [HDU::wcs].
+    pub fn pv_params(&self) -> Result<BTreeMap<(usize, usize), f64>, Error> {
+        pv_params(self.get_header())
+    }
+
+    /// Look for a linear spectral axis in the image header
+    ///
+    /// The first axis whose `CTYPEi` is a spectral coordinate type (e.g. `FREQ` or `WAVE`) is returned.
+    /// Non-linear spectral axes, i.e. having an algorithm code such as `WAVE-F2W` or `FREQ-LOG`,
+    /// are not supported yet and give an error.
+    pub fn spectral_wcs(&self) -> Result<SpectralWcs, Error> {
+        SpectralWcs::parse(self.get_header())
+    }
+
+    /// Look for the Simple Imaging Polynomial (SIP) distortion coefficients in the image header
+    ///
+    /// Returns `None` if `CTYPE1` does not end with `-SIP`
+    pub fn sip(&self) -> Result<Option<Sip>, Error> {
+        Sip::parse(self.get_header())
+    }
+
+    /// Look for a TPV polynomial distortion, e.g. written by SCAMP or Astrometry.net, in the image header
+    ///
+    /// Returns `None` if `CTYPE1` does not end with `-TPV`
+    pub fn tpv(&self) -> Result<Option<Tpv>, Error> {
+        Tpv::parse(self.get_header())
+    }
+
+    /// Get the linear part of the WCS of the first `N` axes, i.e. the `(matrix, crpix, crval)` triplet
+    ///
+    /// The matrix transforms the pixel offsets from `crpix` into intermediate world coordinates. It is
+    /// given by the `CDi_j` cards if any, otherwise by `CDELTi * PCi_j`, the legacy `CROTA2` rotation
+    /// being applied to the two first axes when no `PCi_j` card is found. The missing cards take the
+    /// default values of the standard. No projection is applied.
+    pub fn linear_transform<const N: usize>(&self) -> Result<LinearTransform<N>, Error> {
+        linear_transform(self.get_header())
+    }
+}
+
+/// The `(matrix, crpix, crval)` linear part of a WCS returned by [HDU::linear_transform]
+pub type LinearTransform<const N: usize> = ([[f64; N]; N], [f64; N], [f64; N]);
+
+fn linear_transform<const N: usize>(header: &Header<Image>) -> Result<LinearTransform<N>, Error> {
+    let get = |key: String| header.get_parsed::<Option<f64>>(&key);
+
+    let mut crpix = [0.0; N];
+    let mut crval = [0.0; N];
+    let mut cdelt = [1.0; N];
+    for i in 0..N {
+        crpix[i] = get(format!("CRPIX{}", i + 1))?.unwrap_or(0.0);
+        crval[i] = get(format!("CRVAL{}", i + 1))?.unwrap_or(0.0);
+        cdelt[i] = get(format!("CDELT{}", i + 1))?.unwrap_or(1.0);
+    }
+
+    let mut cd = [[0.0; N]; N];
+    let mut pc = [[0.0; N]; N];
+    let (mut has_cd, mut has_pc) = (false, false);
+    for i in 0..N {
+        pc[i][i] = 1.0;
+        for j in 0..N {
+            if let Some(v) = get(format!("CD{}_{}", i + 1, j + 1))? {
+                cd[i][j] = v;
+                has_cd = true;
+            }
+            if let Some(v) = get(format!("PC{}_{}", i + 1, j + 1))? {
+                pc[i][j] = v;
+                has_pc = true;
+            }
+        }
+    }
+
+    if has_cd {
+        return Ok((cd, crpix, crval));
+    }
+
+    if !has_pc && N >= 2 {
+        if let Some(crota) = get("CROTA2".to_owned())? {
+            let (sin, cos) = crota.to_radians().sin_cos();
+            pc[0][0] = cos;
+            pc[0][1] = -sin * cdelt[1] / cdelt[0];
+            pc[1][0] = sin * cdelt[0] / cdelt[1];
+            pc[1][1] = cos;
+        }
+    }
+
+    let mut matrix = pc;
+    for (row, cdelt) in matrix.iter_mut().zip(cdelt) {
+        for v in row.iter_mut() {
+            *v *= cdelt;
+        }
+    }
+
+    Ok((matrix, crpix, crval))
+}
+
+fn pv_params(header: &Header<Image>) -> Result<BTreeMap<(usize, usize), f64>, Error> {
+    let mut params = BTreeMap::new();
+    for (key, _) in header.iter() {
+        let Some((i, m)) = key
+            .strip_prefix("PV")
+            .and_then(|indices| indices.split_once('_'))
+        else {
+            continue;
+        };
+
+        if let (Ok(i), Ok(m)) = (i.parse::<usize>(), m.parse::<usize>()) {
+            params.insert((i, m), header.get_parsed::<f64>(key)?);
+        }
+    }
+
+    Ok(params)
+}
+
+fn wcs_axes(header: &Header<Image>) -> Result<usize, Error> {
+    let naxis = header.get_xtension().get_naxis().len();
+    Ok(header
+        .get_parsed::<Option<usize>>("WCSAXES")?
+        .unwrap_or(naxis))
+}
+
+/// The errors of the world coordinates returned by [HDU::coordinate_errors], one value per WCS axis
+///
+/// An array is `None` if no card of its kind is found in the header. Otherwise, the axes without
+/// a card take the default error of `0.0`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CoordinateErrors {
+    /// The random errors given by the `CRDERi` cards
+    pub random: Option<Vec<f64>>,
+    /// The systematic errors given by the `CSYERi` cards
+    pub systematic: Option<Vec<f64>>,
+}
+
+fn coordinate_errors(header: &Header<Image>) -> Result<CoordinateErrors, Error> {
+    let num_axes = wcs_axes(header)?;
+    let per_axis = |prefix: &str| -> Result<Option<Vec<f64>>, Error> {
+        let errors = (1..=num_axes)
+            .map(|i| header.get_parsed::<Option<f64>>(&format!("{prefix}{i}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(errors
+            .iter()
+            .any(Option::is_some)
+            .then(|| errors.iter().map(|e| e.unwrap_or(0.0)).collect()))
+    };
+
+    Ok(CoordinateErrors {
+        random: per_axis("CRDER")?,
+        systematic: per_axis("CSYER")?,
+    })
+}
+
+/// Names of the common coordinate types, the celestial ones being in degrees by default
+const CTYPE_NAMES: &[(&str, &str, Option<&str>)] = &[
+    ("RA", "Right Ascension", Some("deg")),
+    ("DEC", "Declination", Some("deg")),
+    ("GLON", "Galactic Longitude", Some("deg")),
+    ("GLAT", "Galactic Latitude", Some("deg")),
+    ("ELON", "Ecliptic Longitude", Some("deg")),
+    ("ELAT", "Ecliptic Latitude", Some("deg")),
+    ("SLON", "Supergalactic Longitude", Some("deg")),
+    ("SLAT", "Supergalactic Latitude", Some("deg")),
+    ("HPLN", "Helioprojective Longitude", Some("deg")),
+    ("HPLT", "Helioprojective Latitude", Some("deg")),
+    ("FREQ", "Frequency", None),
+    ("ENER", "Energy", None),
+    ("WAVN", "Wavenumber", None),
+    ("VRAD", "Radio Velocity", None),
+    ("WAVE", "Wavelength", None),
+    ("VOPT", "Optical Velocity", None),
+    ("ZOPT", "Redshift", None),
+    ("AWAV", "Air Wavelength", None),
+    ("VELO", "Apparent Radial Velocity", None),
+    ("BETA", "Beta Factor", None),
+    ("STOKES", "Stokes", None),
+    ("TIME", "Time", None),
+];
+
+fn axis_labels(header: &Header<Image>) -> Result<Vec<String>, Error> {
+    (1..=wcs_axes(header)?)
+        .map(|axis| {
+            let ctype = header
+                .get_parsed::<Option<String>>(&format!("CTYPE{axis}"))?
+                .unwrap_or_default();
+            let ctype = ctype.trim();
+            let kind = ctype.split('-').next().unwrap_or(ctype);
+            let cunit = header.get_parsed::<Option<String>>(&format!("CUNIT{axis}"))?;
+
+            let (name, default_unit) = match CTYPE_NAMES.iter().find(|(k, ..)| *k == kind) {
+                Some((_, name, unit)) => (name.to_string(), *unit),
+                None if ctype.is_empty() => (format!("Axis {axis}"), None),
+                None => (ctype.to_owned(), None),
+            };
+
+            let unit = cunit
+                .as_deref()
+                .map(str::trim)
+                .filter(|unit| !unit.is_empty())
+                .or(default_unit);
+            Ok(match unit {
+                Some(unit) => format!("{name} ({unit})"),
+                None => name,
+            })
+        })
+        .collect()
+}
+
+/// Keywords of the cards making a WCS description, the axis numbers being removed
+const ALT_WCS_KEYWORDS: &[&str] = &[
+    "CTYPE", "CUNIT", "CRPIX", "CRVAL", "CDELT", "CROTA", "CD", "PC", "PV", "PS", "LONPOLE",
+    "LATPOLE", "RADESYS", "EQUINOX", "WCSNAME", "RESTFRQ", "RESTWAV", "SPECSYS", "CRDER", "CSYER",
+];
+
+/// Check whether a keyword is part of a WCS description, e.g. `CTYPE1` or `PC1_2`
+fn is_alt_wcs_keyword(key: &str) -> bool {
+    let stem = key.trim_end_matches(|c: char| c.is_ascii_digit() || c == '_');
+    ALT_WCS_KEYWORDS.contains(&stem)
 }
 
 impl<'a> TryFrom<&'a Header<Image>> for WCS {
@@ -27,3 +312,1164 @@ impl<'a> TryFrom<&'a Header<Image>> for WCS {
         WCS::new(&params).map_err(|e| e.into())
     }
 }
+
+/// The maximum order of a SIP polynomial, the `X_p_q` keywords having single digit exponents
+pub const SIP_MAX_ORDER: usize = 9;
+
+/// A SIP polynomial `f(u, v) = sum(X_p_q * u^p * v^q)` for `p + q <= X_ORDER`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SipPolynomial {
+    /// The order of the polynomial given by the `X_ORDER` card
+    order: usize,
+    /// The coefficients `X_p_q` stored at index `p * (order + 1) + q`.
+    /// Coefficients not found in the header are set to 0.
+    coeffs: Box<[f64]>,
+}
+
+impl SipPolynomial {
+    /// Parse the polynomial whose cards are prefixed by `prefix` (i.e. `A`, `B`, `AP` or `BP`)
+    ///
+    /// Returns `None` if the `<prefix>_ORDER` card is not found and an error if the order exceeds
+    /// [SIP_MAX_ORDER]
+    fn parse(header: &Header<Image>, prefix: &str) -> Result<Option<Self>, Error> {
+        let Some(order) = header.get_parsed::<Option<usize>>(&format!("{prefix}_ORDER"))? else {
+            return Ok(None);
+        };
+        if order > SIP_MAX_ORDER {
+            return Err(Error::DynamicError(format!(
+                "{prefix}_ORDER = {order} exceeds the maximum SIP order of {SIP_MAX_ORDER}"
+            )));
+        }
+
+        let mut coeffs = vec![0.0; (order + 1) * (order + 1)];
+        for p in 0..=order {
+            for q in 0..=(order - p) {
+                if let Some(c) = header.get_parsed::<Option<f64>>(&format!("{prefix}_{p}_{q}"))? {
+                    coeffs[p * (order + 1) + q] = c;
+                }
+            }
+        }
+
+        Ok(Some(Self {
+            order,
+            coeffs: coeffs.into_boxed_slice(),
+        }))
+    }
+
+    /// Get the order of the polynomial
+    pub fn get_order(&self) -> usize {
+        self.order
+    }
+
+    /// Get the coefficient `X_p_q`
+    pub fn get_coeff(&self, p: usize, q: usize) -> f64 {
+        if p + q > self.order {
+            0.0
+        } else {
+            self.coeffs[p * (self.order + 1) + q]
+        }
+    }
+
+    /// Evaluate the polynomial at the position `(u, v)`
+    pub fn eval(&self, u: f64, v: f64) -> f64 {
+        let mut res = 0.0;
+        let mut u_p = 1.0;
+        for p in 0..=self.order {
+            let mut v_q = 1.0;
+            for q in 0..=(self.order - p) {
+                res += self.coeffs[p * (self.order + 1) + q] * u_p * v_q;
+                v_q *= v;
+            }
+            u_p *= u;
+        }
+        res
+    }
+
+    /// Evaluate the polynomial and its partial derivatives along `u` and `v` at the position `(u, v)`
+    fn eval_with_derivatives(&self, u: f64, v: f64) -> (f64, f64, f64) {
+        let (mut res, mut d_u, mut d_v) = (0.0, 0.0, 0.0);
+        for p in 0..=self.order {
+            for q in 0..=(self.order - p) {
+                let c = self.coeffs[p * (self.order + 1) + q];
+                if c == 0.0 {
+                    continue;
+                }
+
+                let (p, q) = (p as i32, q as i32);
+                res += c * u.powi(p) * v.powi(q);
+                if p > 0 {
+                    d_u += c * f64::from(p) * u.powi(p - 1) * v.powi(q);
+                }
+                if q > 0 {
+                    d_v += c * f64::from(q) * u.powi(p) * v.powi(q - 1);
+                }
+            }
+        }
+        (res, d_u, d_v)
+    }
+}
+
+/// Simple Imaging Polynomial (SIP) distortion
+///
+/// See "The SIP Convention for Representing Distortion in FITS Image Headers", Shupe et al. (2005).
+/// The distorted pixel coordinates are projected by the [WCS] of the header without the `-SIP` suffix
+/// of its `CTYPEi` cards.
+#[derive(Debug)]
+pub struct Sip {
+    /// The linear part of the WCS, the `CD` matrix applying to the distorted pixel coordinates
+    pub linear: LinearTransform<2>,
+    /// Forward polynomial along the first axis (`A_ORDER`, `A_p_q`)
+    pub a: SipPolynomial,
+    /// Forward polynomial along the second axis (`B_ORDER`, `B_p_q`)
+    pub b: SipPolynomial,
+    /// Inverse polynomial along the first axis (`AP_ORDER`, `AP_p_q`)
+    pub ap: Option<SipPolynomial>,
+    /// Inverse polynomial along the second axis (`BP_ORDER`, `BP_p_q`)
+    pub bp: Option<SipPolynomial>,
+    /// The projection of the distorted pixel coordinates
+    projection: WCS,
+}
+
+impl Sip {
+    /// Parse the SIP coefficients from an image header
+    ///
+    /// Returns `None` if `CTYPE1` does not end with `-SIP`
+    pub fn parse(header: &Header<Image>) -> Result<Option<Self>, Error> {
+        let ctype1 = header.get_parsed::<Option<String>>("CTYPE1")?;
+        if !ctype1.is_some_and(|ctype| ctype.trim_end().ends_with("-SIP")) {
+            return Ok(None);
+        }
+
+        let a = SipPolynomial::parse(header, "A")?
+            .ok_or_else(|| Error::FailFindingKeyword("A_ORDER".to_owned()))?;
+        let b = SipPolynomial::parse(header, "B")?
+            .ok_or_else(|| Error::FailFindingKeyword("B_ORDER".to_owned()))?;
+        let ap = SipPolynomial::parse(header, "AP")?;
+        let bp = SipPolynomial::parse(header, "BP")?;
+        let linear = linear_transform::<2>(header)?;
+        // The SIP polynomials are not given to the wcs crate, which does not evaluate them correctly
+        let projection = wcs_without_sip(header)?;
+
+        Ok(Some(Self {
+            linear,
+            a,
+            b,
+            ap,
+            bp,
+            projection,
+        }))
+    }
+
+    /// Apply the forward distortion to pixel coordinates relative to the reference pixel
+    ///
+    /// Returns `(u + f(u, v), v + g(u, v))`, the coordinates to multiply by the `CD` matrix
+    /// to get the intermediate world coordinates.
+    ///
+    /// # Params
+    /// * `u` - `x - CRPIX1`
+    /// * `v` - `y - CRPIX2`
+    pub fn distort(&self, u: f64, v: f64) -> (f64, f64) {
+        (u + self.a.eval(u, v), v + self.b.eval(u, v))
+    }
+
+    /// Apply the inverse distortion to distorted coordinates relative to the reference pixel
+    ///
+    /// The forward distortion is inverted with the Newton method, starting from
+    /// `(U + AP(U, V), V + BP(U, V))` when the inverse polynomials are given by the header and from
+    /// `(U, V)` otherwise. The iteration stops once the correction is below [SIP_INVERSION_TOLERANCE]
+    /// pixel and `None` is returned if this does not happen within [SIP_INVERSION_MAX_ITERATIONS]
+    /// iterations, e.g. far outside the image where the distortion is not invertible.
+    ///
+    /// # Params
+    /// * `u` - the distorted coordinate `U` along the first axis
+    /// * `v` - the distorted coordinate `V` along the second axis
+    pub fn undistort(&self, u: f64, v: f64) -> Option<(f64, f64)> {
+        let (mut x, mut y) = match (&self.ap, &self.bp) {
+            (Some(ap), Some(bp)) => (u + ap.eval(u, v), v + bp.eval(u, v)),
+            _ => (u, v),
+        };
+
+        for _ in 0..SIP_INVERSION_MAX_ITERATIONS {
+            let (f, f_x, f_y) = self.a.eval_with_derivatives(x, y);
+            let (g, g_x, g_y) = self.b.eval_with_derivatives(x, y);
+            // Solve J * (dx, dy) = r with r the residual of the forward distortion and J its jacobian
+            let (rx, ry) = (x + f - u, y + g - v);
+            let (j11, j12, j21, j22) = (1.0 + f_x, f_y, g_x, 1.0 + g_y);
+            let det = j11 * j22 - j12 * j21;
+            if det == 0.0 {
+                return None;
+            }
+            let dx = (j22 * rx - j12 * ry) / det;
+            let dy = (j11 * ry - j21 * rx) / det;
+
+            (x, y) = (x - dx, y - dy);
+            if dx.hypot(dy) < SIP_INVERSION_TOLERANCE {
+                return Some((x, y));
+            }
+        }
+
+        None
+    }
+
+    /// Get the world coordinates `(lon, lat)` in degrees of a pixel
+    ///
+    /// Returns `None` if the distorted pixel coordinates are outside the domain of the projection.
+    ///
+    /// # Params
+    /// * `x` - the 1-based pixel coordinate along the first axis, following the FITS convention
+    /// * `y` - the 1-based pixel coordinate along the second axis
+    pub fn pixel_to_world(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let crpix = &self.linear.1;
+        let (u, v) = self.distort(x - crpix[0], y - crpix[1]);
+        let lonlat = self
+            .projection
+            .unproj_lonlat(&ImgXY::new(u + crpix[0], v + crpix[1]))?;
+
+        Some((lonlat.lon().to_degrees(), lonlat.lat().to_degrees()))
+    }
+
+    /// Get the 1-based pixel coordinates of world coordinates
+    ///
+    /// The distortion is removed with [Sip::undistort]. Returns `None` if the position cannot be
+    /// projected, e.g. on the hemisphere opposite to the reference point for a `TAN` projection, or
+    /// if the inversion of the distortion does not converge.
+    ///
+    /// # Params
+    /// * `lon` - the longitude in degrees
+    /// * `lat` - the latitude in degrees
+    pub fn world_to_pixel(&self, lon: f64, lat: f64) -> Option<(f64, f64)> {
+        let crpix = &self.linear.1;
+        let img = self
+            .projection
+            .proj_lonlat(&LonLat::new(lon.to_radians(), lat.to_radians()))?;
+
+        let (u, v) = self.undistort(img.x() - crpix[0], img.y() - crpix[1])?;
+        Some((u + crpix[0], v + crpix[1]))
+    }
+}
+
+/// Build the WCS of a header whose `CTYPEi` cards are stripped of their `-SIP` suffix
+fn wcs_without_sip(header: &Header<Image>) -> Result<WCS, Error> {
+    let ctypes = ["CTYPE1", "CTYPE2"]
+        .iter()
+        .filter_map(|&key| {
+            let ctype = header.get_str(key)?.trim_end();
+            let value = Value::String {
+                value: ctype.strip_suffix("-SIP").unwrap_or(ctype).to_owned(),
+                comment: None,
+            };
+            Some((key, value))
+        })
+        .collect::<Vec<_>>();
+
+    let cards = header
+        .iter()
+        .filter(|(key, _)| !ctypes.iter().any(|(ctype, _)| ctype == key))
+        .chain(ctypes.iter().map(|(key, value)| (*key, value)));
+    let params = WCSParams::deserialize(MapDeserializer::<_, Error>::new(cards))?;
+    WCS::new(&params).map_err(|e| e.into())
+}
+
+/// The correction in pixel below which the iterative inversion of the SIP distortion stops
+pub const SIP_INVERSION_TOLERANCE: f64 = 1e-8;
+/// The maximum number of iterations of the iterative inversion of the SIP distortion
+pub const SIP_INVERSION_MAX_ITERATIONS: usize = 20;
+
+/// Gnomonic deprojection of intermediate world coordinates in degrees around the reference point `crval`
+///
+/// Returns `(ra, dec)` in degrees
+fn gnomonic_deprojection(xi: f64, eta: f64, crval: &[f64; 2]) -> (f64, f64) {
+    let (xi, eta) = (xi.to_radians(), eta.to_radians());
+    let (ra0, dec0) = (crval[0].to_radians(), crval[1].to_radians());
+    let (sin_dec0, cos_dec0) = dec0.sin_cos();
+    let den = cos_dec0 - eta * sin_dec0;
+    let ra = ra0 + xi.atan2(den);
+    let dec = (eta * cos_dec0 + sin_dec0).atan2(xi.hypot(den));
+
+    (ra.to_degrees().rem_euclid(360.0), dec.to_degrees())
+}
+
+/// Number of coefficients of a TPV polynomial, i.e. up to the 7th order
+const NUM_TPV_COEFFS: usize = 40;
+
+/// A gnomonic (`TAN`) projection whose intermediate world coordinates are distorted by the TPV polynomials
+///
+/// Contrary to SIP, the distortion is part of the projection: the polynomials given by the `PV1_k` and
+/// `PV2_k` cards are applied to the intermediate world coordinates, in degrees, before the gnomonic
+/// deprojection. See <https://fits.gsfc.nasa.gov/registry/tpvwcs/tpv.html>.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tpv {
+    /// The linear part of the WCS
+    linear: LinearTransform<2>,
+    /// The coefficients `PV1_k` of the polynomial giving `xi`
+    pv1: [f64; NUM_TPV_COEFFS],
+    /// The coefficients `PV2_k` of the polynomial giving `eta`
+    pv2: [f64; NUM_TPV_COEFFS],
+}
+
+impl Tpv {
+    /// Parse the TPV projection from an image header
+    ///
+    /// The coefficients not found in the header are 0, except `PV1_1` and `PV2_1` that are 1 so that
+    /// a header without any `PVi_k` card gives a plain `TAN` projection.
+    /// Returns `None` if `CTYPE1` does not end with `-TPV`
+    pub fn parse(header: &Header<Image>) -> Result<Option<Self>, Error> {
+        let ctype1 = header.get_parsed::<Option<String>>("CTYPE1")?;
+        if !ctype1.is_some_and(|ctype| ctype.trim_end().ends_with("-TPV")) {
+            return Ok(None);
+        }
+
+        let linear = linear_transform::<2>(header)?;
+        let (mut pv1, mut pv2) = ([0.0; NUM_TPV_COEFFS], [0.0; NUM_TPV_COEFFS]);
+        (pv1[1], pv2[1]) = (1.0, 1.0);
+        for ((i, k), v) in pv_params(header)? {
+            match i {
+                1 if k < NUM_TPV_COEFFS => pv1[k] = v,
+                2 if k < NUM_TPV_COEFFS => pv2[k] = v,
+                _ => (),
+            }
+        }
+
+        Ok(Some(Self { linear, pv1, pv2 }))
+    }
+
+    /// Apply the TPV polynomials to intermediate world coordinates
+    ///
+    /// Returns the distorted `(xi, eta)` in degrees
+    ///
+    /// # Params
+    /// * `x` - the intermediate world coordinate along the first axis in degrees
+    /// * `y` - the intermediate world coordinate along the second axis in degrees
+    pub fn distort(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            tpv_polynomial(&self.pv1, x, y),
+            tpv_polynomial(&self.pv2, y, x),
+        )
+    }
+
+    /// Get the world coordinates `(ra, dec)` in degrees of a pixel
+    ///
+    /// # Params
+    /// * `x` - the 1-based pixel coordinate along the first axis, following the FITS convention
+    /// * `y` - the 1-based pixel coordinate along the second axis
+    pub fn pixel_to_world(&self, x: f64, y: f64) -> (f64, f64) {
+        let (matrix, crpix, crval) = &self.linear;
+        let (u, v) = (x - crpix[0], y - crpix[1]);
+        let (xi, eta) = self.distort(
+            matrix[0][0] * u + matrix[0][1] * v,
+            matrix[1][0] * u + matrix[1][1] * v,
+        );
+
+        gnomonic_deprojection(xi, eta, crval)
+    }
+}
+
+/// Evaluate a TPV polynomial, the terms of `eta` being obtained by swapping `x` and `y`
+fn tpv_polynomial(pv: &[f64; NUM_TPV_COEFFS], x: f64, y: f64) -> f64 {
+    let r = x.hypot(y);
+    let mut res = pv[0];
+    let mut k = 1;
+    for order in 1..=7 {
+        for p in (0..=order).rev() {
+            res += pv[k] * x.powi(p) * y.powi(order - p);
+            k += 1;
+        }
+        // The odd orders have a radial term
+        if order % 2 == 1 {
+            res += pv[k] * r.powi(order);
+            k += 1;
+        }
+    }
+    res
+}
+
+/// Spectral coordinate types defined in "Representations of spectral coordinates in FITS",
+/// Greisen et al. (2006)
+const SPECTRAL_CTYPES: &[&str] = &[
+    "FREQ", "ENER", "WAVN", "VRAD", "WAVE", "VOPT", "ZOPT", "AWAV", "VELO", "BETA",
+];
+
+/// A linear spectral axis, e.g. a linearly sampled spectrum
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectralWcs {
+    /// The index of the axis starting from 1
+    axis: usize,
+    /// The spectral coordinate type, e.g. `FREQ` or `WAVE`
+    ctype: String,
+    /// The unit of the spectral coordinate given by `CUNITi`
+    cunit: Option<String>,
+    /// The spectral coordinate of the reference pixel
+    crval: f64,
+    /// The reference pixel
+    crpix: f64,
+    /// The spectral increment per pixel
+    cdelt: f64,
+}
+
+impl SpectralWcs {
+    /// Parse the first spectral axis of an image header
+    pub fn parse(header: &Header<Image>) -> Result<Self, Error> {
+        for axis in 1..=wcs_axes(header)? {
+            let Some(ctype) = header.get_str(&format!("CTYPE{axis}")) else {
+                continue;
+            };
+            let ctype = ctype.trim_end();
+            let (kind, algorithm) = ctype.split_once('-').unwrap_or((ctype, ""));
+            if !SPECTRAL_CTYPES.contains(&kind) {
+                continue;
+            }
+
+            if !algorithm.trim_start_matches('-').is_empty() {
+                return Err(Error::DynamicError(format!(
+                    "Non-linear spectral axis '{ctype}' is not supported"
+                )));
+            }
+
+            let crval = header
+                .get_parsed::<Option<f64>>(&format!("CRVAL{axis}"))?
+                .unwrap_or(0.0);
+            let crpix = header
+                .get_parsed::<Option<f64>>(&format!("CRPIX{axis}"))?
+                .unwrap_or(0.0);
+            let cdelt =
+                if let Some(cdelt) = header.get_parsed::<Option<f64>>(&format!("CDELT{axis}"))? {
+                    cdelt
+                } else {
+                    header
+                        .get_parsed::<Option<f64>>(&format!("CD{axis}_{axis}"))?
+                        .unwrap_or(1.0)
+                };
+            let cunit = header
+                .get_str(&format!("CUNIT{axis}"))
+                .map(|unit| unit.trim_end().to_owned());
+
+            return Ok(Self {
+                axis,
+                ctype: kind.to_owned(),
+                cunit,
+                crval,
+                crpix,
+                cdelt,
+            });
+        }
+
+        Err(Error::StaticError("No spectral axis has been found"))
+    }
+
+    /// Get the index of the spectral axis starting from 1
+    pub fn get_axis(&self) -> usize {
+        self.axis
+    }
+
+    /// Get the spectral coordinate type, e.g. `FREQ` or `WAVE`
+    pub fn get_ctype(&self) -> &str {
+        &self.ctype
+    }
+
+    /// Get the unit of the spectral coordinate given by the `CUNITi` card, e.g. `Hz` or `m`
+    pub fn get_unit(&self) -> Option<&str> {
+        self.cunit.as_deref()
+    }
+
+    /// Compute the spectral coordinate of a pixel
+    ///
+    /// # Params
+    /// * `px` - the pixel coordinate along the spectral axis, the center of the first pixel being 1
+    pub fn pixel_to_spectral(&self, px: f64) -> f64 {
+        self.crval + self.cdelt * (px - self.crpix)
+    }
+
+    /// Compute the pixel coordinate of a spectral coordinate
+    ///
+    /// # Params
+    /// * `value` - the spectral coordinate expressed in the unit of the axis
+    pub fn spectral_to_pixel(&self, value: f64) -> f64 {
+        self.crpix + (value - self.crval) / self.cdelt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        axis_labels, coordinate_errors, linear_transform, pv_params, wcs_axes, CoordinateErrors,
+        ImgXY, Sip, SpectralWcs, Tpv, WCS,
+    };
+    use crate::card::CardBuf;
+
+    use crate::test_utils::{first_primary, mock_hdu, parse_header};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn sip_forward_distortion() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                 4096                                                  ",
+            b"NAXIS2  =                 4096                                                  ",
+            b"CTYPE1  = 'RA---TAN-SIP'                                                        ",
+            b"CTYPE2  = 'DEC--TAN-SIP'                                                        ",
+            b"CRPIX1  =               2048.0                                                  ",
+            b"CRPIX2  =               2048.0                                                  ",
+            b"A_ORDER =                    2                                                  ",
+            b"A_0_2   =   2.516256421752E-06                                                  ",
+            b"A_1_1   =  -1.045457516913E-05                                                  ",
+            b"A_2_0   =   8.542373632426E-06                                                  ",
+            b"B_ORDER =                    2                                                  ",
+            b"B_0_2   =   1.210112437439E-05                                                  ",
+            b"B_1_1   =  -2.988219186258E-06                                                  ",
+            b"B_2_0   =   8.000000000000E-07                                                  ",
+            b"END                                                                             ",
+        ]);
+        let sip = Sip::parse(&header).unwrap().unwrap();
+
+        assert_eq!(sip.a.get_order(), 2);
+        assert_eq!(sip.a.get_coeff(1, 1), -1.045457516913E-05);
+        assert_eq!(sip.a.get_coeff(0, 0), 0.0);
+        assert!(sip.ap.is_none() && sip.bp.is_none());
+
+        let (u, v) = (1000.0, -500.0);
+        let (up, vp) = sip.distort(u, v);
+        // f = A_0_2 * v^2 + A_1_1 * u * v + A_2_0 * u^2
+        let f = 2.516256421752E-06 * 250_000.0
+            + 1.045457516913E-05 * 500_000.0
+            + 8.542373632426E-06 * 1_000_000.0;
+        // g = B_0_2 * v^2 + B_1_1 * u * v + B_2_0 * u^2
+        let g =
+            1.210112437439E-05 * 250_000.0 + 2.988219186258E-06 * 500_000.0 + 8.0E-07 * 1_000_000.0;
+        assert!((up - (u + f)).abs() < 1e-9);
+        assert!((vp - (v + g)).abs() < 1e-9);
+        assert!((up - 1014.3987).abs() < 1e-3);
+        assert!((vp - (-494.6806)).abs() < 1e-3);
+
+        // An order beyond the maximum is rejected before allocating the coefficients
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"CTYPE1  = 'RA---TAN-SIP'                                                        ",
+            b"A_ORDER =           1000000000                                                  ",
+            b"B_ORDER =                    2                                                  ",
+            b"END                                                                             ",
+        ]);
+        assert!(Sip::parse(&header).is_err());
+    }
+
+    #[test]
+    fn sip_round_trip() {
+        let cards: [&CardBuf; 20] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                 4096                                                  ",
+            b"NAXIS2  =                 4096                                                  ",
+            b"CTYPE1  = 'RA---TAN-SIP'                                                        ",
+            b"CTYPE2  = 'DEC--TAN-SIP'                                                        ",
+            b"CRPIX1  =               2048.0                                                  ",
+            b"CRPIX2  =               2048.0                                                  ",
+            b"CRVAL1  =                 83.6                                                  ",
+            b"CRVAL2  =                 22.0                                                  ",
+            b"CD1_1   =             -2.8E-04                                                  ",
+            b"CD1_2   =              1.0E-06                                                  ",
+            b"CD2_1   =              1.0E-06                                                  ",
+            b"CD2_2   =              2.8E-04                                                  ",
+            b"A_ORDER =                    2                                                  ",
+            b"A_1_1   =  -1.045457516913E-06                                                  ",
+            b"A_2_0   =   8.542373632426E-07                                                  ",
+            b"B_ORDER =                    2                                                  ",
+            b"B_0_2   =   1.210112437439E-06                                                  ",
+        ];
+        let end: &CardBuf =
+            b"END                                                                             ";
+        let inverse: [&CardBuf; 5] = [
+            b"AP_ORDER=                    2                                                  ",
+            b"AP_1_1  =   1.045457516913E-06                                                  ",
+            b"AP_2_0  =  -8.542373632426E-07                                                  ",
+            b"BP_ORDER=                    2                                                  ",
+            b"BP_0_2  =  -1.210112437439E-06                                                  ",
+        ];
+
+        // The reference point itself fails a debug assertion of the projection of the mapproj crate
+        let pixels = [
+            (2048.5, 2047.5),
+            (1.0, 1.0),
+            (3000.5, 1200.25),
+            (4096.0, 10.0),
+        ];
+
+        // Without AP/BP, the forward distortion is inverted iteratively
+        let mut header = cards.to_vec();
+        header.push(end);
+        let sip = Sip::parse(&parse_header(&header)).unwrap().unwrap();
+        assert!(sip.ap.is_none());
+        for &(x, y) in &pixels {
+            let (ra, dec) = sip.pixel_to_world(x, y).unwrap();
+            let (px, py) = sip.world_to_pixel(ra, dec).unwrap();
+            assert!((px - x).abs() < 1e-6 && (py - y).abs() < 1e-6);
+        }
+        let (ra, dec) = sip.pixel_to_world(2048.0, 2048.0).unwrap();
+        assert!((ra - 83.6).abs() < 1e-12 && (dec - 22.0).abs() < 1e-12);
+        assert!(sip.world_to_pixel(250.0, -30.0).is_none());
+
+        // The inverse polynomials give the starting point of the iteration
+        let mut header = cards.to_vec();
+        header.extend(inverse);
+        header.push(end);
+        let sip = Sip::parse(&parse_header(&header)).unwrap().unwrap();
+        assert!(sip.ap.is_some() && sip.bp.is_some());
+        for &(x, y) in &pixels {
+            let (ra, dec) = sip.pixel_to_world(x, y).unwrap();
+            let (px, py) = sip.world_to_pixel(ra, dec).unwrap();
+            assert!((px - x).abs() < 1e-6 && (py - y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn sip_strong_distortion() {
+        // The derivative of the distortion exceeds 1 at the edges, where a fixed point iteration diverges
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                 4096                                                  ",
+            b"NAXIS2  =                 4096                                                  ",
+            b"CTYPE1  = 'RA---TAN-SIP'                                                        ",
+            b"CTYPE2  = 'DEC--TAN-SIP'                                                        ",
+            b"CRPIX1  =               2048.0                                                  ",
+            b"CRPIX2  =               2048.0                                                  ",
+            b"CRVAL1  =                 83.6                                                  ",
+            b"CRVAL2  =                 22.0                                                  ",
+            b"CD1_1   =             -2.8E-05                                                  ",
+            b"CD2_2   =              2.8E-05                                                  ",
+            b"A_ORDER =                    2                                                  ",
+            b"A_2_0   =               5.0E-4                                                  ",
+            b"A_1_1   =               1.0E-4                                                  ",
+            b"B_ORDER =                    2                                                  ",
+            b"B_0_2   =               4.0E-4                                                  ",
+            b"END                                                                             ",
+        ]);
+        let sip = Sip::parse(&header).unwrap().unwrap();
+
+        for (x, y) in [
+            (4096.0, 4096.0),
+            (4000.0, 2500.0),
+            (3000.0, 1500.0),
+            (2050.0, 2040.0),
+        ] {
+            let (u, v) = (x - 2048.0, y - 2048.0);
+            let (du, dv) = sip.distort(u, v);
+            let (ru, rv) = sip.undistort(du, dv).unwrap();
+            assert!((ru - u).abs() < 1e-6 && (rv - v).abs() < 1e-6);
+
+            let (ra, dec) = sip.pixel_to_world(x, y).unwrap();
+            let (px, py) = sip.world_to_pixel(ra, dec).unwrap();
+            assert!((px - x).abs() < 1e-6 && (py - y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn sip_projection_agrees_with_wcs() {
+        let cards: [&CardBuf; 18] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                 2000                                                  ",
+            b"NAXIS2  =                 2000                                                  ",
+            b"CTYPE1  = 'RA---SIN-SIP'                                                        ",
+            b"CTYPE2  = 'DEC--SIN-SIP'                                                        ",
+            b"CRPIX1  =               1000.5                                                  ",
+            b"CRPIX2  =               1000.5                                                  ",
+            b"CRVAL1  =                210.8                                                  ",
+            b"CRVAL2  =                 54.3                                                  ",
+            b"CDELT1  =                -0.01                                                  ",
+            b"CDELT2  =                 0.01                                                  ",
+            b"PC1_2   =                 0.03                                                  ",
+            b"PC2_1   =                -0.03                                                  ",
+            b"A_ORDER =                    2                                                  ",
+            b"B_ORDER =                    2                                                  ",
+            b"END                                                                             ",
+        ];
+        let pixels = [
+            (1.0, 1.0),
+            (1000.5, 1000.5),
+            (1500.0, 200.0),
+            (2000.0, 2000.0),
+        ];
+
+        // Without distortion, the SIP header gives the world coordinates of the WCS
+        let header = parse_header(&cards);
+        let sip = Sip::parse(&header).unwrap().unwrap();
+        let wcs = WCS::try_from(&header).unwrap();
+        for &(x, y) in &pixels {
+            let (ra, dec) = sip.pixel_to_world(x, y).unwrap();
+            let lonlat = wcs.unproj_lonlat(&ImgXY::new(x, y)).unwrap();
+            assert!((ra - lonlat.lon().to_degrees()).abs() < 1e-9);
+            assert!((dec - lonlat.lat().to_degrees()).abs() < 1e-9);
+        }
+
+        // The distorted pixel coordinates are projected by the WCS without the SIP suffix
+        let mut header = parse_header(&cards);
+        for (key, value) in [("A_2_0", 2e-6), ("A_0_2", -1e-6), ("B_1_1", 3e-6)] {
+            let value = crate::card::Value::Float {
+                value,
+                comment: None,
+            };
+            header.push(key, value).unwrap();
+        }
+        let sip = Sip::parse(&header).unwrap().unwrap();
+        let mut sin_cards = cards;
+        sin_cards[5] =
+            b"CTYPE1  = 'RA---SIN'                                                            ";
+        sin_cards[6] =
+            b"CTYPE2  = 'DEC--SIN'                                                            ";
+        let sin = WCS::try_from(&parse_header(&sin_cards)).unwrap();
+        for &(x, y) in &pixels {
+            let (u, v) = sip.distort(x - 1000.5, y - 1000.5);
+            let (ra, dec) = sip.pixel_to_world(x, y).unwrap();
+            let lonlat = sin
+                .unproj_lonlat(&ImgXY::new(u + 1000.5, v + 1000.5))
+                .unwrap();
+            assert!((ra - lonlat.lon().to_degrees()).abs() < 1e-9);
+            assert!((dec - lonlat.lat().to_degrees()).abs() < 1e-9);
+
+            let img = sin.proj_lonlat(&lonlat).unwrap();
+            let (px, py) = sip.world_to_pixel(ra, dec).unwrap();
+            let (pu, pv) = sip.distort(px - 1000.5, py - 1000.5);
+            assert!((pu + 1000.5 - img.x()).abs() < 1e-6 && (pv + 1000.5 - img.y()).abs() < 1e-6);
+            assert!((px - x).abs() < 1e-6 && (py - y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn tpv_projection() {
+        let cards: [&CardBuf; 14] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                 2048                                                  ",
+            b"NAXIS2  =                 4096                                                  ",
+            b"CTYPE1  = 'RA---TPV'                                                            ",
+            b"CTYPE2  = 'DEC--TPV'                                                            ",
+            b"CRPIX1  =               1024.5                                                  ",
+            b"CRPIX2  =               2048.5                                                  ",
+            b"CRVAL1  =            150.11916                                                  ",
+            b"CRVAL2  =             2.205833                                                  ",
+            b"CD1_1   =          -7.5038E-05                                                  ",
+            b"CD2_2   =           7.5038E-05                                                  ",
+            b"END                                                                             ",
+        ];
+
+        // Without PV cards, the projection is a plain TAN one
+        let tpv = Tpv::parse(&parse_header(&cards)).unwrap().unwrap();
+        let mut tan_cards = cards;
+        tan_cards[5] =
+            b"CTYPE1  = 'RA---TAN'                                                            ";
+        tan_cards[6] =
+            b"CTYPE2  = 'DEC--TAN'                                                            ";
+        let tan = WCS::try_from(&parse_header(&tan_cards)).unwrap();
+        assert!(Tpv::parse(&parse_header(&tan_cards)).unwrap().is_none());
+
+        assert_eq!(tpv.pixel_to_world(1024.5, 2048.5), (150.11916, 2.205833));
+        for (x, y) in [(1.0, 1.0), (2048.0, 4096.0), (300.0, 3500.0)] {
+            let (ra, dec) = tpv.pixel_to_world(x, y);
+            let lonlat = tan.unproj_lonlat(&ImgXY::new(x, y)).unwrap();
+            assert!((ra - lonlat.lon().to_degrees()).abs() < 1e-9);
+            assert!((dec - lonlat.lat().to_degrees()).abs() < 1e-9);
+        }
+
+        // Distortion with a constant, linear, radial and quadratic terms
+        let mut header = parse_header(&cards);
+        for (key, value) in [
+            ("PV1_0", 1e-4),
+            ("PV1_1", 1.001),
+            ("PV1_3", 0.5),
+            ("PV1_4", 0.02),
+            ("PV2_1", 0.999),
+            ("PV2_5", -0.03),
+        ] {
+            let value = crate::card::Value::Float {
+                value,
+                comment: None,
+            };
+            header.push(key, value).unwrap();
+        }
+        let tpv = Tpv::parse(&header).unwrap().unwrap();
+        let (x, y) = (0.05_f64, -0.08_f64);
+        let (xi, eta) = tpv.distort(x, y);
+        let r = x.hypot(y);
+        assert!((xi - (1e-4 + 1.001 * x + 0.5 * r + 0.02 * x * x)).abs() < 1e-15);
+        assert!((eta - (0.999 * y - 0.03 * y * x)).abs() < 1e-15);
+
+        // The world coordinates of the distorted position are the TAN ones of a shifted pixel
+        let (ra, dec) = tpv.pixel_to_world(1024.5, 2048.5);
+        let lonlat = tan
+            .unproj_lonlat(&ImgXY::new(1024.5 - 1e-4 / 7.5038E-05, 2048.5))
+            .unwrap();
+        assert!((ra - lonlat.lon().to_degrees()).abs() < 1e-9);
+        assert!((dec - lonlat.lat().to_degrees()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tpv_scamp_header() {
+        // Distortion terms up to the 7th order, with the radial ones, in the form written by SCAMP
+        let cards: [&CardBuf; 48] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                 4096                                                  ",
+            b"NAXIS2  =                 4176                                                  ",
+            b"CTYPE1  = 'RA---TPV'           / WCS projection type for this axis              ",
+            b"CTYPE2  = 'DEC--TPV'           / WCS projection type for this axis              ",
+            b"CRVAL1  =   1.501191000000E+02 / World coordinate on this axis                  ",
+            b"CRVAL2  =   2.205800000000E+00 / World coordinate on this axis                  ",
+            b"CRPIX1  =   2.048500000000E+03 / Reference pixel on this axis                   ",
+            b"CRPIX2  =   2.088500000000E+03 / Reference pixel on this axis                   ",
+            b"CD1_1   =  -5.158700000000E-05 / Linear projection matrix                       ",
+            b"CD1_2   =   1.200000000000E-07 / Linear projection matrix                       ",
+            b"CD2_1   =  -9.800000000000E-08 / Linear projection matrix                       ",
+            b"CD2_2   =   5.159100000000E-05 / Linear projection matrix                       ",
+            b"PV1_0   =   1.200000000000E-05 / Projection distortion parameter                ",
+            b"PV1_1   =   1.000172000000E+00 / Projection distortion parameter                ",
+            b"PV1_2   =   2.300000000000E-04 / Projection distortion parameter                ",
+            b"PV1_4   =  -3.100000000000E-04 / Projection distortion parameter                ",
+            b"PV1_5   =   5.200000000000E-04 / Projection distortion parameter                ",
+            b"PV1_6   =  -1.700000000000E-04 / Projection distortion parameter                ",
+            b"PV1_7   =  -1.150000000000E-02 / Projection distortion parameter                ",
+            b"PV1_8   =   4.100000000000E-03 / Projection distortion parameter                ",
+            b"PV1_9   =  -9.600000000000E-03 / Projection distortion parameter                ",
+            b"PV1_10  =   2.200000000000E-03 / Projection distortion parameter                ",
+            b"PV1_11  =   3.000000000000E-03 / Projection distortion parameter                ",
+            b"PV1_17  =   8.000000000000E-01 / Projection distortion parameter                ",
+            b"PV1_19  =  -6.000000000000E-01 / Projection distortion parameter                ",
+            b"PV1_23  =   4.000000000000E-01 / Projection distortion parameter                ",
+            b"PV1_31  =   2.500000000000E+01 / Projection distortion parameter                ",
+            b"PV1_39  =  -1.200000000000E+01 / Projection distortion parameter                ",
+            b"PV2_0   =  -8.000000000000E-06 / Projection distortion parameter                ",
+            b"PV2_1   =   9.998610000000E-01 / Projection distortion parameter                ",
+            b"PV2_2   =  -1.900000000000E-04 / Projection distortion parameter                ",
+            b"PV2_4   =   2.700000000000E-04 / Projection distortion parameter                ",
+            b"PV2_5   =  -4.400000000000E-04 / Projection distortion parameter                ",
+            b"PV2_6   =   1.300000000000E-04 / Projection distortion parameter                ",
+            b"PV2_7   =  -1.080000000000E-02 / Projection distortion parameter                ",
+            b"PV2_8   =  -3.300000000000E-03 / Projection distortion parameter                ",
+            b"PV2_9   =  -9.900000000000E-03 / Projection distortion parameter                ",
+            b"PV2_10  =   1.600000000000E-03 / Projection distortion parameter                ",
+            b"PV2_11  =   2.000000000000E-03 / Projection distortion parameter                ",
+            b"PV2_18  =  -5.000000000000E-01 / Projection distortion parameter                ",
+            b"PV2_22  =   7.000000000000E-01 / Projection distortion parameter                ",
+            b"PV2_23  =  -3.000000000000E-01 / Projection distortion parameter                ",
+            b"PV2_33  =   1.800000000000E+01 / Projection distortion parameter                ",
+            b"PV2_39  =   9.000000000000E+00 / Projection distortion parameter                ",
+            b"END                                                                             ",
+        ];
+        let tpv = Tpv::parse(&parse_header(&cards)).unwrap().unwrap();
+
+        // Reference values evaluated with the terms listed term by term in the TPV registry, followed
+        // by the zenithal projection and spherical rotation of Calabretta & Greisen (2002)
+        for ((x, y), (ra, dec)) in [
+            ((1.0, 1.0), (150.22453162612868, 2.098319160304062)),
+            ((4096.0, 4176.0), (150.01369484209954, 2.3132752685531908)),
+            ((1.0, 4176.0), (150.22512513087142, 2.3136260854815918)),
+            ((3000.0, 500.0), (150.06978311648382, 2.1237764375010175)),
+            ((2048.5, 2088.5), (150.1191120088982, 2.20579199999995)),
+        ] {
+            let world = tpv.pixel_to_world(x, y);
+            assert!((world.0 - ra).abs() < 1e-10);
+            assert!((world.1 - dec).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn alternate_wcs() {
+        let cards: [&CardBuf; 19] = [
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                  100                                                  ",
+            b"NAXIS2  =                  100                                                  ",
+            b"CTYPE1  = 'RA---TAN'                                                            ",
+            b"CTYPE2  = 'DEC--TAN'                                                            ",
+            b"CRPIX1  =                 50.0                                                  ",
+            b"CRPIX2  =                 50.0                                                  ",
+            b"CRVAL1  =                 10.0                                                  ",
+            b"CRVAL2  =                 20.0                                                  ",
+            b"CDELT1  =                -0.01                                                  ",
+            b"CDELT2  =                 0.01                                                  ",
+            b"CTYPE1A = 'RA---CAR'                                                            ",
+            b"CTYPE2A = 'DEC--CAR'                                                            ",
+            b"CRPIX1A =                  1.0                                                  ",
+            b"CRPIX2A =                  1.0                                                  ",
+            b"CRVAL1A =                120.0                                                  ",
+            b"END                                                                             ",
+        ];
+        let buf = mock_hdu(&cards, &[0; 100 * 100 * 4]);
+
+        let (_, hdu) = first_primary(&buf);
+        assert_eq!(hdu.wcs_alts(), ['A']);
+
+        // The alternate description does not inherit the default CRVAL2 and CDELT
+        let xy = ImgXY::new(0.0, 0.0);
+        let lonlat = hdu.wcs().unwrap().unproj_lonlat(&xy).unwrap();
+        let lonlat_alt = hdu.wcs_alt('A').unwrap().unproj_lonlat(&xy).unwrap();
+        assert!((lonlat_alt.lon().to_degrees() - 119.0).abs() < 1e-9);
+        assert!((lonlat_alt.lat().to_degrees() + 1.0).abs() < 1e-9);
+        assert!((lonlat.lon().to_degrees() - 10.53).abs() < 1e-2);
+
+        assert!(hdu.wcs_alt('B').is_err());
+        assert!(hdu.wcs_alt('a').is_err());
+    }
+
+    #[test]
+    fn linear_transform_matrix() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                  100                                                  ",
+            b"NAXIS2  =                  100                                                  ",
+            b"CRPIX1  =                 50.0                                                  ",
+            b"CRPIX2  =                 60.0                                                  ",
+            b"CRVAL1  =                 10.0                                                  ",
+            b"CRVAL2  =                 20.0                                                  ",
+            b"CDELT1  =                 -2.0                                                  ",
+            b"CDELT2  =                  3.0                                                  ",
+            b"PC1_2   =                  0.5                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (matrix, crpix, crval) = linear_transform::<2>(&header).unwrap();
+        assert_eq!(matrix, [[-2.0, -1.0], [0.0, 3.0]]);
+        assert_eq!((crpix, crval), ([50.0, 60.0], [10.0, 20.0]));
+
+        // The CD matrix takes precedence and its missing elements are zero
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                  100                                                  ",
+            b"NAXIS2  =                  100                                                  ",
+            b"CDELT1  =                 -2.0                                                  ",
+            b"CD1_1   =                 -0.1                                                  ",
+            b"CD2_2   =                  0.2                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (matrix, crpix, _) = linear_transform::<2>(&header).unwrap();
+        assert_eq!(matrix, [[-0.1, 0.0], [0.0, 0.2]]);
+        assert_eq!(crpix, [0.0, 0.0]);
+
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                  100                                                  ",
+            b"NAXIS2  =                  100                                                  ",
+            b"CDELT1  =                 -1.0                                                  ",
+            b"CDELT2  =                  1.0                                                  ",
+            b"CROTA2  =                 90.0                                                  ",
+            b"END                                                                             ",
+        ]);
+        let (matrix, ..) = linear_transform::<2>(&header).unwrap();
+        let expected = [[0.0, -1.0], [-1.0, 0.0]];
+        for (row, expected) in matrix.iter().zip(expected) {
+            for (v, e) in row.iter().zip(expected) {
+                assert!((v - e).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn projection_parameters() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                  100                                                  ",
+            b"NAXIS2  =                  100                                                  ",
+            b"CTYPE1  = 'RA---ZPN'                                                            ",
+            b"CTYPE2  = 'DEC--ZPN'                                                            ",
+            b"PV2_0   =                  0.0                                                  ",
+            b"PV2_1   =                  1.0                                                  ",
+            b"PV2_3   =                220.0                                                  ",
+            b"PV1_0   =                    1                                                  ",
+            b"PVNAME  = 'not a parameter'                                                     ",
+            b"END                                                                             ",
+        ]);
+        let params = pv_params(&header).unwrap();
+
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[&(2, 1)], 1.0);
+        assert_eq!(params[&(2, 3)], 220.0);
+        assert_eq!(params[&(1, 0)], 1.0);
+        assert_eq!(params.get(&(2, 2)), None);
+    }
+
+    #[test]
+    fn linear_spectral_axis() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    1                                                  ",
+            b"NAXIS1  =                 2048                                                  ",
+            b"CTYPE1  = 'WAVE    '                                                            ",
+            b"CUNIT1  = 'Angstrom'                                                            ",
+            b"CRVAL1  =               3800.0                                                  ",
+            b"CRPIX1  =                  1.0                                                  ",
+            b"CDELT1  =                  2.5                                                  ",
+            b"END                                                                             ",
+        ]);
+        let spectral = SpectralWcs::parse(&header).unwrap();
+
+        assert_eq!(spectral.get_axis(), 1);
+        assert_eq!(spectral.get_ctype(), "WAVE");
+        assert_eq!(spectral.get_unit(), Some("Angstrom"));
+        assert_eq!(spectral.pixel_to_spectral(1.0), 3800.0);
+        assert_eq!(spectral.pixel_to_spectral(101.0), 4050.0);
+        assert_eq!(spectral.spectral_to_pixel(4050.0), 101.0);
+    }
+
+    #[test]
+    fn spectral_axis_of_cube() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    3                                                  ",
+            b"NAXIS1  =                   64                                                  ",
+            b"NAXIS2  =                   64                                                  ",
+            b"NAXIS3  =                  128                                                  ",
+            b"CTYPE1  = 'RA---SIN'                                                            ",
+            b"CTYPE2  = 'DEC--SIN'                                                            ",
+            b"CTYPE3  = 'FREQ'                                                                ",
+            b"CRVAL3  =          1.420405E09                                                  ",
+            b"CRPIX3  =                 64.0                                                  ",
+            b"CDELT3  =             -10000.0                                                  ",
+            b"END                                                                             ",
+        ]);
+        let spectral = SpectralWcs::parse(&header).unwrap();
+
+        assert_eq!(spectral.get_axis(), 3);
+        assert_eq!(spectral.get_unit(), None);
+        assert_eq!(spectral.pixel_to_spectral(65.0), 1.420395E09);
+    }
+
+    #[test]
+    fn degenerate_spectral_axis() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                   64                                                  ",
+            b"NAXIS2  =                   64                                                  ",
+            b"WCSAXES =                    3                                                  ",
+            b"CTYPE1  = 'RA---SIN'                                                            ",
+            b"CTYPE2  = 'DEC--SIN'                                                            ",
+            b"CTYPE3  = 'FREQ'                                                                ",
+            b"CRVAL3  =          1.420405E09                                                  ",
+            b"END                                                                             ",
+        ]);
+        assert_eq!(wcs_axes(&header).unwrap(), 3);
+        let spectral = SpectralWcs::parse(&header).unwrap();
+        assert_eq!(spectral.get_axis(), 3);
+        assert_eq!(spectral.pixel_to_spectral(0.0), 1.420405E09);
+
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                   64                                                  ",
+            b"NAXIS2  =                   64                                                  ",
+            b"CTYPE3  = 'FREQ'                                                                ",
+            b"END                                                                             ",
+        ]);
+        assert_eq!(wcs_axes(&header).unwrap(), 2);
+        assert!(SpectralWcs::parse(&header).is_err());
+    }
+
+    #[test]
+    fn labels_of_axes() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    3                                                  ",
+            b"NAXIS1  =                   64                                                  ",
+            b"NAXIS2  =                   64                                                  ",
+            b"NAXIS3  =                  128                                                  ",
+            b"WCSAXES =                    5                                                  ",
+            b"CTYPE1  = 'RA---TAN'                                                            ",
+            b"CTYPE2  = 'DEC--TAN'                                                            ",
+            b"CTYPE3  = 'WAVE'                                                                ",
+            b"CUNIT3  = 'nm      '                                                            ",
+            b"CTYPE4  = 'CUSTOM'                                                              ",
+            b"END                                                                             ",
+        ]);
+
+        assert_eq!(
+            axis_labels(&header).unwrap(),
+            [
+                "Right Ascension (deg)",
+                "Declination (deg)",
+                "Wavelength (nm)",
+                "CUSTOM",
+                "Axis 5"
+            ]
+        );
+    }
+
+    #[test]
+    fn random_and_systematic_errors() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    2                                                  ",
+            b"NAXIS1  =                   64                                                  ",
+            b"NAXIS2  =                   64                                                  ",
+            b"CTYPE1  = 'RA---TAN'                                                            ",
+            b"CTYPE2  = 'DEC--TAN'                                                            ",
+            b"CRDER1  =               2.5E-5 / [deg] random error                             ",
+            b"CRDER2  =               3.0E-5 / [deg] random error                             ",
+            b"CSYER2  =               1.0E-4 / [deg] systematic error                         ",
+            b"END                                                                             ",
+        ]);
+        assert_eq!(
+            coordinate_errors(&header).unwrap(),
+            CoordinateErrors {
+                random: Some(vec![2.5e-5, 3.0e-5]),
+                systematic: Some(vec![0.0, 1.0e-4]),
+            }
+        );
+
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    1                                                  ",
+            b"NAXIS1  =                   64                                                  ",
+            b"CSYER1  = 'large'                                                               ",
+            b"END                                                                             ",
+        ]);
+        assert!(coordinate_errors(&header).is_err());
+    }
+
+    #[test]
+    fn non_linear_spectral_axis() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    1                                                  ",
+            b"NAXIS1  =                 2048                                                  ",
+            b"CTYPE1  = 'WAVE-F2W'                                                            ",
+            b"END                                                                             ",
+        ]);
+        assert!(SpectralWcs::parse(&header).is_err());
+    }
+
+    #[test]
+    fn no_sip_distortion() {
+        let header = parse_header(&[
+            b"SIMPLE  =                    T                                                  ",
+            b"BITPIX  =                  -32                                                  ",
+            b"NAXIS   =                    0                                                  ",
+            b"CTYPE1  = 'RA---TAN'                                                            ",
+            b"A_ORDER =                    2                                                  ",
+            b"END                                                                             ",
+        ]);
+        assert!(Sip::parse(&header).unwrap().is_none());
+    }
+}